@@ -1,12 +1,16 @@
 use crate::board::BitBoard;
 use crate::player::{Entry, NodeType, Player};
 use fxhash::FxHashMap;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering as AtomicOrdering};
 use std::sync::Arc;
 
-// 置換表の設定を最適化
-const MAX_TT_SIZE: usize = 2_000_000; // 適切なサイズに調整
-const TT_CLEANUP_THRESHOLD: usize = 1_500_000; // クリーンアップ閾値を調整
+// 置換表のエントリ数の上限の既定値
+const MAX_TT_SIZE: usize = 2_000_000;
 
 // Null Move Pruning は削除（オセロには不適切）
 
@@ -17,6 +21,14 @@ const LMR_MOVE_THRESHOLD: usize = 3;
 // Aspiration Window を調整
 const ASPIRATION_WINDOW: i32 = 50;
 
+// ルートの手を並列に探索する深さの下限（浅いとスレッドの準備の方が高くつく）
+const PARALLEL_MIN_DEPTH: usize = 8;
+
+// 手の並び替えで、着手により増える確定石1つあたりに加える点数
+const STABILITY_ORDER_WEIGHT: i32 = 30;
+// 確定石による並び替えを行う残り深さの下限（末端付近では計算コストの方が高い）
+const STABILITY_ORDER_MIN_DEPTH: u8 = 3;
+
 // Futility Pruning の設定
 const FUTILITY_MARGIN: [i32; 5] = [0, 200, 300, 500, 900];
 
@@ -30,17 +42,33 @@ fn safe_neg(value: i32) -> i32 {
     }
 }
 
-// 評価値の定数を最適化
-const POSITION_SCORE: [[i32; 8]; 8] = [
-    [100, -20, 10, 5, 5, 10, -20, 100],
-    [-20, -50, -2, -2, -2, -2, -50, -20],
-    [10, -2, -1, -1, -1, -1, -2, 10],
-    [5, -2, -1, -1, -1, -1, -2, 5],
-    [5, -2, -1, -1, -1, -1, -2, 5],
-    [10, -2, -1, -1, -1, -1, -2, 10],
-    [-20, -50, -2, -2, -2, -2, -50, -20],
-    [100, -20, 10, 5, 5, 10, -20, 100],
-];
+/// マスの位置価値の既定値
+/// 回転・反転で重なるマスは同じ値なので、左上の三角形の10マス
+/// （a1, b1, c1, d1, b2, c2, d2, c3, d3, d4 の順）だけで表す
+pub const POSITION_VALUES: [i32; 10] = [100, -20, 10, 5, -50, -2, -2, -1, -1, -1];
+
+// 各マスが POSITION_VALUES の何番目の値を使うか
+const SQUARE_CLASS: [usize; 64] = {
+    // 左上の三角形の各行の先頭の番号（行 r では列 r..4 が並ぶ）
+    const ROW_START: [usize; 4] = [0, 4, 7, 9];
+    let mut classes = [0; 64];
+    let mut pos = 0;
+    while pos < 64 {
+        let (mut row, mut col) = (pos / 8, pos % 8);
+        if row > 3 {
+            row = 7 - row;
+        }
+        if col > 3 {
+            col = 7 - col;
+        }
+        if row > col {
+            (row, col) = (col, row);
+        }
+        classes[pos] = ROW_START[row] + col - row;
+        pos += 1;
+    }
+    classes
+};
 
 // ゲーム段階の調整（より適切な閾値）
 const EARLY_GAME_THRESHOLD: u32 = 25;
@@ -51,6 +79,432 @@ const MOBILITY_WEIGHT: [i32; 3] = [25, 15, 8];
 const PASS_BONUS: i32 = 30;
 const DISC_DIFF_WEIGHT: [i32; 3] = [5, 20, 1000];
 const CORNER_WEIGHT: i32 = 300;
+const FRONTIER_WEIGHT: i32 = 8; // 序盤・中盤のフロンティアの差1つあたりの評価
+const REPLY_MOBILITY_WEIGHT: i32 = 5; // evaluate_move の相手応手数ペナルティ
+
+// 完全読みに切り替える空きマス数の既定値
+const DEFAULT_ENDGAME_SOLVE_EMPTIES: u32 = 12;
+
+/// 勝敗だけの完全読み（必勝判定の表示）を行う空きマス数
+pub const WLD_ANNOUNCE_EMPTIES: u32 = 16;
+
+// 完全読みで速さ優先の並び替えを行う空きマス数の下限
+const SOLVER_ORDERING_EMPTIES: u32 = 7;
+
+/// 置換表のキーの作り方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtKeying {
+    Exact,   // (黒, 白, 手番) をそのまま使う（衝突しない）
+    Zobrist, // 64ビットの Zobrist ハッシュを使う（衝突で結果が変わらないかの検証用）
+}
+
+/// 末端評価の重み（AIごとに持たせて強さや棋風を変える）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalParams {
+    pub mobility_weight: [i32; 3],  // 序盤・中盤・終盤のモビリティの重み
+    pub disc_diff_weight: [i32; 3], // 序盤・中盤・終盤の石数差の重み
+    pub disc_weight_ramp: bool, // 終盤の石数差の重みを、盤が埋まるにつれて中盤の値から終盤の値まで少しずつ上げる
+    pub corner_weight: i32,     // 角1つあたりの評価
+    pub stability_weight: i32,  // 確定石の評価の倍率
+    pub pass_bonus: i32,        // 相手にパスさせた場合のボーナス
+    pub frontier_weight: i32,   // フロンティア（空きマスに接する石）の少なさの重み（0で無効）
+    pub position_values: [i32; 10], // 位置価値（並びは POSITION_VALUES と同じ）
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self {
+            mobility_weight: MOBILITY_WEIGHT,
+            disc_diff_weight: DISC_DIFF_WEIGHT,
+            disc_weight_ramp: true,
+            corner_weight: CORNER_WEIGHT,
+            stability_weight: 1,
+            pass_bonus: PASS_BONUS,
+            frontier_weight: FRONTIER_WEIGHT,
+            position_values: POSITION_VALUES,
+        }
+    }
+}
+
+impl EvalParams {
+    /// 空きマスが empty_count の局面での石数差の重み
+    /// disc_weight_ramp が有効なら、終盤に入った時点の中盤の重みから、盤が埋まった時点の
+    /// 終盤の重みまで、打たれた石の数に比例して上げる（序盤・中盤は一定）
+    pub fn disc_diff_weight_at(&self, empty_count: u32) -> i32 {
+        let [early, mid, end] = self.disc_diff_weight;
+        match GamePhase::from_empty_count(empty_count) {
+            GamePhase::Early => early,
+            GamePhase::Mid => mid,
+            GamePhase::End if self.disc_weight_ramp => {
+                let end_start = 64 - MID_GAME_THRESHOLD;
+                let placed = (end_start - empty_count.min(end_start)) as i32;
+                mid + (end - mid) * placed / end_start as i32
+            }
+            GamePhase::End => end,
+        }
+    }
+
+    /// pos の位置価値
+    #[inline]
+    fn position_value(&self, pos: usize) -> i32 {
+        self.position_values[SQUARE_CLASS[pos]]
+    }
+}
+
+/// AIエンジンの設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineConfig {
+    pub endgame_solve_empties: u32, // 空きマスがこの数以下なら完全読み（0で無効）
+    pub eval: EvalParams,           // 末端評価と手の並び替えで使う重み
+    pub randomness: i32,            // 末端評価に加えるノイズの最大幅（0で無効）
+    pub opening_random_moves: u32,  // 序盤でランダムに打つ手数（0で無効）
+    pub temperature: f64, // レベル1の手選びの softmax の温度（0なら常に簡易評価が最も高い手）
+    pub seed: Option<u64>, // temperature が正のときの乱数の種（同じ種なら同じ局面で同じ手を選ぶ）
+    pub move_time_limit: Option<std::time::Duration>, // 1手あたりの思考時間の上限（超えたら打ち切る）
+    pub tt_max_entries: usize, // 置換表のエントリ数の上限（探索の前後でこの数以下に減らす）
+    pub pad_thinking_time: bool, // すぐに手が決まってもレベルに応じた最低時間だけ待つ（対局の見やすさのため）
+    pub tt_keying: TtKeying,     // 置換表のキーの作り方
+    pub max_nodes: Option<u64>, // 探索ノード数の上限（時間の代わりに使うと機械の速さによらず同じ手を返す）
+    pub use_mtdf: bool,         // 反復深化の各深さを Aspiration Window の代わりに MTD(f) で読む
+    pub forward_pruning: bool, // Futility Pruning と Late Move Reduction を使う（false なら探索の窓によらず同じ値を返す）
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            endgame_solve_empties: DEFAULT_ENDGAME_SOLVE_EMPTIES,
+            eval: EvalParams::default(),
+            randomness: 0,
+            opening_random_moves: 0,
+            temperature: 0.0,
+            seed: None,
+            move_time_limit: None,
+            tt_max_entries: MAX_TT_SIZE,
+            pad_thinking_time: true,
+            tt_keying: TtKeying::Exact,
+            max_nodes: None,
+            use_mtdf: false,
+            forward_pruning: true,
+        }
+    }
+}
+
+impl EngineConfig {
+    /// 指定した空きマス数で完全読みを使うかどうか
+    #[inline]
+    pub fn should_solve_exactly(&self, empty_count: u32) -> bool {
+        self.endgame_solve_empties > 0 && empty_count <= self.endgame_solve_empties
+    }
+
+    /// ブリッツ用の設定（レベルに関係なく1手を制限時間内に打つ）
+    pub fn blitz() -> Self {
+        Self {
+            endgame_solve_empties: BLITZ_ENDGAME_SOLVE_EMPTIES,
+            move_time_limit: Some(BLITZ_TIME_LIMIT),
+            ..Self::default()
+        }
+    }
+}
+
+/// ブリッツAIの1手あたりの思考時間
+pub const BLITZ_TIME_LIMIT: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// ブリッツAIの最大探索深度（実際には時間内に読める深さまで）
+pub const BLITZ_LEVEL: usize = 20;
+
+// ブリッツで完全読みに切り替える空きマス数（制限時間内に確実に終わる範囲）
+const BLITZ_ENDGAME_SOLVE_EMPTIES: u32 = 8;
+
+// 探索打ち切りのために時刻を確認するノード間隔（2の累乗）
+const STOP_CHECK_INTERVAL: u64 = 1024;
+
+/// 個性派AIの探索レベル
+pub const PERSONALITY_LEVEL: usize = 5;
+
+/// AIの個性（評価の重み付けとランダム性のプリセット）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Personality {
+    Balanced,   // 標準
+    Aggressive, // 石数重視
+    Positional, // 角・確定石重視
+    Wild,       // ノイズが大きく読みにくい
+}
+
+impl Personality {
+    pub const ALL: [Personality; 4] = [
+        Personality::Balanced,
+        Personality::Aggressive,
+        Personality::Positional,
+        Personality::Wild,
+    ];
+
+    /// 表示名を返す
+    pub fn name(&self) -> &'static str {
+        match self {
+            Personality::Balanced => "標準型",
+            Personality::Aggressive => "攻撃型",
+            Personality::Positional => "堅実型",
+            Personality::Wild => "気まぐれ型",
+        }
+    }
+
+    /// この個性に対応するエンジン設定
+    pub fn engine_config(&self) -> EngineConfig {
+        let base = EngineConfig::default();
+        match self {
+            Personality::Balanced => base,
+            Personality::Aggressive => EngineConfig {
+                eval: EvalParams {
+                    mobility_weight: [10, 8, 4],
+                    disc_diff_weight: [40, 60, 1000],
+                    corner_weight: 150,
+                    ..base.eval
+                },
+                ..base
+            },
+            Personality::Positional => EngineConfig {
+                eval: EvalParams {
+                    disc_diff_weight: [0, 5, 1000],
+                    corner_weight: 600,
+                    stability_weight: 3,
+                    ..base.eval
+                },
+                ..base
+            },
+            Personality::Wild => EngineConfig {
+                randomness: 150,
+                opening_random_moves: 6,
+                ..base
+            },
+        }
+    }
+}
+
+// 探索時間の見積もりに使う1ノードあたりの概算時間（マイクロ秒）
+const ESTIMATED_MICROS_PER_NODE: f64 = 5.0;
+
+/// これを超えると探索が長引きそうだと警告する見積もり時間
+pub const SEARCH_TIME_WARNING: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 深さと空きマス数から探索時間を概算する（経験則による目安で、正確な予測ではない）
+pub fn estimate_search_time(depth: usize, empty_count: u32) -> std::time::Duration {
+    // 読める深さは残りの空きマス数まで
+    let effective_depth = depth.min(empty_count as usize) as f64;
+
+    // 枝刈り後の実効分岐数：空きマスが多いほど合法手も多い
+    let branching = 1.5 + empty_count.min(40) as f64 / 16.0;
+
+    let nodes = branching.powf(effective_depth);
+    std::time::Duration::from_secs_f64(nodes * ESTIMATED_MICROS_PER_NODE / 1_000_000.0)
+}
+
+// 評価値を勝率に変換するときの尺度（この評価値差でおよそ73%）
+const WIN_PROBABILITY_SCALE: f64 = 300.0;
+
+/// 評価値を評価した側から見た勝率（0.0〜1.0）に変換する
+pub fn win_probability(score: i32) -> f64 {
+    1.0 / (1.0 + (-(score as f64) / WIN_PROBABILITY_SCALE).exp())
+}
+
+// 最終石差を探索の評価値スケールに変換
+#[inline(always)]
+pub(crate) fn final_score(diff: i32) -> i32 {
+    if diff > 0 {
+        10000 + diff
+    } else if diff < 0 {
+        -10000 + diff
+    } else {
+        0
+    }
+}
+
+/// 局面解析の結果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Analysis {
+    pub best_move: Option<usize>, // 最善手（打てる手が無ければ None）
+    pub score: Option<i32>,       // 手番側から見た評価値
+}
+
+/// 読み筋と探索の統計付きの解析結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetailedAnalysis {
+    pub best_move: Option<usize>, // 最善手（打てる手が無ければ None）
+    pub score: Option<i32>,       // 手番側から見た評価値
+    pub pv: Vec<Option<usize>>,   // 読み筋（最善手から順に、None はパス）
+    pub stats: SearchStats,
+}
+
+/// 1手にかかった時間の内訳
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveTiming {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::stats::game_stats::duration_millis")
+    )]
+    pub search_time: std::time::Duration, // 実際に探索していた時間
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::stats::game_stats::duration_millis")
+    )]
+    pub cleanup_time: std::time::Duration, // 置換表を上限以内に減らしていた時間
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::stats::game_stats::duration_millis")
+    )]
+    pub padding_time: std::time::Duration, // 見やすさのために待った時間（計算はしていない）
+}
+
+impl MoveTiming {
+    /// 待ち時間を除いた、エンジンが実際に計算していた時間
+    pub fn compute_time(&self) -> std::time::Duration {
+        self.search_time + self.cleanup_time
+    }
+}
+
+/// 探索の統計情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchStats {
+    pub depth_reached: usize, // 読み終えた深さ（完全読みなら残りの空きマス数）
+    pub nodes: u64,           // 探索したノード数（完全読みでは数えない）
+    pub tt_entries: usize,    // 探索後の置換表のエントリ数
+    pub timing: MoveTiming,   // 時間の内訳（待ち時間は呼び出し側が加える）
+}
+
+impl SearchStats {
+    /// 1秒あたりの探索ノード数（探索時間を測っていない、またはノードを数えていなければ None）
+    pub fn nodes_per_second(&self) -> Option<f64> {
+        let seconds = self.timing.search_time.as_secs_f64();
+        (self.nodes > 0 && seconds > 0.0).then(|| self.nodes as f64 / seconds)
+    }
+}
+
+/// メモリ量の表示に使う1MBのバイト数
+pub const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// 置換表が使っているメモリの目安（バイト、エントリ数 × 1エントリの大きさ）
+pub fn tt_memory_estimate(tt: &FxHashMap<(u64, u64, u8), Entry>) -> usize {
+    tt_entries_memory(tt.len())
+}
+
+/// 指定したエントリ数の置換表が使うメモリの目安（バイト）
+pub fn tt_entries_memory(entries: usize) -> usize {
+    entries * std::mem::size_of::<((u64, u64, u8), Entry)>()
+}
+
+/// この深さのルートを並列に読むか
+///
+/// 深い探索ではルートの手を並列に読む。ノード数の上限がある場合は結果を再現できるよう、
+/// スレッドが1つしかない場合は並列にしても遅くなるだけなので、それぞれ逐次探索にする。
+/// 並列探索は共有の下限で窓を狭めるだけで MTD(f) の窓を使わないので、MTD(f) も逐次探索で読む
+fn uses_parallel_search(depth: usize, config: &EngineConfig) -> bool {
+    depth >= PARALLEL_MIN_DEPTH
+        && config.max_nodes.is_none()
+        && !config.use_mtdf
+        && rayon::current_num_threads() > 1
+}
+
+/// 置換表のキーを作る
+#[inline(always)]
+fn tt_key(board: &BitBoard, player: Player, keying: TtKeying) -> (u64, u64, u8) {
+    match keying {
+        TtKeying::Exact => (board.black, board.white, player as u8),
+        TtKeying::Zobrist => (board.zobrist_hash(player), 0, 0),
+    }
+}
+
+/// 置換表が上限を超えていたら、深さの浅いエントリから削除して上限の3/4まで減らす
+fn enforce_tt_cap(tt: &mut FxHashMap<(u64, u64, u8), Entry>, max_entries: usize) {
+    if tt.len() <= max_entries {
+        return;
+    }
+    let target = max_entries * 3 / 4;
+    if target == 0 {
+        tt.clear();
+        return;
+    }
+
+    // 削除する数だけ浅い方から数えた深さを境目にする
+    let mut depths: Vec<u8> = tt.values().map(|entry| entry.depth).collect();
+    let remove_count = depths.len() - target;
+    let (_, &mut threshold, _) = depths.select_nth_unstable(remove_count);
+
+    // 境目より浅いものを削除し、足りなければ境目と同じ深さのものを削除する
+    let mut excess = remove_count;
+    for remove_equal in [false, true] {
+        tt.retain(|_, entry| {
+            let removable = entry.depth < threshold || (remove_equal && entry.depth == threshold);
+            if excess > 0 && removable {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+// 探索中に共有する状態（エンジン設定と手の並び替え用テーブル）
+#[derive(Clone)]
+struct SearchContext {
+    config: EngineConfig,
+    pv_table: PVTable,
+    killer_moves: KillerMoves,
+    history_table: HistoryTable,
+    deadline: Option<std::time::Instant>, // これを過ぎたら探索を打ち切る
+    node_limit: Option<u64>,              // 探索ノード数がこれに達したら打ち切る
+    cancel: Option<Arc<AtomicBool>>,      // 外から true にされたら打ち切る
+    nodes: u64,
+    stopped: bool,
+}
+
+impl SearchContext {
+    fn new(config: &EngineConfig) -> Self {
+        Self {
+            config: *config,
+            pv_table: PVTable::new(),
+            killer_moves: KillerMoves::new(),
+            history_table: HistoryTable::new(),
+            deadline: None,
+            node_limit: None,
+            cancel: None,
+            nodes: 0,
+            stopped: false,
+        }
+    }
+
+    /// 制限時間・ノード数の上限を超えたか、中止されたかどうか（時刻と中止の確認は一定ノードごと）
+    #[inline]
+    fn should_stop(&mut self) -> bool {
+        if self.stopped {
+            return true;
+        }
+        self.nodes += 1;
+        if self.node_limit.is_some_and(|limit| self.nodes >= limit) {
+            self.stopped = true;
+        }
+        if self.nodes & (STOP_CHECK_INTERVAL - 1) == 0 {
+            if self
+                .deadline
+                .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+            {
+                self.stopped = true;
+            }
+            if self.is_cancelled() {
+                self.stopped = true;
+            }
+        }
+        self.stopped
+    }
+
+    /// 外から中止されたかどうか
+    #[inline]
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(AtomicOrdering::Relaxed))
+    }
+}
 
 // PV (Principal Variation) の管理
 #[derive(Clone)]
@@ -119,18 +573,18 @@ impl KillerMoves {
 // History Table の最適化
 #[derive(Clone)]
 struct HistoryTable {
-    scores: [[[i32; 64]; 2]; 2], // [phase][player][move]
+    scores: [[[i32; 64]; 2]; 3], // [phase][player][move]
 }
 
 impl HistoryTable {
     fn new() -> Self {
         Self {
-            scores: [[[0; 64]; 2]; 2],
+            scores: [[[0; 64]; 2]; 3],
         }
     }
 
     fn update(&mut self, phase: usize, player: usize, mv: u8, depth: u8, is_good: bool) {
-        if mv >= 64 || phase >= 2 || player >= 2 {
+        if mv >= 64 || phase >= 3 || player >= 2 {
             return;
         }
 
@@ -147,14 +601,14 @@ impl HistoryTable {
     }
 
     fn get_score(&self, phase: usize, player: usize, mv: u8) -> i32 {
-        if mv >= 64 || phase >= 2 || player >= 2 {
+        if mv >= 64 || phase >= 3 || player >= 2 {
             return 0;
         }
         self.scores[phase][player][mv as usize]
     }
 
     fn age(&mut self) {
-        for phase in 0..2 {
+        for phase in 0..3 {
             for player in 0..2 {
                 for mv in 0..64 {
                     self.scores[phase][player][mv] = (self.scores[phase][player][mv] * 7) / 8;
@@ -169,11 +623,16 @@ impl HistoryTable {
 struct Move {
     position: u8,
     score: i32,
+    flips: u64, // 並び替え時に計算したひっくり返る石（着手時に再利用）
 }
 
 impl Move {
-    fn new(position: u8, score: i32) -> Self {
-        Self { position, score }
+    fn new(position: u8, score: i32, flips: u64) -> Self {
+        Self {
+            position,
+            score,
+            flips,
+        }
     }
 }
 
@@ -185,7 +644,156 @@ impl PartialOrd for Move {
 
 impl Ord for Move {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.score.cmp(&self.score) // 降順ソート
+        // 評価の降順、同点ならマスの番号が小さい順（並び順を常に一定にする）
+        other
+            .score
+            .cmp(&self.score)
+            .then(self.position.cmp(&other.position))
+    }
+}
+
+/// ルートを読む窓の決め方（一括の探索と ChunkedSearch で同じ手順を使う）
+#[derive(Clone, Copy)]
+enum RootPlan {
+    /// 全幅の窓で1回読む（浅い深さ、または Aspiration Window が広がりすぎた場合）
+    FullWindow,
+    /// 直前の深さの評価値の周りの窓から始め、外れたら窓を広げて読み直す
+    Aspiration {
+        alpha: i32,
+        beta: i32,
+        window_size: i32,
+    },
+    /// MTD(f)：直前の深さの評価値から始め、幅0の窓（alpha = beta - 1）で読み直して
+    /// 評価値の上界と下界を狭めていく。2回目以降の探索は置換表に残った境界で大半が打ち切られる
+    Mtdf {
+        guess: i32,
+        lower: i32,
+        upper: i32,
+        best_move: Option<u8>,
+    },
+}
+
+impl RootPlan {
+    /// 次に読む窓 (alpha, beta)
+    fn window(&self) -> (i32, i32) {
+        match *self {
+            RootPlan::FullWindow => (i32::MIN + 1, i32::MAX - 1),
+            RootPlan::Aspiration { alpha, beta, .. } => (alpha, beta),
+            RootPlan::Mtdf { guess, lower, .. } => {
+                let beta = if guess == lower { guess + 1 } else { guess };
+                (beta - 1, beta)
+            }
+        }
+    }
+
+    /// window() の窓で読んだ評価値を受け取り、決まれば Some(最善手と評価値) を返す
+    /// （None なら窓を変えて読み直す）
+    fn advance(
+        &mut self,
+        score: i32,
+        ctx: &SearchContext,
+        tt: &FxHashMap<(u64, u64, u8), Entry>,
+        root_key: (u64, u64, u8),
+    ) -> Option<Option<(usize, i32)>> {
+        let (_, window_beta) = self.window();
+        match self {
+            RootPlan::FullWindow => {
+                Some(ctx.pv_table.get_pv_move(0).map(|pos| (pos as usize, score)))
+            }
+            RootPlan::Aspiration {
+                alpha,
+                beta,
+                window_size,
+            } => {
+                if score <= *alpha {
+                    // Fail low - alpha を下げる
+                    *alpha = score - *window_size;
+                    *window_size *= 2;
+                } else if score >= *beta {
+                    // Fail high - beta を上げる
+                    *beta = score + *window_size;
+                    *window_size *= 2;
+                } else {
+                    // 正常な範囲内
+                    return Some(ctx.pv_table.get_pv_move(0).map(|pos| (pos as usize, score)));
+                }
+
+                // ウィンドウが大きくなりすぎたら通常探索に切り替え
+                if *window_size > 1000 {
+                    *self = RootPlan::FullWindow;
+                }
+                None
+            }
+            RootPlan::Mtdf {
+                guess,
+                lower,
+                upper,
+                best_move,
+            } => {
+                *guess = score;
+                if score < window_beta {
+                    *upper = score;
+                } else {
+                    *lower = score;
+                    // 下界を上げた手が最善手（ルートが置換表で打ち切られた場合はそこに残っている）
+                    *best_move = ctx
+                        .pv_table
+                        .get_pv_move(0)
+                        .or_else(|| tt.get(&root_key).and_then(|entry| entry.best_move));
+                }
+
+                if *lower < *upper {
+                    None
+                } else {
+                    Some(best_move.map(|pos| (pos as usize, *lower)))
+                }
+            }
+        }
+    }
+}
+
+/// 手を順に読んでいる途中の節点
+/// ループの途中の状態を持つので、ルートでは手ごとに区切って読み進められる
+struct NodeSearch {
+    player: Player,
+    depth: u8,
+    ply: usize,
+    alpha: i32,
+    beta: i32,
+    original_alpha: i32,
+    tt_key: (u64, u64, u8),
+    moves: Vec<Move>, // 並び替えた合法手
+    next_move: usize, // 次に読む手の添字（βカットしたら手の数）
+    futility_prune: bool,
+    static_eval: i32, // Futility Pruning 用の静的評価
+    best_score: i32,
+    best_move: Option<u8>,
+    phase_idx: usize,
+}
+
+impl NodeSearch {
+    fn new(player: Player, depth: u8, alpha: i32, beta: i32, ply: usize) -> Self {
+        Self {
+            player,
+            depth,
+            ply,
+            alpha,
+            beta,
+            original_alpha: alpha,
+            tt_key: (0, 0, 0),
+            moves: Vec::new(),
+            next_move: 0,
+            futility_prune: false,
+            static_eval: 0,
+            best_score: i32::MIN,
+            best_move: None,
+            phase_idx: 0,
+        }
+    }
+
+    /// すべての手を読み終えたか（βカットを含む）
+    fn is_finished(&self) -> bool {
+        self.next_move >= self.moves.len()
     }
 }
 
@@ -211,164 +819,544 @@ impl GamePhase {
     fn index(&self) -> usize {
         match self {
             GamePhase::Early => 0,
-            GamePhase::Mid => 0,
-            GamePhase::End => 1,
+            GamePhase::Mid => 1,
+            GamePhase::End => 2,
         }
     }
 }
 
-impl BitBoard {
-    /// Transposition Table を使用した最善手探索のメインエントリーポイント
-    pub fn find_best_move_with_tt(
-        &mut self,
-        player: Player,
-        depth: usize,
-        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-    ) -> (Option<usize>, Option<i32>) {
-        if depth == 0 {
-            return (None, None);
-        }
-
-        // Transposition Table のサイズ管理
-        if tt.len() > TT_CLEANUP_THRESHOLD {
-            self.cleanup_tt(tt);
-        }
-
-        // 反復深化探索を使用
-        self.iterative_deepening_search(player, depth, tt)
-    }
+/// 少しずつ進められる探索（スレッドを使えない環境で、フレームごとに探索を進める）
+///
+/// 一括の探索（find_best_move_with_config など）と同じ反復深化・窓の決め方・手の並び替えを、
+/// ルートの手を1つ読むごとに区切れるようにしたもの。探索の途中の状態は step 間で引き継ぐので、
+/// 読み直しは起こらず、並列探索を使わない一括の探索と同じ手を返す。
+/// 1回の step は、ノード数が上限に達した時点で読んでいるルートの手を読み終えるまで続く。
+/// 時間制限の代わりに最大深度まで読んだら終了する。
+pub struct ChunkedSearch {
+    board: BitBoard,
+    player: Player,
+    max_depth: usize,
+    current_depth: usize,
+    tt: FxHashMap<(u64, u64, u8), Entry>,
+    ctx: SearchContext,
+    plan: Option<RootPlan>,   // 読んでいる深さのルートの窓の決め方
+    root: Option<NodeSearch>, // 読んでいる途中のルート
+    best_move: Option<usize>,
+    best_eval: Option<i32>,
+    depth_reached: usize,
+    finished: bool,
+}
 
-    /// 反復深化探索（時間管理付き）
-    fn iterative_deepening_search(
-        &mut self,
+impl ChunkedSearch {
+    /// 探索を準備する（まだ何も読まない）
+    /// tt は一括の探索と同様に前の手番から引き継いだ置換表で、終わったら into_tt で取り出す
+    pub fn new(
+        board: BitBoard,
         player: Player,
         max_depth: usize,
-        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-    ) -> (Option<usize>, Option<i32>) {
-        let mut best_move = None;
-        let mut best_eval = None;
-        let mut pv_table = PVTable::new();
-        let mut killer_moves = KillerMoves::new();
-        let mut history_table = HistoryTable::new();
+        config: EngineConfig,
+        mut tt: FxHashMap<(u64, u64, u8), Entry>,
+    ) -> Self {
+        enforce_tt_cap(&mut tt, config.tt_max_entries);
+        Self {
+            board,
+            player,
+            max_depth,
+            current_depth: 1,
+            tt,
+            ctx: SearchContext::new(&config),
+            plan: None,
+            root: None,
+            best_move: None,
+            best_eval: None,
+            depth_reached: 0,
+            finished: max_depth == 0,
+        }
+    }
 
-        let start_time = std::time::Instant::now();
-        let time_limit = std::time::Duration::from_millis(match max_depth {
-            1..=3 => 100,
-            4..=6 => 500,
-            7..=10 => 2000,
-            11..=15 => 5000,
-            _ => 10000,
-        });
+    /// 最大 node_budget ノードほど探索を進め、読み終えていれば (最善手, 評価値) を返す
+    pub fn step(&mut self, node_budget: u64) -> Option<(Option<usize>, Option<i32>)> {
+        if self.finished {
+            return Some((self.best_move, self.best_eval));
+        }
 
-        // 反復深化
-        for current_depth in 1..=max_depth {
-            if start_time.elapsed() > time_limit && current_depth > 3 {
-                break;
+        // 最初の step で、探索せずに決まる手を確認する
+        if self.current_depth == 1 && self.plan.is_none() {
+            if let Some((best_move, best_eval, stats)) =
+                self.board.search_shortcut(self.player, &self.ctx.config)
+            {
+                self.best_move = best_move;
+                self.best_eval = best_eval;
+                self.depth_reached = stats.depth_reached;
+                self.finished = true;
+                return Some((best_move, best_eval));
             }
+        }
 
-            let result = self.aspiration_window_search(
-                player,
-                current_depth,
-                tt,
-                &mut pv_table,
-                &mut killer_moves,
-                &mut history_table,
-                best_eval.unwrap_or(0),
-            );
+        let node_limit = self.ctx.nodes + node_budget;
+        while self.current_depth <= self.max_depth {
+            let mut plan = match self.plan {
+                Some(plan) => plan,
+                None => match self.board.root_plan(
+                    self.player,
+                    self.current_depth,
+                    &self.ctx,
+                    self.best_eval.unwrap_or(0),
+                ) {
+                    Ok(plan) => plan,
+                    Err(result) => {
+                        self.complete_depth(result);
+                        continue;
+                    }
+                },
+            };
+            self.plan = Some(plan);
+
+            let score = self.advance_root(&plan, node_limit)?;
+            let root_key = tt_key(&self.board, self.player, self.ctx.config.tt_keying);
+            match plan.advance(score, &self.ctx, &self.tt, root_key) {
+                Some(result) => self.complete_depth(result),
+                None => self.plan = Some(plan),
+            }
+        }
 
-            if let Some((mv, eval)) = result {
-                best_move = Some(mv);
-                best_eval = Some(eval);
+        self.ctx.history_table.age();
+        enforce_tt_cap(&mut self.tt, self.ctx.config.tt_max_entries);
+        self.finished = true;
+        Some((self.best_move, self.best_eval))
+    }
 
-                // 時間制限チェック
-                if start_time.elapsed() > time_limit {
-                    break;
+    /// ルートを plan の窓で読み進め、読み終えたら評価値を返す（ノード数が上限に達したら None）
+    fn advance_root(&mut self, plan: &RootPlan, node_limit: u64) -> Option<i32> {
+        let mut root = match self.root.take() {
+            Some(root) => root,
+            None => {
+                let (alpha, beta) = plan.window();
+                self.ctx.pv_table.length[0] = 0;
+                let mut root =
+                    NodeSearch::new(self.player, self.current_depth as u8, alpha, beta, 0);
+                if let Some(score) = self
+                    .board
+                    .begin_node(&mut root, &mut self.tt, &mut self.ctx)
+                {
+                    return Some(score);
                 }
+                root
+            }
+        };
+
+        while !root.is_finished() {
+            if self.ctx.nodes >= node_limit {
+                self.root = Some(root);
+                return None;
             }
+            self.board
+                .search_next_move(&mut root, &mut self.tt, &mut self.ctx);
         }
+        Some(self.board.finish_node(root, &mut self.tt))
+    }
 
-        // History Table の老化
-        history_table.age();
+    /// 読み終えた深さの結果を記録して次の深さへ進む
+    fn complete_depth(&mut self, result: Option<(usize, i32)>) {
+        if let Some((mv, eval)) = result {
+            self.best_move = Some(mv);
+            self.best_eval = Some(eval);
+            self.depth_reached = self.current_depth;
+        }
+        self.plan = None;
+        self.current_depth += 1;
+    }
+
+    /// ここまでの探索の統計情報（フレームをまたぐので時間の内訳は測らない）
+    pub fn stats(&self) -> SearchStats {
+        SearchStats {
+            depth_reached: self.depth_reached,
+            nodes: self.ctx.nodes,
+            tt_entries: self.tt.len(),
+            ..SearchStats::default()
+        }
+    }
 
-        (best_move, best_eval)
+    /// 置換表を取り出す（次の手番の探索に引き継ぐ）
+    pub fn into_tt(self) -> FxHashMap<(u64, u64, u8), Entry> {
+        self.tt
     }
+}
 
-    /// Aspiration Window を使った探索
-    fn aspiration_window_search(
+impl BitBoard {
+    /// Transposition Table を使用した最善手探索のメインエントリーポイント
+    /// time_limit を過ぎたら、それまでに読み終えた深さの結果を返す（深さごとの既定の時間表は使わない）
+    pub fn find_best_move_with_tt(
         &mut self,
         player: Player,
         depth: usize,
         tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-        pv_table: &mut PVTable,
-        killer_moves: &mut KillerMoves,
-        history_table: &mut HistoryTable,
-        prev_score: i32,
-    ) -> Option<(usize, i32)> {
-        if depth <= 3 {
-            return self.minimax_best_move_with_tt(
-                player,
-                depth,
-                tt,
-                pv_table,
-                killer_moves,
-                history_table,
-            );
-        }
-
-        let mut alpha = prev_score - ASPIRATION_WINDOW;
-        let mut beta = prev_score + ASPIRATION_WINDOW;
-        let mut window_size = ASPIRATION_WINDOW;
+        time_limit: std::time::Duration,
+    ) -> (Option<usize>, Option<i32>) {
+        let config = EngineConfig {
+            move_time_limit: Some(time_limit),
+            ..EngineConfig::default()
+        };
+        self.find_best_move_with_config(player, depth, tt, &config)
+    }
 
-        loop {
-            pv_table.length[0] = 0; // PV をリセット
+    /// 最善手だけでなく読み筋全体を返す探索（最善手から順に。打てる手が無ければ空）
+    /// パスは含めない（打てる手が無い側は必ずパスするので、順に打っていけば再現できる）
+    pub fn find_best_line_with_tt(
+        &mut self,
+        player: Player,
+        depth: usize,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+    ) -> (Vec<usize>, Option<i32>) {
+        let config = EngineConfig::default();
+        let board = *self;
+        let (best_move, score) = self.find_best_move_with_config(player, depth, tt, &config);
+        let line = best_move
+            .map(|pos| board.principal_variation(pos, player, tt, depth, config.tt_keying))
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect();
+        (line, score)
+    }
 
-            let score = self.minimax_with_tt_internal(
+    /// エンジン設定を指定した最善手探索
+    pub fn find_best_move_with_config(
+        &mut self,
+        player: Player,
+        depth: usize,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+        config: &EngineConfig,
+    ) -> (Option<usize>, Option<i32>) {
+        let (best_move, evaluation, _) = self.find_best_move_with_stats(player, depth, tt, config);
+        (best_move, evaluation)
+    }
+
+    /// 最善手探索（実際に読んだ深さなどの統計情報も返す）
+    pub fn find_best_move_with_stats(
+        &mut self,
+        player: Player,
+        depth: usize,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+        config: &EngineConfig,
+    ) -> (Option<usize>, Option<i32>, SearchStats) {
+        self.find_best_move_cancellable(player, depth, tt, config, None)
+    }
+
+    /// 中止できる最善手探索（cancel が true になったら、それまでに読み終えた深さの結果を返す）
+    /// 最初の深さは中止されても読み終えるので、打てる手があれば手を返す
+    pub fn find_best_move_with_cancel(
+        &mut self,
+        player: Player,
+        depth: usize,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+        config: &EngineConfig,
+        cancel: &Arc<AtomicBool>,
+    ) -> (Option<usize>, Option<i32>, SearchStats) {
+        self.find_best_move_cancellable(player, depth, tt, config, Some(cancel))
+    }
+
+    fn find_best_move_cancellable(
+        &mut self,
+        player: Player,
+        depth: usize,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+        config: &EngineConfig,
+        cancel: Option<&Arc<AtomicBool>>,
+    ) -> (Option<usize>, Option<i32>, SearchStats) {
+        debug_assert!(self.validate().is_ok(), "不正な盤面で探索しています");
+
+        if depth == 0 {
+            return (None, None, SearchStats::default());
+        }
+
+        // Transposition Table を上限以内にしてから探索する
+        let start = std::time::Instant::now();
+        enforce_tt_cap(tt, config.tt_max_entries);
+        let mut cleanup_time = start.elapsed();
+
+        // 決まる手が無ければ反復深化探索を使用
+        let search_start = std::time::Instant::now();
+        let (best_move, evaluation, mut stats) = match self.search_shortcut(player, config) {
+            Some(result) => result,
+            None => self.iterative_deepening_search(player, depth, tt, config, cancel),
+        };
+        let search_time = search_start.elapsed();
+
+        // 探索中に増えた分も上限以内に収める
+        let cleanup_start = std::time::Instant::now();
+        enforce_tt_cap(tt, config.tt_max_entries);
+        cleanup_time += cleanup_start.elapsed();
+        stats.tt_entries = tt.len();
+        stats.timing = MoveTiming {
+            search_time,
+            cleanup_time,
+            padding_time: std::time::Duration::ZERO,
+        };
+
+        (best_move, evaluation, stats)
+    }
+
+    /// 通常の探索をせずに決まる手（序盤のランダム手・完全読み）があれば返す
+    fn search_shortcut(
+        &self,
+        player: Player,
+        config: &EngineConfig,
+    ) -> Option<(Option<usize>, Option<i32>, SearchStats)> {
+        // 序盤の指定手数はランダムに打つ（定跡の幅を広げる）
+        let moves_played = (self.black | self.white).count_ones().saturating_sub(4);
+        if moves_played < config.opening_random_moves {
+            let legal_moves = self.get_legal_move_positions(player);
+            if !legal_moves.is_empty() {
+                let pos = legal_moves[rand::thread_rng().gen_range(0..legal_moves.len())];
+                return Some((Some(pos), None, SearchStats::default()));
+            }
+        }
+
+        // 空きマスが閾値以下なら完全読みに切り替える
+        if config.should_solve_exactly(self.count_empty()) {
+            if let Some((pos, diff)) = self.solve_endgame_best_move(player) {
+                let stats = SearchStats {
+                    depth_reached: self.count_empty() as usize,
+                    ..SearchStats::default()
+                };
+                return Some((Some(pos), Some(final_score(diff)), stats));
+            }
+        }
+
+        None
+    }
+
+    /// 局面を解析して最善手と評価値を返す（盤面は変更しない）
+    pub fn analyze(&self, player: Player, depth: usize) -> Analysis {
+        self.analyze_with_config(player, depth, &EngineConfig::default())
+    }
+
+    /// 指定した設定で局面を解析する（盤面は変更しない）
+    pub fn analyze_with_config(
+        &self,
+        player: Player,
+        depth: usize,
+        config: &EngineConfig,
+    ) -> Analysis {
+        let mut board = *self;
+        let mut tt = FxHashMap::default();
+        let (best_move, score) = board.find_best_move_with_config(player, depth, &mut tt, config);
+
+        Analysis { best_move, score }
+    }
+
+    /// 局面を解析して、最善手・評価値に加えて読み筋と探索の統計を返す（盤面は変更しない）
+    pub fn analyze_detailed(&self, player: Player, depth: usize) -> DetailedAnalysis {
+        let mut board = *self;
+        let mut tt = FxHashMap::default();
+        let config = EngineConfig::default();
+        let (best_move, score, stats) =
+            board.find_best_move_with_stats(player, depth, &mut tt, &config);
+        let pv = best_move
+            .map(|pos| self.principal_variation(pos, player, &tt, depth, config.tt_keying))
+            .unwrap_or_default();
+
+        DetailedAnalysis {
+            best_move,
+            score,
+            pv,
+            stats,
+        }
+    }
+
+    /// 最善手から置換表の最善手をたどって読み筋を作る（最大 max_len 手）
+    fn principal_variation(
+        &self,
+        first_move: usize,
+        player: Player,
+        tt: &FxHashMap<(u64, u64, u8), Entry>,
+        max_len: usize,
+        keying: TtKeying,
+    ) -> Vec<Option<usize>> {
+        let mut pv = vec![Some(first_move)];
+        let Some(mut board) = self.with_move(first_move, player) else {
+            return pv;
+        };
+        let mut side = player.opponent();
+
+        while pv.len() < max_len && !board.is_game_over() {
+            if board.is_pass_required(side) {
+                pv.push(None);
+                side = side.opponent();
+                continue;
+            }
+            let Some(pos) = tt
+                .get(&tt_key(&board, side, keying))
+                .and_then(|entry| entry.best_move)
+            else {
+                break;
+            };
+            if !board.make_move(pos as usize, side) {
+                break;
+            }
+            pv.push(Some(pos as usize));
+            side = side.opponent();
+        }
+
+        // 末尾のパスは読み筋に含めない
+        while pv.last() == Some(&None) {
+            pv.pop();
+        }
+        pv
+    }
+
+    /// すべての合法手を浅く読み、手番側から見た評価値を付けて良い手から順に返す
+    /// （打てる手が無ければ空）
+    pub fn analyze_multipv(&self, player: Player, depth: usize) -> Vec<(usize, i32)> {
+        let mut tt = FxHashMap::default();
+        let mut ctx = SearchContext::new(&EngineConfig::default());
+        let child_depth = depth.saturating_sub(1).min(u8::MAX as usize) as u8;
+
+        let mut scores: Vec<(usize, i32)> = self
+            .legal_moves_iter(player)
+            .filter_map(|pos| {
+                let mut child = self.with_move(pos, player)?;
+                let score = child.minimax_with_tt_internal(
+                    player.opponent(),
+                    child_depth,
+                    i32::MIN + 1,
+                    i32::MAX - 1,
+                    1,
+                    false,
+                    &mut tt,
+                    &mut ctx,
+                );
+                Some((pos, safe_neg(score)))
+            })
+            .collect();
+        scores.sort_by_key(|&(pos, score)| (std::cmp::Reverse(score), pos));
+        scores
+    }
+
+    /// after_move を打った場合に相手が返してきそうな手を浅い探索で予測する
+    /// after_move が不正、または相手がパスになる場合は None
+    pub fn predict_opponent_reply(
+        &self,
+        after_move: usize,
+        opponent: Player,
+        depth: usize,
+    ) -> Option<usize> {
+        let board = self.with_move(after_move, opponent.opponent())?;
+        board.analyze(opponent, depth).best_move
+    }
+
+    /// 反復深化探索（時間管理付き）
+    fn iterative_deepening_search(
+        &mut self,
+        player: Player,
+        max_depth: usize,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+        config: &EngineConfig,
+        cancel: Option<&Arc<AtomicBool>>,
+    ) -> (Option<usize>, Option<i32>, SearchStats) {
+        let mut best_move = None;
+        let mut best_eval = None;
+        let mut depth_reached = 0;
+        let mut ctx = SearchContext::new(config);
+
+        let start_time = std::time::Instant::now();
+        let hard_limit = config.move_time_limit;
+        // ノード数の上限だけが指定されていれば、経過時間では打ち切らない
+        let time_limit = match (hard_limit, config.max_nodes) {
+            (Some(limit), _) => Some(limit),
+            (None, Some(_)) => None,
+            (None, None) => Some(std::time::Duration::from_millis(match max_depth {
+                1..=3 => 100,
+                4..=6 => 500,
+                7..=10 => 2000,
+                11..=15 => 5000,
+                _ => 10000,
+            })),
+        };
+        let time_exceeded =
+            |start: std::time::Instant| time_limit.is_some_and(|limit| start.elapsed() > limit);
+
+        // 反復深化
+        for current_depth in 1..=max_depth {
+            if time_exceeded(start_time) && (current_depth > 3 || hard_limit.is_some()) {
+                break;
+            }
+            if ctx.is_cancelled() {
+                break;
+            }
+
+            // 時間・ノード数の制限や中止は、最初の深さを読み終えてから効くようにする
+            // （中止されても、打てる手があれば必ず手を返す）
+            if best_move.is_some() {
+                ctx.deadline = hard_limit.map(|limit| start_time + limit);
+                ctx.node_limit = config.max_nodes;
+                ctx.cancel = cancel.cloned();
+            }
+
+            let result = self.minimax_best_move_with_tt_internal(
                 player,
-                depth as u8,
-                alpha,
-                beta,
-                0,
-                false,
+                current_depth,
                 tt,
-                pv_table,
-                killer_moves,
-                history_table,
+                &mut ctx,
+                best_eval.unwrap_or(0),
             );
 
-            if score <= alpha {
-                // Fail low - alpha を下げる
-                alpha = score - window_size;
-                window_size *= 2;
-            } else if score >= beta {
-                // Fail high - beta を上げる
-                beta = score + window_size;
-                window_size *= 2;
-            } else {
-                // 正常な範囲内
-                if let Some(best_move) = pv_table.get_pv_move(0) {
-                    return Some((best_move as usize, score));
-                }
+            // 打ち切られた深さの結果は、途中で見つけた手が良さそうでも使わない
+            // （読み終えた深さの結果だけを正とし、直前の深さの結果を返す）
+            if ctx.stopped {
                 break;
             }
 
-            // ウィンドウが大きくなりすぎたら通常探索に切り替え
-            if window_size > 1000 {
-                return self.minimax_best_move_with_tt(
-                    player,
-                    depth,
-                    tt,
-                    pv_table,
-                    killer_moves,
-                    history_table,
-                );
+            if let Some((mv, eval)) = result {
+                best_move = Some(mv);
+                best_eval = Some(eval);
+                depth_reached = current_depth;
+
+                // 時間制限チェック
+                if time_exceeded(start_time) {
+                    break;
+                }
             }
         }
 
-        None
+        // History Table の老化
+        ctx.history_table.age();
+
+        let stats = SearchStats {
+            depth_reached,
+            nodes: ctx.nodes,
+            tt_entries: tt.len(),
+            ..SearchStats::default()
+        };
+        (best_move, best_eval, stats)
+    }
+
+    /// レベル1用の手の選択
+    /// 温度が0なら簡易評価が最も高い手、正なら簡易評価の softmax で近い評価の手から選ぶ
+    fn level1_move(&self, player: Player, config: &EngineConfig) -> Option<usize> {
+        if config.temperature <= 0.0 {
+            return self.level1_best_move(player, &config.eval);
+        }
+
+        // 局面ごとに違う手を選べるよう、種に盤面のハッシュを混ぜる
+        let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut rng = StdRng::seed_from_u64(seed ^ self.zobrist_hash(player));
+
+        let scored: Vec<(usize, i32)> = self
+            .legal_moves_iter(player)
+            .map(|pos| (pos, self.evaluate_move_fast(pos, player, &config.eval)))
+            .collect();
+        let best_score = scored.iter().map(|&(_, score)| score).max()?;
+        let weights = scored
+            .iter()
+            .map(|&(_, score)| ((score - best_score) as f64 / config.temperature).exp());
+        let index = WeightedIndex::new(weights).ok()?.sample(&mut rng);
+        Some(scored[index].0)
     }
 
     /// レベル1用の高速な最善手探索
-    fn level1_best_move(&self, player: Player) -> Option<usize> {
+    fn level1_best_move(&self, player: Player, params: &EvalParams) -> Option<usize> {
         let legal_moves = self.get_legal_moves(player);
         if legal_moves == 0 {
             return None;
@@ -380,7 +1368,7 @@ impl BitBoard {
         for pos in 0..64 {
             let bit = 1u64 << pos;
             if (legal_moves & bit) != 0 {
-                let score = self.evaluate_move_fast(pos, player);
+                let score = self.evaluate_move_fast(pos, player, params);
                 if score > best_score {
                     best_score = score;
                     best_move = Some(pos);
@@ -391,117 +1379,210 @@ impl BitBoard {
         best_move
     }
 
-    /// Transposition Table を使った最善手探索
-    fn minimax_best_move_with_tt(
+    /// 内部的な Minimax 実装（高度な最適化版）
+    fn minimax_best_move_with_tt_internal(
         &mut self,
         player: Player,
         depth: usize,
         tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-        pv_table: &mut PVTable,
-        killer_moves: &mut KillerMoves,
-        history_table: &mut HistoryTable,
+        ctx: &mut SearchContext,
+        prev_score: i32,
     ) -> Option<(usize, i32)> {
+        if uses_parallel_search(depth, &ctx.config) {
+            // 並列探索を使用
+            self.parallel_search(player, depth, tt, ctx)
+        } else {
+            // 逐次探索を使用
+            self.sequential_search(player, depth, tt, ctx, prev_score)
+        }
+    }
+
+    /// この深さでルートをどの窓で読むかを決める（読まずに決まる場合は Err で結果を返す）
+    fn root_plan(
+        &self,
+        player: Player,
+        depth: usize,
+        ctx: &SearchContext,
+        prev_score: i32,
+    ) -> Result<RootPlan, Option<(usize, i32)>> {
         if depth == 1 {
-            if let Some(pos) = self.level1_best_move(player) {
-                return Some((pos, 0));
+            if let Some(pos) = self.level1_move(player, &ctx.config) {
+                return Err(Some((pos, 0)));
             }
         }
-
-        let legal_moves = self.get_legal_moves(player);
-        if legal_moves == 0 {
-            return None;
+        if self.get_legal_moves(player) == 0 {
+            return Err(None);
         }
 
-        pv_table.length[0] = 0;
-
-        let score = self.minimax_with_tt_internal(
-            player,
-            depth as u8,
-            i32::MIN + 1,
-            i32::MAX - 1,
-            0,
-            false,
-            tt,
-            pv_table,
-            killer_moves,
-            history_table,
-        );
-
-        if let Some(best_move) = pv_table.get_pv_move(0) {
-            Some((best_move as usize, score))
+        Ok(if depth <= 3 {
+            RootPlan::FullWindow
+        } else if ctx.config.use_mtdf {
+            RootPlan::Mtdf {
+                guess: prev_score,
+                lower: i32::MIN + 1,
+                upper: i32::MAX - 1,
+                best_move: None,
+            }
         } else {
-            None
-        }
+            RootPlan::Aspiration {
+                alpha: prev_score - ASPIRATION_WINDOW,
+                beta: prev_score + ASPIRATION_WINDOW,
+                window_size: ASPIRATION_WINDOW,
+            }
+        })
     }
 
-    /// 内部的な Minimax 実装（高度な最適化版）
-    fn minimax_best_move_with_tt_internal(
+    /// ルートを1スレッドで読む（設定に応じて MTD(f) か Aspiration Window）
+    fn sequential_search(
         &mut self,
         player: Player,
         depth: usize,
         tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-        pv_table: &mut PVTable,
-        killer_moves: &mut KillerMoves,
-        history_table: &mut HistoryTable,
+        ctx: &mut SearchContext,
+        prev_score: i32,
     ) -> Option<(usize, i32)> {
-        if depth >= 8 {
-            // 並列探索を使用
-            self.parallel_search(player, depth, tt, pv_table, killer_moves, history_table)
-        } else {
-            // 逐次探索を使用
-            self.sequential_search(player, depth, tt, pv_table, killer_moves, history_table)
+        let mut plan = match self.root_plan(player, depth, ctx, prev_score) {
+            Ok(plan) => plan,
+            Err(result) => return result,
+        };
+        let root_key = tt_key(self, player, ctx.config.tt_keying);
+
+        loop {
+            let (alpha, beta) = plan.window();
+            ctx.pv_table.length[0] = 0; // PV をリセット
+
+            let score =
+                self.minimax_with_tt_internal(player, depth as u8, alpha, beta, 0, false, tt, ctx);
+            if ctx.stopped {
+                return None;
+            }
+
+            if let Some(result) = plan.advance(score, ctx, tt, root_key) {
+                return result;
+            }
         }
     }
 
-    /// 並列探索の実装（簡略化）
+    /// ルートでの並列探索
+    ///
+    /// 並び替えで最も良さそうな手を先に1人で読んで評価値の下限を決め、残りの手をスレッドに分ける。
+    /// 各スレッドは自分用の置換表と手の並び替え用テーブルを持ち、下限は全スレッドで共有して、
+    /// 誰かがより良い手を見つけたら以降の探索の窓を狭める。読み終えたら各スレッドの置換表を
+    /// 元の置換表にまとめる（同じ局面なら深く読んだ方を残す）。
     fn parallel_search(
         &mut self,
         player: Player,
         depth: usize,
         tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-        pv_table: &mut PVTable,
-        killer_moves: &mut KillerMoves,
-        history_table: &mut HistoryTable,
-    ) -> Option<(usize, i32)> {
-        // 深い探索でも通常の逐次探索を使用（並列処理のオーバーヘッドを避ける）
-        self.sequential_search(player, depth, tt, pv_table, killer_moves, history_table)
-    }
-
-    /// 逐次探索の実装
-    fn sequential_search(
-        &mut self,
-        player: Player,
-        depth: usize,
-        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-        pv_table: &mut PVTable,
-        killer_moves: &mut KillerMoves,
-        history_table: &mut HistoryTable,
+        ctx: &mut SearchContext,
     ) -> Option<(usize, i32)> {
         let legal_moves = self.get_legal_moves(player);
         if legal_moves == 0 {
             return None;
         }
 
-        pv_table.length[0] = 0;
-
-        let best_score = self.minimax_with_tt_internal(
-            player,
-            depth as u8,
+        ctx.pv_table.length[0] = 0;
+        let moves = self.order_moves(legal_moves, player, depth as u8, 0, ctx);
+        let (&first, rest) = moves.split_first()?;
+        let child_depth = depth as u8 - 1;
+
+        // 最初の手は full window で読む
+        let mut first_board = *self;
+        first_board.flip_bits(first.flips | (1u64 << first.position), player);
+        let first_score = safe_neg(first_board.minimax_with_tt_internal(
+            player.opponent(),
+            child_depth,
             i32::MIN + 1,
             i32::MAX - 1,
-            0,
+            1,
             false,
             tt,
-            pv_table,
-            killer_moves,
-            history_table,
-        );
+            ctx,
+        ));
+        if ctx.stopped {
+            return None;
+        }
 
-        if let Some(best_move) = pv_table.get_pv_move(0) {
-            Some((best_move as usize, best_score))
-        } else {
-            None
+        let shared_alpha = AtomicI32::new(first_score);
+        let results: Vec<_> = rest
+            .par_iter()
+            .map(|&mv| {
+                let mut local_ctx = ctx.clone();
+                local_ctx.nodes = 0;
+                let mut local_tt = FxHashMap::default();
+                let mut new_board = *self;
+                new_board.flip_bits(mv.flips | (1u64 << mv.position), player);
+
+                // Null window で下限を超えるか確かめ、超えたら同じ下限で読み直して正確な値を求める
+                let alpha = shared_alpha.load(AtomicOrdering::Relaxed);
+                let mut score = safe_neg(new_board.minimax_with_tt_internal(
+                    player.opponent(),
+                    child_depth,
+                    safe_neg(alpha) - 1,
+                    safe_neg(alpha),
+                    1,
+                    false,
+                    &mut local_tt,
+                    &mut local_ctx,
+                ));
+                if score > alpha && !local_ctx.stopped {
+                    score = safe_neg(new_board.minimax_with_tt_internal(
+                        player.opponent(),
+                        child_depth,
+                        i32::MIN + 1,
+                        safe_neg(alpha),
+                        1,
+                        false,
+                        &mut local_tt,
+                        &mut local_ctx,
+                    ));
+                    shared_alpha.fetch_max(score, AtomicOrdering::Relaxed);
+                }
+                // 下限以下なら値は上界でしかないので、最善手の候補にしない
+                (mv.position, score, score > alpha, local_tt, local_ctx)
+            })
+            .collect();
+
+        let mut best_move = first.position;
+        let mut best_score = first_score;
+        let mut best_pv = None;
+        for (position, score, exact, local_tt, local_ctx) in results {
+            ctx.nodes += local_ctx.nodes;
+            ctx.stopped |= local_ctx.stopped;
+            for (key, entry) in local_tt {
+                match tt.get(&key) {
+                    Some(existing) if existing.depth >= entry.depth => {}
+                    _ => {
+                        tt.insert(key, entry);
+                    }
+                }
+            }
+            // 同じ評価値なら並び替えで先にあった手を選ぶ
+            if exact && score > best_score {
+                best_move = position;
+                best_score = score;
+                best_pv = Some(local_ctx.pv_table);
+            }
+        }
+        if ctx.stopped {
+            return None;
         }
+
+        if let Some(pv_table) = best_pv {
+            ctx.pv_table = pv_table;
+        }
+        ctx.pv_table.update(0, best_move, 1);
+        tt.insert(
+            tt_key(self, player, ctx.config.tt_keying),
+            Entry {
+                score: best_score,
+                depth: depth as u8,
+                flag: NodeType::Exact,
+                best_move: Some(best_move),
+            },
+        );
+
+        Some((best_move as usize, best_score))
     }
 
     /// 手の並び替え（高度な最適化版）
@@ -509,18 +1590,18 @@ impl BitBoard {
         &self,
         legal_moves: u64,
         player: Player,
+        depth: u8,
         ply: usize,
-        pv_table: &PVTable,
-        killer_moves: &KillerMoves,
-        history_table: &HistoryTable,
+        ctx: &SearchContext,
     ) -> Vec<Move> {
         let mut moves = Vec::new();
         let phase = GamePhase::from_empty_count(64 - (self.black | self.white).count_ones());
         let phase_idx = phase.index();
-        let player_idx = match player {
-            Player::Black => 0,
-            Player::White => 1,
-        };
+        let player_idx = player.index();
+
+        // 中盤以降は確定石を増やす手を優先する（着手前の確定石は必要になった時に一度だけ計算）
+        let order_by_stability = phase != GamePhase::Early && depth >= STABILITY_ORDER_MIN_DEPTH;
+        let mut stable_before = None;
 
         for pos in 0..64 {
             let bit = 1u64 << pos;
@@ -531,38 +1612,44 @@ impl BitBoard {
             let mut score = 0;
 
             // PV move が最優先
-            if let Some(pv_move) = pv_table.get_pv_move(ply) {
+            if let Some(pv_move) = ctx.pv_table.get_pv_move(ply) {
                 if pv_move == pos as u8 {
                     score += 10000;
                 }
             }
 
             // Killer moves
-            if killer_moves.is_killer(ply, pos as u8) {
+            if ctx.killer_moves.is_killer(ply, pos as u8) {
                 score += 5000;
             }
 
             // History heuristic
-            score += history_table.get_score(phase_idx, player_idx, pos as u8);
+            score += ctx
+                .history_table
+                .get_score(phase_idx, player_idx, pos as u8);
 
             // 位置の価値
-            let row = pos / 8;
-            let col = pos % 8;
-            score += POSITION_SCORE[row][col];
+            score += ctx.config.eval.position_value(pos);
 
             // 角の特別ボーナス
             if pos == 0 || pos == 7 || pos == 56 || pos == 63 {
-                score += CORNER_WEIGHT;
+                score += ctx.config.eval.corner_weight;
             }
 
-            // モビリティの評価
+            // モビリティの評価（ひっくり返る石は着手時にも使うので保持する）
             let flips = self.compute_flips(pos, player);
             score += flips.count_ones() as i32 * 10;
 
-            moves.push(Move::new(pos as u8, score));
+            // 確定石が増える手のボーナス
+            if order_by_stability {
+                score += self.stable_disc_gain(pos, flips, player, &mut stable_before)
+                    * STABILITY_ORDER_WEIGHT;
+            }
+
+            moves.push(Move::new(pos as u8, score, flips));
         }
 
-        moves.sort_unstable();
+        moves.sort();
         moves
     }
 
@@ -571,33 +1658,62 @@ impl BitBoard {
         &mut self,
         player: Player,
         depth: u8,
-        mut alpha: i32,
+        alpha: i32,
         beta: i32,
         ply: usize,
         null_move: bool,
         tt: &mut FxHashMap<(u64, u64, u8), Entry>,
-        pv_table: &mut PVTable,
-        killer_moves: &mut KillerMoves,
-        history_table: &mut HistoryTable,
+        ctx: &mut SearchContext,
     ) -> i32 {
-        let original_alpha = alpha;
-        pv_table.length[ply] = 0;
+        let mut node = NodeSearch::new(player, depth, alpha, beta, ply);
+        if let Some(score) = self.begin_node(&mut node, tt, ctx) {
+            return score;
+        }
+
+        while !node.is_finished() {
+            self.search_next_move(&mut node, tt, ctx);
+
+            // 打ち切られた探索の結果は置換表にも入れない
+            if ctx.stopped {
+                return 0;
+            }
+        }
+
+        self.finish_node(node, tt)
+    }
+
+    /// 節点の探索を始める
+    /// 置換表・末端・終局・パスで値が決まればその値を返し、決まらなければ読む手を並べて None を返す
+    fn begin_node(
+        &self,
+        node: &mut NodeSearch,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+        ctx: &mut SearchContext,
+    ) -> Option<i32> {
+        let (player, depth, ply) = (node.player, node.depth, node.ply);
+        ctx.pv_table.length[ply] = 0;
+
+        // 制限時間切れ（戻り値は呼び出し側で捨てられる）
+        if ctx.should_stop() {
+            return Some(0);
+        }
 
         // Transposition Table の確認
-        let tt_key = (self.black, self.white, player as u8);
+        let tt_key = tt_key(self, player, ctx.config.tt_keying);
+        node.tt_key = tt_key;
         if let Some(entry) = tt.get(&tt_key) {
             if entry.depth >= depth {
                 match entry.flag {
-                    NodeType::Exact => return entry.score,
+                    NodeType::Exact => return Some(entry.score),
                     NodeType::LowerBound => {
-                        if entry.score >= beta {
-                            return entry.score;
+                        if entry.score >= node.beta {
+                            return Some(entry.score);
                         }
-                        alpha = alpha.max(entry.score);
+                        node.alpha = node.alpha.max(entry.score);
                     }
                     NodeType::UpperBound => {
-                        if entry.score <= alpha {
-                            return entry.score;
+                        if entry.score <= node.alpha {
+                            return Some(entry.score);
                         }
                     }
                 }
@@ -606,7 +1722,7 @@ impl BitBoard {
 
         // 終端ノード
         if depth == 0 {
-            let score = self.evaluate_board_optimized(player);
+            let score = self.evaluate_board_optimized(player, &ctx.config);
             tt.insert(
                 tt_key,
                 Entry {
@@ -616,7 +1732,7 @@ impl BitBoard {
                     best_move: None,
                 },
             );
-            return score;
+            return Some(score);
         }
 
         // ゲーム終了チェック
@@ -631,7 +1747,7 @@ impl BitBoard {
                     best_move: None,
                 },
             );
-            return score;
+            return Some(score);
         }
 
         let legal_moves = self.get_legal_moves(player);
@@ -642,15 +1758,16 @@ impl BitBoard {
             let score = safe_neg(pass_board.minimax_with_tt_internal(
                 player.opponent(),
                 depth - 1,
-                safe_neg(beta),
-                safe_neg(alpha),
+                safe_neg(node.beta),
+                safe_neg(node.alpha),
                 ply + 1,
                 false,
                 tt,
-                pv_table,
-                killer_moves,
-                history_table,
+                ctx,
             ));
+            if ctx.stopped {
+                return Some(0);
+            }
 
             tt.insert(
                 tt_key,
@@ -662,166 +1779,169 @@ impl BitBoard {
                 },
             );
 
-            return score;
+            return Some(score);
         }
 
         // Null Move Pruning は削除（オセロには適用不可）
 
         // Futility Pruning
-        let futility_prune = depth < 5 && !self.is_endgame();
-        let static_eval = if futility_prune {
-            self.evaluate_board_optimized(player)
-        } else {
-            0
-        };
+        node.futility_prune = ctx.config.forward_pruning && depth < 5 && !self.is_endgame();
+        if node.futility_prune {
+            node.static_eval = self.evaluate_board_optimized(player, &ctx.config);
+        }
 
-        let moves = self.order_moves(
-            legal_moves,
-            player,
-            ply,
-            pv_table,
-            killer_moves,
-            history_table,
-        );
-        let mut best_score = i32::MIN;
-        let mut best_move = None;
-        let phase = GamePhase::from_empty_count(64 - (self.black | self.white).count_ones());
-        let phase_idx = phase.index();
-        let player_idx = match player {
-            Player::Black => 0,
-            Player::White => 1,
-        };
+        node.moves = self.order_moves(legal_moves, player, depth, ply, ctx);
+        node.phase_idx =
+            GamePhase::from_empty_count(64 - (self.black | self.white).count_ones()).index();
+        None
+    }
 
-        for (move_count, &mv) in moves.iter().enumerate() {
-            let pos = mv.position as usize;
+    /// 節点の次の手を1つ読む（打ち切られたかどうかは ctx.stopped で確かめる）
+    fn search_next_move(
+        &self,
+        node: &mut NodeSearch,
+        tt: &mut FxHashMap<(u64, u64, u8), Entry>,
+        ctx: &mut SearchContext,
+    ) {
+        let (player, depth, ply) = (node.player, node.depth, node.ply);
+        let (alpha, beta) = (node.alpha, node.beta);
+        let move_count = node.next_move;
+        let mv = node.moves[move_count];
+        node.next_move += 1;
+        let pos = mv.position as usize;
 
-            // Futility Pruning
-            if futility_prune && move_count > 0 {
-                if static_eval + FUTILITY_MARGIN[depth as usize] <= alpha {
-                    continue;
-                }
+        // Futility Pruning
+        if node.futility_prune && move_count > 0 {
+            if node.static_eval + FUTILITY_MARGIN[depth as usize] <= alpha {
+                return;
             }
+        }
 
-            let mut new_board = *self;
-            if !new_board.make_move(pos, player) {
-                continue;
-            }
+        // 並び替えで計算済みのひっくり返しを使って着手（compute_flipsの再計算を避ける）
+        if mv.flips == 0 {
+            return;
+        }
+        let mut new_board = *self;
+        new_board.flip_bits(mv.flips | (1u64 << pos), player);
 
-            let mut score;
+        let mut score;
 
-            // PVS (Principal Variation Search)
-            if move_count == 0 {
-                // 最初の手は full window で探索
-                score = safe_neg(new_board.minimax_with_tt_internal(
-                    player.opponent(),
-                    depth - 1,
-                    safe_neg(beta),
-                    safe_neg(alpha),
-                    ply + 1,
-                    false,
-                    tt,
-                    pv_table,
-                    killer_moves,
-                    history_table,
-                ));
+        // PVS (Principal Variation Search)
+        if move_count == 0 {
+            // 最初の手は full window で探索
+            score = safe_neg(new_board.minimax_with_tt_internal(
+                player.opponent(),
+                depth - 1,
+                safe_neg(beta),
+                safe_neg(alpha),
+                ply + 1,
+                false,
+                tt,
+                ctx,
+            ));
+        } else {
+            // Late Move Reduction
+            let reduction = if ctx.config.forward_pruning
+                && depth >= LMR_DEPTH_THRESHOLD
+                && move_count >= LMR_MOVE_THRESHOLD
+                && !ctx.killer_moves.is_killer(ply, mv.position)
+            {
+                1
             } else {
-                // Late Move Reduction
-                let reduction = if depth >= LMR_DEPTH_THRESHOLD
-                    && move_count >= LMR_MOVE_THRESHOLD
-                    && !killer_moves.is_killer(ply, mv.position)
-                {
-                    1
-                } else {
-                    0
-                };
+                0
+            };
+
+            let search_depth = depth.saturating_sub(1 + reduction);
 
-                let search_depth = depth.saturating_sub(1 + reduction);
+            // Null window で探索
+            score = safe_neg(new_board.minimax_with_tt_internal(
+                player.opponent(),
+                search_depth,
+                safe_neg(alpha) - 1,
+                safe_neg(alpha),
+                ply + 1,
+                false,
+                tt,
+                ctx,
+            ));
 
-                // Null window で探索
+            // Re-search が必要な場合
+            if score > alpha && (reduction > 0 || score < beta) {
                 score = safe_neg(new_board.minimax_with_tt_internal(
                     player.opponent(),
-                    search_depth,
-                    safe_neg(alpha) - 1,
+                    depth - 1,
+                    safe_neg(beta),
                     safe_neg(alpha),
                     ply + 1,
                     false,
                     tt,
-                    pv_table,
-                    killer_moves,
-                    history_table,
+                    ctx,
                 ));
-
-                // Re-search が必要な場合
-                if score > alpha && (reduction > 0 || score < beta) {
-                    score = safe_neg(new_board.minimax_with_tt_internal(
-                        player.opponent(),
-                        depth - 1,
-                        safe_neg(beta),
-                        safe_neg(alpha),
-                        ply + 1,
-                        false,
-                        tt,
-                        pv_table,
-                        killer_moves,
-                        history_table,
-                    ));
-                }
             }
+        }
 
-            if score > best_score {
-                best_score = score;
-                best_move = Some(mv.position);
-
-                // PV の更新
-                pv_table.update(ply, mv.position, ply + 1);
-
-                if score > alpha {
-                    alpha = score;
-
-                    // History heuristic の更新
-                    history_table.update(phase_idx, player_idx, mv.position, depth, true);
-
-                    if score >= beta {
-                        // Killer move の追加
-                        killer_moves.add_killer(ply, mv.position);
-
-                        // 残りの手の history を減点
-                        for &remaining_move in moves.iter().skip(move_count + 1) {
-                            history_table.update(
-                                phase_idx,
-                                player_idx,
-                                remaining_move.position,
-                                depth,
-                                false,
-                            );
-                        }
+        if ctx.stopped {
+            return;
+        }
 
-                        break; // Beta cutoff
+        if score > node.best_score {
+            node.best_score = score;
+            node.best_move = Some(mv.position);
+
+            // PV の更新
+            ctx.pv_table.update(ply, mv.position, ply + 1);
+
+            if score > alpha {
+                node.alpha = score;
+
+                // History heuristic の更新
+                let player_idx = player.index();
+                ctx.history_table
+                    .update(node.phase_idx, player_idx, mv.position, depth, true);
+
+                if score >= beta {
+                    // Killer move の追加
+                    ctx.killer_moves.add_killer(ply, mv.position);
+
+                    // 残りの手の history を減点
+                    for &remaining_move in node.moves.iter().skip(move_count + 1) {
+                        ctx.history_table.update(
+                            node.phase_idx,
+                            player_idx,
+                            remaining_move.position,
+                            depth,
+                            false,
+                        );
                     }
+
+                    node.next_move = node.moves.len(); // Beta cutoff
                 }
             }
         }
+    }
 
+    /// すべての手を読み終えた節点の値を置換表に保存して返す
+    fn finish_node(&self, node: NodeSearch, tt: &mut FxHashMap<(u64, u64, u8), Entry>) -> i32 {
         // Transposition Table への保存
-        let flag = if best_score <= original_alpha {
+        let flag = if node.best_score <= node.original_alpha {
             NodeType::UpperBound
-        } else if best_score >= beta {
+        } else if node.best_score >= node.beta {
             NodeType::LowerBound
         } else {
             NodeType::Exact
         };
 
         tt.insert(
-            tt_key,
+            node.tt_key,
             Entry {
-                score: best_score,
-                depth,
+                score: node.best_score,
+                depth: node.depth,
                 flag,
-                best_move,
+                best_move: node.best_move,
             },
         );
 
-        best_score
+        node.best_score
     }
 
     /// 終盤判定の最適化
@@ -830,138 +1950,213 @@ impl BitBoard {
         (self.black | self.white).count_ones() >= 55
     }
 
-    /// Transposition Table のクリーンアップ（改良版）
-    fn cleanup_tt(&self, tt: &mut FxHashMap<(u64, u64, u8), Entry>) {
-        if tt.len() <= MAX_TT_SIZE {
-            return;
-        }
-
-        // より効率的なクリーンアップ：深度の低いエントリから削除
-        let target_size = MAX_TT_SIZE * 3 / 4;
-        let mut to_remove = Vec::new();
-
-        for (key, entry) in tt.iter() {
-            if entry.depth <= 2 {
-                to_remove.push(*key);
-                if to_remove.len() + target_size >= tt.len() {
-                    break;
-                }
-            }
-        }
-
-        for key in to_remove {
-            tt.remove(&key);
-        }
-    }
-
     /// 最適化された盤面評価関数
-    fn evaluate_board_optimized(&self, player: Player) -> i32 {
+    fn evaluate_board_optimized(&self, player: Player, config: &EngineConfig) -> i32 {
         let empty_count = 64 - (self.black | self.white).count_ones();
         let phase = GamePhase::from_empty_count(empty_count);
 
         // ゲーム終了チェックを慎重に
-        let black_legal = self.get_legal_moves(Player::Black);
-        let white_legal = self.get_legal_moves(Player::White);
-
-        if black_legal == 0 && white_legal == 0 {
+        if Player::all()
+            .into_iter()
+            .all(|side| self.get_legal_moves(side) == 0)
+        {
             return self.evaluate_game_end(player);
         }
 
+        let params = &config.eval;
         let mut score = 0;
 
         match phase {
             GamePhase::Early => {
                 // 序盤はモビリティと位置を重視、石数差は控えめ
-                score += self.evaluate_mobility(player) * MOBILITY_WEIGHT[0];
-                score += self.evaluate_position_value(player);
-                score += self.evaluate_disc_count(player) * DISC_DIFF_WEIGHT[0];
+                score +=
+                    self.evaluate_mobility(player, params.pass_bonus) * params.mobility_weight[0];
+                score += self.evaluate_position_value(player, &params.position_values);
+                score += self.evaluate_disc_count(player) * params.disc_diff_weight_at(empty_count);
+                if params.frontier_weight != 0 {
+                    score += self.evaluate_frontier(player) * params.frontier_weight;
+                }
             }
             GamePhase::Mid => {
                 // 中盤はバランス重視
-                score += self.evaluate_mobility(player) * MOBILITY_WEIGHT[1];
-                score += self.evaluate_position_value(player);
-                score += self.evaluate_corners_optimized(player);
-                score += self.evaluate_stability(player);
-                score += self.evaluate_disc_count(player) * DISC_DIFF_WEIGHT[1];
+                score +=
+                    self.evaluate_mobility(player, params.pass_bonus) * params.mobility_weight[1];
+                score += self.evaluate_position_value(player, &params.position_values);
+                score += self.evaluate_corners_optimized(player, params.corner_weight);
+                score += self.evaluate_stability(player) * params.stability_weight;
+                score += self.evaluate_disc_count(player) * params.disc_diff_weight_at(empty_count);
+                if params.frontier_weight != 0 {
+                    score += self.evaluate_frontier(player) * params.frontier_weight;
+                }
             }
             GamePhase::End => {
                 // 終盤は石数と確定石を重視
-                score += self.evaluate_disc_count(player) * DISC_DIFF_WEIGHT[2];
-                score += self.evaluate_corners_optimized(player);
-                score += self.evaluate_stability(player) * 2;
+                score += self.evaluate_disc_count(player) * params.disc_diff_weight_at(empty_count);
+                score += self.evaluate_corners_optimized(player, params.corner_weight);
+                score += self.evaluate_stability(player) * 2 * params.stability_weight;
                 score += self.evaluate_parity(player);
-                score += self.evaluate_mobility(player) * MOBILITY_WEIGHT[2];
+                score +=
+                    self.evaluate_mobility(player, params.pass_bonus) * params.mobility_weight[2];
             }
         }
 
+        // 個性によるノイズ
+        if config.randomness > 0 {
+            score += rand::thread_rng().gen_range(-config.randomness..=config.randomness);
+        }
+
         score
     }
 
     /// ゲーム終了時の評価
     #[inline]
     fn evaluate_game_end(&self, player: Player) -> i32 {
-        let black_count = self.black.count_ones() as i32;
-        let white_count = self.white.count_ones() as i32;
-        let total_discs = black_count + white_count;
-
         // 序盤の調整を削除（実際のオセロでは石が10個未満になることは稀）
+        final_score(self.final_disc_diff(player))
+    }
 
-        let diff = match player {
-            Player::Black => black_count - white_count,
-            Player::White => white_count - black_count,
-        };
+    /// player から見た石数差
+    #[inline(always)]
+    fn final_disc_diff(&self, player: Player) -> i32 {
+        let (my_board, opp_board) = self.boards(player);
+        my_board.count_ones() as i32 - opp_board.count_ones() as i32
+    }
 
-        if diff > 0 {
-            10000 + diff
-        } else if diff < 0 {
-            -10000 + diff
-        } else {
-            0
+    /// 完全読みによる終盤解析
+    /// 空きマスが empty_threshold 以下の場合のみ、player から見た最終石差を返す（正なら player の勝ち）
+    pub fn solve_endgame(&mut self, player: Player, empty_threshold: u32) -> Option<i32> {
+        if self.count_empty() > empty_threshold {
+            return None;
         }
+        debug_assert!(self.validate().is_ok(), "不正な盤面で完全読みしています");
+
+        Some(self.solve_exact(player, -64, 64, false))
+    }
+
+    /// 完全読みで最善手とその最終石差を求める
+    pub fn solve_endgame_best_move(&self, player: Player) -> Option<(usize, i32)> {
+        let legal_moves = self.get_legal_moves(player);
+        if legal_moves == 0 {
+            return None;
+        }
+
+        let mut best_move = None;
+        let mut alpha = -65;
+
+        for (pos, flips) in self.solver_move_order(legal_moves, player) {
+            let mut new_board = *self;
+            new_board.flip_bits(flips | (1u64 << pos), player);
+
+            let score = -new_board.solve_exact(player.opponent(), -64, -alpha, false);
+            if score > alpha {
+                alpha = score;
+                best_move = Some(pos);
+            }
+        }
+
+        best_move.map(|pos| (pos, alpha))
+    }
+
+    /// 勝ち・負け・引き分けだけを求める完全読み（player から見た結果）
+    /// 石差を求めず ±1 の窓で探索するので solve_endgame より速い
+    pub fn solve_wld(&self, player: Player) -> Ordering {
+        self.solve_exact(player, -1, 1, false).cmp(&0)
+    }
+
+    /// 完全読み用の negamax（アルファベータ枝刈り）
+    fn solve_exact(&self, player: Player, mut alpha: i32, beta: i32, passed: bool) -> i32 {
+        let legal_moves = self.get_legal_moves(player);
+
+        // パスの処理（連続パスなら終局）
+        if legal_moves == 0 {
+            if passed {
+                return self.final_disc_diff(player);
+            }
+            return -self.solve_exact(player.opponent(), -beta, -alpha, true);
+        }
+
+        let mut best_score = -65;
+
+        for (pos, flips) in self.solver_move_order(legal_moves, player) {
+            let mut new_board = *self;
+            new_board.flip_bits(flips | (1u64 << pos), player);
+
+            let score = -new_board.solve_exact(player.opponent(), -beta, -alpha, false);
+            if score > best_score {
+                best_score = score;
+                if score > alpha {
+                    alpha = score;
+                    if alpha >= beta {
+                        break; // Beta cutoff
+                    }
+                }
+            }
+        }
+
+        best_score
+    }
+
+    /// 完全読み用の手の並び替え（相手の合法手が少ない順）
+    fn solver_move_order(&self, legal_moves: u64, player: Player) -> Vec<(usize, u64)> {
+        let mut moves = Vec::with_capacity(legal_moves.count_ones() as usize);
+        let mut remaining = legal_moves;
+
+        while remaining != 0 {
+            let pos = remaining.trailing_zeros() as usize;
+            remaining &= remaining - 1;
+            moves.push((pos, self.compute_flips(pos, player)));
+        }
+
+        // 空きが少ないうちは並び替えのコストの方が高い
+        if self.count_empty() >= SOLVER_ORDERING_EMPTIES {
+            moves.sort_by_cached_key(|&(pos, flips)| {
+                let mut new_board = *self;
+                new_board.flip_bits(flips | (1u64 << pos), player);
+                new_board.legal_move_count(player.opponent())
+            });
+        }
+
+        moves
     }
 
     /// モビリティ評価の最適化
     #[inline]
-    fn evaluate_mobility(&self, player: Player) -> i32 {
-        let my_moves = self.get_legal_moves(player).count_ones() as i32;
-        let opp_moves = self.get_legal_moves(player.opponent()).count_ones() as i32;
+    fn evaluate_mobility(&self, player: Player, pass_bonus: i32) -> i32 {
+        let my_moves = self.legal_move_count(player) as i32;
+        let opp_moves = self.legal_move_count(player.opponent()) as i32;
 
         let mobility_diff = my_moves - opp_moves;
 
         // パスを強制する場合のボーナス
         if opp_moves == 0 && my_moves > 0 {
-            mobility_diff + PASS_BONUS
+            mobility_diff + pass_bonus
         } else if my_moves == 0 && opp_moves > 0 {
             // 自分がパスする場合のペナルティ
-            mobility_diff - PASS_BONUS
+            mobility_diff - pass_bonus
         } else {
             mobility_diff
         }
     }
 
-    /// 位置価値の評価
+    /// フロンティアの評価（空きマスに接する石が相手より少ないほど良い）
     #[inline]
-    fn evaluate_position_value(&self, player: Player) -> i32 {
-        let mut score = 0;
-        let (my_board, opp_board) = match player {
-            Player::Black => (self.black, self.white),
-            Player::White => (self.white, self.black),
-        };
+    fn evaluate_frontier(&self, player: Player) -> i32 {
+        let my_frontier = self.get_frontier_discs(player).count_ones() as i32;
+        let opp_frontier = self.get_frontier_discs(player.opponent()).count_ones() as i32;
 
-        for pos in 0..64 {
-            let bit = 1u64 << pos;
-            let row = pos / 8;
-            let col = pos % 8;
-            let value = POSITION_SCORE[row][col];
-
-            if (my_board & bit) != 0 {
-                score += value;
-            } else if (opp_board & bit) != 0 {
-                score -= value;
-            }
-        }
+        opp_frontier - my_frontier
+    }
 
-        score
+    /// 位置価値の評価
+    #[inline]
+    fn evaluate_position_value(&self, player: Player, position_values: &[i32; 10]) -> i32 {
+        let value = |pos: usize| position_values[SQUARE_CLASS[pos]];
+
+        self.disc_positions(player).map(value).sum::<i32>()
+            - self
+                .disc_positions(player.opponent())
+                .map(value)
+                .sum::<i32>()
     }
 
     /// 石数差の評価
@@ -973,192 +2168,914 @@ impl BitBoard {
     }
 
     /// 角の評価の最適化
-    fn evaluate_corners_optimized(&self, player: Player) -> i32 {
-        const CORNERS: [usize; 4] = [0, 7, 56, 63];
-        let mut score = 0;
+    fn evaluate_corners_optimized(&self, player: Player, corner_weight: i32) -> i32 {
+        let (my_board, opp_board) = self.boards(player);
+        let my_corners = (my_board & Self::CORNER_MASK).count_ones() as i32;
+        let opp_corners = (opp_board & Self::CORNER_MASK).count_ones() as i32;
 
-        for &corner in &CORNERS {
-            let bit = 1u64 << corner;
-            if (self.black & bit) != 0 {
-                score += if player == Player::Black {
-                    CORNER_WEIGHT
-                } else {
-                    -CORNER_WEIGHT
-                };
-            } else if (self.white & bit) != 0 {
-                score += if player == Player::White {
-                    CORNER_WEIGHT
-                } else {
-                    -CORNER_WEIGHT
-                };
+        (my_corners - opp_corners) * corner_weight
+    }
+
+    /// 確定石の評価
+    fn evaluate_stability(&self, player: Player) -> i32 {
+        let my_stable = self.stable_discs(player);
+        let opp_stable = self.stable_discs(player.opponent());
+
+        (my_stable.count_ones() as i32) - (opp_stable.count_ones() as i32)
+    }
+
+    /// 着手で増える自分の確定石の数（手の並び替え用の簡易判定）
+    /// 角を持たない、または辺に触れない手は確定石を増やさないものとみなす
+    fn stable_disc_gain(
+        &self,
+        pos: usize,
+        flips: u64,
+        player: Player,
+        stable_before: &mut Option<u32>,
+    ) -> i32 {
+        let changed = flips | (1u64 << pos);
+        let mut new_board = *self;
+        new_board.flip_bits(changed, player);
+        let (my_board, _) = new_board.boards(player);
+        if (my_board & Self::CORNER_MASK) == 0 || (changed & Self::EDGE_MASK) == 0 {
+            return 0;
+        }
+
+        let before = *stable_before.get_or_insert_with(|| self.stable_discs(player).count_ones());
+        let after = new_board.stable_discs(player).count_ones();
+        after as i32 - before as i32
+    }
+
+    /// パリティの評価
+    fn evaluate_parity(&self, player: Player) -> i32 {
+        let empty_count = 64 - (self.black | self.white).count_ones();
+
+        if empty_count % 2 == 0 {
+            // 偶数なら後手有利
+            if player == Player::White {
+                10
+            } else {
+                -10
             }
+        } else {
+            // 奇数なら先手有利
+            if player == Player::Black {
+                10
+            } else {
+                -10
+            }
+        }
+    }
+
+    /// 高速な手の評価（レベル1用）
+    fn evaluate_move_fast(&self, pos: usize, player: Player, params: &EvalParams) -> i32 {
+        let mut score = params.position_value(pos);
+
+        // 角のボーナス
+        if pos == 0 || pos == 7 || pos == 56 || pos == 63 {
+            score += params.corner_weight;
         }
 
+        // ひっくり返す石の数
+        let flips = self.compute_flips(pos, player);
+        score += flips.count_ones() as i32 * 10;
+
         score
     }
 
-    /// 確定石の評価
-    fn evaluate_stability(&self, player: Player) -> i32 {
-        let my_stable = self.compute_stable_discs(player);
-        let opp_stable = self.compute_stable_discs(player.opponent());
+    /// 浅い手の評価（位置価値・ひっくり返す石数に加えて、相手の応手数を1手だけ先読み）
+    /// 値が大きいほど良い手。不正な手の場合は i32::MIN を返す
+    pub fn evaluate_move(&self, pos: usize, player: Player, params: &EvalParams) -> i32 {
+        debug_assert!(pos < 64, "ビット位置が範囲外です");
 
-        (my_stable.count_ones() as i32) - (opp_stable.count_ones() as i32)
+        let pos_bit = 1u64 << pos;
+        if (self.black | self.white) & pos_bit != 0 {
+            return i32::MIN;
+        }
+
+        let flips = self.compute_flips(pos, player);
+        if flips == 0 {
+            return i32::MIN;
+        }
+
+        let mut score = self.evaluate_move_fast(pos, player, params);
+
+        // 相手の応手が少ないほど良い（パスを強いるならボーナス）
+        let mut new_board = *self;
+        new_board.flip_bits(flips | pos_bit, player);
+        let reply_count = new_board.legal_move_count(player.opponent()) as i32;
+        score -= reply_count * REPLY_MOBILITY_WEIGHT;
+        if reply_count == 0 {
+            score += params.pass_bonus;
+        }
+
+        score
+    }
+
+    /// 探索を行わない静的評価（player から見た値）
+    pub fn static_eval(&self, player: Player) -> i32 {
+        self.evaluate_board_optimized(player, &EngineConfig::default())
+    }
+
+    /// 黒から見た静的評価（正なら黒が有利）
+    pub fn static_eval_absolute(&self) -> i32 {
+        self.static_eval(Player::Black)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::notation;
+    use crate::selftest::random_position_from_seed;
+
+    #[test]
+    fn analyze_detailed_finds_the_wipeout_move() {
+        // 黒は a1 で白石を2つとも返して勝つ（a3 は b2 しか返せない）
+        let position = concat!(
+            ".OX.....", ".O......", "..X.....", "........", "........", "........", "........",
+            "........", " X"
+        );
+        let (board, player) = BitBoard::from_position_string(position).unwrap();
+        let mut moves = board.get_legal_move_positions(player);
+        moves.sort_unstable();
+        assert_eq!(moves, [0, 16]);
+
+        let analysis = board.analyze_detailed(player, 3);
+        assert_eq!(analysis.best_move, Some(0));
+        assert_eq!(analysis.pv, [Some(0)]); // 白石が無くなって終局する
+        let score = analysis.score.unwrap();
+        assert!(score > 0 && win_probability(score) > 0.99);
+        assert!(analysis.stats.nodes > 0);
     }
 
-    /// 確定石の計算（簡略版）
-    fn compute_stable_discs(&self, player: Player) -> u64 {
-        let my_board = match player {
-            Player::Black => self.black,
-            Player::White => self.white,
+    #[test]
+    fn cancelled_worker_returns_before_finishing_the_search() {
+        // 時間制限もノード数の上限も無い深い探索を別スレッドで始め、途中で中止する
+        let config = EngineConfig {
+            move_time_limit: None,
+            max_nodes: None,
+            ..EngineConfig::default()
         };
+        let (board, player) = random_position_from_seed(3, 50);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let worker_cancel = Arc::clone(&cancel);
+        let worker = std::thread::spawn(move || {
+            let mut board = board;
+            let mut tt = FxHashMap::default();
+            board.find_best_move_with_cancel(player, 30, &mut tt, &config, &worker_cancel)
+        });
 
-        let occupied = self.black | self.white;
-        let mut stable = 0u64;
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let cancelled_at = std::time::Instant::now();
+        cancel.store(true, AtomicOrdering::Relaxed);
+        let (best_move, _, stats) = worker.join().unwrap();
+
+        assert!(cancelled_at.elapsed() < std::time::Duration::from_secs(5));
+        assert!(stats.depth_reached < 30);
+        // 読み終えた深さの最善手は返す
+        assert!(board
+            .get_legal_move_positions(player)
+            .contains(&best_move.unwrap()));
+    }
 
-        // 角の確定石
-        const CORNERS: [usize; 4] = [0, 7, 56, 63];
-        for &corner in &CORNERS {
-            let bit = 1u64 << corner;
-            if (my_board & bit) != 0 {
-                stable |= bit;
-                stable = self.expand_stability(stable, my_board, occupied);
-            }
+    #[test]
+    fn disc_weight_ramps_up_as_the_board_fills() {
+        let ramp = EvalParams::default();
+        let flat = EvalParams {
+            disc_weight_ramp: false,
+            ..ramp
+        };
+        let [early, mid, end] = ramp.disc_diff_weight;
+
+        // 空きマスが減るほど重みは下がらず、盤が埋まった時点で終盤の値になる
+        let weights: Vec<i32> = (0..=60)
+            .rev()
+            .map(|e| ramp.disc_diff_weight_at(e))
+            .collect();
+        assert!(weights.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(ramp.disc_diff_weight_at(0), end);
+        assert_eq!(ramp.disc_diff_weight_at(64 - MID_GAME_THRESHOLD), mid);
+        assert!(ramp.disc_diff_weight_at(7) < end);
+        assert_eq!(flat.disc_diff_weight_at(7), end);
+
+        // 序盤・中盤の重みと評価値は変わらない
+        for empty_count in 64 - MID_GAME_THRESHOLD + 1..=60 {
+            assert_eq!(
+                ramp.disc_diff_weight_at(empty_count),
+                flat.disc_diff_weight_at(empty_count)
+            );
+        }
+        assert_eq!(ramp.disc_diff_weight_at(60), early);
+        let ramp_config = EngineConfig::default();
+        let flat_config = EngineConfig {
+            eval: flat,
+            ..ramp_config
+        };
+        for seed in 0..6 {
+            let (board, player) = random_position_from_seed(seed, 40);
+            assert_eq!(
+                board.evaluate_board_optimized(player, &ramp_config),
+                board.evaluate_board_optimized(player, &flat_config)
+            );
         }
+    }
 
-        stable
+    #[test]
+    fn abort_flag_set_before_search_stops_after_first_depth() {
+        let config = EngineConfig {
+            move_time_limit: None,
+            max_nodes: None,
+            ..EngineConfig::default()
+        };
+        let (board, player) = random_position_from_seed(5, 50);
+        let cancel = Arc::new(AtomicBool::new(true));
+
+        let start = std::time::Instant::now();
+        let mut searched = board;
+        let mut tt = FxHashMap::default();
+        let (best_move, score, stats) =
+            searched.find_best_move_with_cancel(player, 30, &mut tt, &config, &cancel);
+
+        // 最初の深さだけは読み終えて手を返す
+        // （中止は一定のノード数ごとに確かめるので、すぐ読み終わる次の深さまで進むことはある）
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+        assert!(stats.depth_reached <= 2, "{}", stats.depth_reached);
+        assert!(board.is_legal_move(best_move.unwrap(), player));
+        assert!(score.is_some());
     }
 
-    /// 確定石の拡張
-    fn expand_stability(&self, mut stable: u64, my_board: u64, occupied: u64) -> u64 {
-        let mut changed = true;
+    #[test]
+    fn solve_endgame_on_near_full_board() {
+        // 上半分が黒、下半分が白で h8 だけ空いている
+        // 黒は h8 に打って縦と斜めの6個を返し、39対25で終わる
+        let mut board = BitBoard {
+            black: 0x0000_0000_FFFF_FFFF,
+            white: 0x7FFF_FFFF_0000_0000,
+        };
+        assert_eq!(board.solve_endgame(Player::Black, 1), Some(14));
+        // 白は打てずにパスし、黒が打って終わる
+        assert_eq!(board.solve_endgame(Player::White, 1), Some(-14));
+        // 閾値より空きマスが多ければ解かない
+        assert_eq!(board.solve_endgame(Player::Black, 0), None);
+    }
 
-        while changed {
-            changed = false;
-            let _old_stable = stable;
+    #[test]
+    fn solve_wld_matches_sign_of_solve_endgame() {
+        for seed in 0..12 {
+            let (board, player) = random_position_from_seed(seed, 10);
+            let mut solved = board;
+            let diff = solved.solve_endgame(player, 10).unwrap();
+            assert_eq!(board.solve_wld(player), diff.cmp(&0), "seed {}", seed);
+        }
+    }
 
-            // 8方向をチェックして、両端が確定または盤面端の石を確定石とする
-            for pos in 0..64 {
-                let bit = 1u64 << pos;
-                if (my_board & bit) != 0 && (stable & bit) == 0 {
-                    let row = pos / 8;
-                    let col = pos % 8;
+    fn tt_entry(depth: u8) -> Entry {
+        Entry {
+            score: 0,
+            depth,
+            flag: NodeType::Exact,
+            best_move: None,
+        }
+    }
 
-                    let mut is_stable = true;
+    #[test]
+    fn enforce_tt_cap_shrinks_uniform_depth_table() {
+        let max_entries = 1000;
+        let mut tt = FxHashMap::default();
+        for i in 0..1500u64 {
+            tt.insert((i, 0, 0), tt_entry(5));
+        }
+        enforce_tt_cap(&mut tt, max_entries);
+        assert_eq!(tt.len(), max_entries * 3 / 4);
+    }
 
-                    // 水平、垂直、対角線方向をチェック
-                    let directions = [
-                        (0, 1),
-                        (0, -1), // 水平
-                        (1, 0),
-                        (-1, 0), // 垂直
-                        (1, 1),
-                        (-1, -1), // 対角線1
-                        (1, -1),
-                        (-1, 1), // 対角線2
-                    ];
+    #[test]
+    fn enforce_tt_cap_evicts_shallowest_entries() {
+        let max_entries = 100;
+        let mut tt = FxHashMap::default();
+        for i in 0..200u64 {
+            tt.insert((i, 0, 0), tt_entry((i % 10) as u8));
+        }
+        enforce_tt_cap(&mut tt, max_entries);
+        assert_eq!(tt.len(), max_entries * 3 / 4);
+        // 深さ0〜5の120件と深さ6の5件が消え、深さ6の残り15件と深さ7以上が残る
+        assert!(tt.values().all(|entry| entry.depth >= 6));
+        assert_eq!(tt.values().filter(|entry| entry.depth == 6).count(), 15);
+
+        // 上限以内なら何もしない
+        let before = tt.len();
+        enforce_tt_cap(&mut tt, max_entries);
+        assert_eq!(tt.len(), before);
+    }
 
-                    for chunk in directions.chunks(2) {
-                        let (dr1, dc1) = chunk[0];
-                        let (dr2, dc2) = chunk[1];
+    #[test]
+    fn tt_memory_estimate_scales_and_cap_holds_after_search() {
+        let mut tt = FxHashMap::default();
+        assert_eq!(tt_memory_estimate(&tt), 0);
+        for i in 0..10u64 {
+            tt.insert((i, 0, 0), tt_entry(1));
+        }
+        let ten = tt_memory_estimate(&tt);
+        assert!(ten > 0);
+        for i in 10..30u64 {
+            tt.insert((i, 0, 0), tt_entry(1));
+        }
+        assert_eq!(tt_memory_estimate(&tt), ten * 3);
+        assert_eq!(tt_entries_memory(30), ten * 3);
+
+        // 探索で上限を超えるほどエントリが増えても、探索後は上限以内に収まる
+        let config = EngineConfig {
+            tt_max_entries: 200,
+            max_nodes: Some(u64::MAX),
+            ..EngineConfig::default()
+        };
+        let (board, player) = random_position_from_seed(1, 44);
+        let mut tt = FxHashMap::default();
+        for i in 0..500u64 {
+            tt.insert((i, 0, 0), tt_entry(1));
+        }
+        let mut searched = board;
+        let (_, _, stats) = searched.find_best_move_with_stats(player, 6, &mut tt, &config);
+        assert!(stats.nodes > config.tt_max_entries as u64);
+        assert!(tt.len() <= config.tt_max_entries);
+        assert_eq!(stats.tt_entries, tt.len());
+    }
 
-                        let stable1 = self.check_direction_stability(
-                            row as i32, col as i32, dr1, dc1, my_board, stable, occupied,
-                        );
-                        let stable2 = self.check_direction_stability(
-                            row as i32, col as i32, dr2, dc2, my_board, stable, occupied,
-                        );
+    #[test]
+    fn analyze_multipv_scores_every_legal_move_by_best_reply() {
+        for seed in 0..6 {
+            let (board, player) = random_position_from_seed(seed, 40);
+            let evals = board.analyze_multipv(player, 3);
+
+            let mut positions: Vec<usize> = evals.iter().map(|&(pos, _)| pos).collect();
+            positions.sort_unstable();
+            assert_eq!(positions, board.get_legal_move_positions(player));
+            assert!(evals.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+            for &(pos, score) in &evals {
+                let child = board.with_move(pos, player).unwrap();
+                let replies = child.analyze_multipv(player.opponent(), 2);
+                if let Some(&(_, reply)) = replies.first() {
+                    assert_eq!(score, -reply, "seed {} move {}", seed, pos);
+                }
+            }
+        }
+    }
 
-                        if !stable1 && !stable2 {
-                            is_stable = false;
-                            break;
-                        }
-                    }
+    #[test]
+    fn chunked_search_matches_one_shot_search() {
+        // ノード数の上限を付けると、一括の探索は時間で打ち切らず並列にもしない
+        for use_mtdf in [false, true] {
+            let config = EngineConfig {
+                max_nodes: Some(u64::MAX),
+                use_mtdf,
+                ..EngineConfig::default()
+            };
+            for seed in 0..4 {
+                let (board, player) = random_position_from_seed(seed, 44);
+                let mut one_shot = board;
+                let expected = one_shot.find_best_move_with_config(
+                    player,
+                    6,
+                    &mut FxHashMap::default(),
+                    &config,
+                );
 
-                    if is_stable {
-                        stable |= bit;
-                        changed = true;
+                let mut search = ChunkedSearch::new(board, player, 6, config, FxHashMap::default());
+                let mut steps = 1;
+                let result = loop {
+                    if let Some(result) = search.step(200) {
+                        break result;
                     }
-                }
+                    steps += 1;
+                };
+                assert!(steps > 1);
+                assert_eq!(result, expected, "seed {} mtdf {}", seed, use_mtdf);
+                assert_eq!(search.stats().depth_reached, 6);
             }
         }
+    }
 
-        stable
+    #[test]
+    fn tiny_time_limit_completes_shallower_depth() {
+        use std::time::{Duration, Instant};
+
+        let (board, player) = random_position_from_seed(1, 40);
+        let depth_reached = |limit| {
+            let config = EngineConfig {
+                move_time_limit: Some(limit),
+                ..EngineConfig::default()
+            };
+            let mut board = board;
+            let (_, _, stats) =
+                board.find_best_move_with_stats(player, 9, &mut FxHashMap::default(), &config);
+            stats.depth_reached
+        };
+        let tiny = depth_reached(Duration::from_micros(1));
+        let large = depth_reached(Duration::from_secs(60));
+        assert!(tiny >= 1);
+        assert_eq!(large, 9);
+        assert!(tiny < large);
+
+        // 時間制限を渡した探索も、制限を過ぎたら読み終えた深さの手を返す
+        let mut searched = board;
+        let start = Instant::now();
+        let (best_move, _) = searched.find_best_move_with_tt(
+            player,
+            20,
+            &mut FxHashMap::default(),
+            Duration::from_millis(10),
+        );
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(best_move.is_some_and(|pos| board.is_legal_move(pos, player)));
     }
 
-    /// 方向の安定性をチェック
-    fn check_direction_stability(
-        &self,
-        row: i32,
-        col: i32,
-        dr: i32,
-        dc: i32,
-        my_board: u64,
-        stable: u64,
-        _occupied: u64,
-    ) -> bool {
-        let mut r = row + dr;
-        let mut c = col + dc;
-
-        while r >= 0 && r < 8 && c >= 0 && c < 8 {
-            let pos = (r * 8 + c) as usize;
-            let bit = 1u64 << pos;
+    #[test]
+    fn mobility_heavy_params_change_the_move() {
+        let mobility_heavy = EngineConfig {
+            eval: EvalParams {
+                mobility_weight: [200, 200, 200],
+                ..EvalParams::default()
+            },
+            ..EngineConfig::default()
+        };
+        let (board, player) = random_position_from_seed(2, 50);
+        let default_move = board
+            .analyze_with_config(player, 3, &EngineConfig::default())
+            .best_move
+            .unwrap();
+        let mobility_move = board
+            .analyze_with_config(player, 3, &mobility_heavy)
+            .best_move
+            .unwrap();
+        assert_ne!(default_move, mobility_move);
+
+        // レベル1の簡易評価も位置価値の重みに従う
+        let inverted = EngineConfig {
+            eval: EvalParams {
+                position_values: POSITION_VALUES.map(|value| -value),
+                corner_weight: 0,
+                ..EvalParams::default()
+            },
+            ..EngineConfig::default()
+        };
+        assert_ne!(
+            board
+                .analyze_with_config(player, 1, &EngineConfig::default())
+                .best_move,
+            board.analyze_with_config(player, 1, &inverted).best_move
+        );
+    }
 
-            if (stable & bit) != 0 {
-                return true; // 確定石に到達
-            }
+    #[test]
+    fn parallel_search_matches_sequential_search() {
+        // 前向き枝刈りを切ると値が探索の窓によらないので、並列でも逐次でも同じ値になる
+        let config = EngineConfig {
+            forward_pruning: false,
+            ..EngineConfig::default()
+        };
+        for seed in 0..3 {
+            let (board, player) = random_position_from_seed(seed, 40);
+            let search = |parallel: bool| {
+                let mut board = board;
+                let mut tt = FxHashMap::default();
+                let mut ctx = SearchContext::new(&config);
+                if parallel {
+                    board.parallel_search(player, PARALLEL_MIN_DEPTH, &mut tt, &mut ctx)
+                } else {
+                    board.sequential_search(player, PARALLEL_MIN_DEPTH, &mut tt, &mut ctx, 0)
+                }
+            };
+            let (parallel_move, parallel_score) = search(true).unwrap();
+            let (_, sequential_score) = search(false).unwrap();
+            assert_eq!(parallel_score, sequential_score, "seed {seed}");
+            assert!(board.is_legal_move(parallel_move, player));
+        }
+    }
+
+    #[test]
+    fn mtdf_is_searched_sequentially_at_parallel_depths() {
+        let aspiration = EngineConfig {
+            forward_pruning: false,
+            ..EngineConfig::default()
+        };
+        let mtdf = EngineConfig {
+            use_mtdf: true,
+            ..aspiration
+        };
+        assert!(!uses_parallel_search(PARALLEL_MIN_DEPTH, &mtdf));
+        assert!(!uses_parallel_search(PARALLEL_MIN_DEPTH + 4, &mtdf));
+        assert_eq!(
+            uses_parallel_search(PARALLEL_MIN_DEPTH, &aspiration),
+            rayon::current_num_threads() > 1
+        );
 
-            if (my_board & bit) == 0 {
-                return false; // 自分の石でない
+        // 並列探索の深さでも、前向き枝刈りを切れば MTD(f) と Aspiration Window は同じ値になる
+        for seed in 0..2 {
+            let (board, player) = random_position_from_seed(seed, 40);
+            let search = |config: &EngineConfig| {
+                let mut board = board;
+                board.find_best_move_with_config(
+                    player,
+                    PARALLEL_MIN_DEPTH,
+                    &mut FxHashMap::default(),
+                    config,
+                )
+            };
+            let (mtdf_move, mtdf_score) = search(&mtdf);
+            let (_, aspiration_score) = search(&aspiration);
+            assert_eq!(mtdf_score, aspiration_score, "seed {seed}");
+            assert!(mtdf_move.is_some_and(|pos| board.is_legal_move(pos, player)));
+        }
+    }
+
+    #[test]
+    fn midgame_and_endgame_update_different_history_slots() {
+        assert_eq!(
+            [GamePhase::Early, GamePhase::Mid, GamePhase::End].map(|phase| phase.index()),
+            [0, 1, 2]
+        );
+        assert_eq!(GamePhase::from_empty_count(60), GamePhase::Early);
+        assert_eq!(GamePhase::from_empty_count(20), GamePhase::Mid);
+        assert_eq!(GamePhase::from_empty_count(10), GamePhase::End);
+
+        // 完全読みに切り替えず、探索した局面の段階の欄だけが更新される
+        let config = EngineConfig {
+            endgame_solve_empties: 0,
+            ..EngineConfig::default()
+        };
+        let touched_phases = |empties: u32| {
+            let (mut board, player) = random_position_from_seed(0, empties);
+            let mut tt = FxHashMap::default();
+            let mut ctx = SearchContext::new(&config);
+            board.sequential_search(player, 3, &mut tt, &mut ctx, 0);
+            ctx.history_table
+                .scores
+                .map(|slot| slot.iter().flatten().any(|&score| score != 0))
+        };
+        assert_eq!(touched_phases(22), [false, true, false]);
+        assert_eq!(touched_phases(12), [false, false, true]);
+    }
+
+    #[test]
+    fn principal_variation_is_playable_from_the_root() {
+        for seed in 0..4 {
+            let (board, player) = random_position_from_seed(seed, 40);
+            let mut searched = board;
+            let mut tt = FxHashMap::default();
+            let (line, score) = searched.find_best_line_with_tt(player, 5, &mut tt);
+            assert!(score.is_some());
+            assert!(!line.is_empty() && line.len() <= 5, "seed {}", seed);
+
+            // パスは含まれないので、打てない側を飛ばしながら順に打てる
+            let mut replay = board;
+            let mut side = player;
+            for &pos in &line {
+                if replay.is_pass_required(side) {
+                    side = side.opponent();
+                }
+                assert!(
+                    replay.make_move(pos, side),
+                    "seed {} の {}",
+                    seed,
+                    notation(pos)
+                );
+                side = side.opponent();
             }
+        }
+    }
 
-            r += dr;
-            c += dc;
+    #[test]
+    fn search_reuses_flips_from_move_ordering() {
+        use crate::board::COMPUTE_FLIPS_CALLS;
+
+        let config = EngineConfig::default();
+        let (board, player) = random_position_from_seed(3, 48);
+
+        // 並び替えは合法手ごとに1回だけひっくり返しを計算し、その値は着手に使うものと同じ
+        let ctx = SearchContext::new(&config);
+        let legal_moves = board.get_legal_moves(player);
+        COMPUTE_FLIPS_CALLS.with(|calls| calls.set(0));
+        let moves = board.order_moves(legal_moves, player, 2, 0, &ctx);
+        assert_eq!(
+            COMPUTE_FLIPS_CALLS.with(|calls| calls.get()),
+            legal_moves.count_ones() as u64
+        );
+        for mv in &moves {
+            assert_eq!(mv.flips, board.compute_flips(mv.position as usize, player));
         }
 
-        true // 盤面端に到達
+        // 着手時には計算し直さないので、子が末端の節点では並び替えの分しか計算しない
+        let mut searched = board;
+        let mut tt = FxHashMap::default();
+        let mut ctx = SearchContext::new(&config);
+        COMPUTE_FLIPS_CALLS.with(|calls| calls.set(0));
+        searched.minimax_with_tt_internal(
+            player,
+            1,
+            i32::MIN + 1,
+            i32::MAX - 1,
+            0,
+            false,
+            &mut tt,
+            &mut ctx,
+        );
+        assert_eq!(
+            COMPUTE_FLIPS_CALLS.with(|calls| calls.get()),
+            moves.len() as u64
+        );
     }
 
-    /// パリティの評価
-    fn evaluate_parity(&self, player: Player) -> i32 {
-        let empty_count = 64 - (self.black | self.white).count_ones();
+    #[test]
+    fn exact_and_zobrist_keying_agree() {
+        // 時間で打ち切られないよう、ノード数の上限（実質無制限）で読む
+        let config = EngineConfig {
+            max_nodes: Some(u64::MAX),
+            ..EngineConfig::default()
+        };
+        for seed in 0..6 {
+            let (board, player) = random_position_from_seed(seed, 40);
+            let search = |tt_keying: TtKeying| {
+                let mut board = board;
+                let mut tt = FxHashMap::default();
+                let config = EngineConfig {
+                    tt_keying,
+                    ..config
+                };
+                board.find_best_move_with_config(player, 6, &mut tt, &config)
+            };
+            assert_eq!(
+                search(TtKeying::Exact),
+                search(TtKeying::Zobrist),
+                "seed {seed}"
+            );
+        }
+    }
 
-        if empty_count % 2 == 0 {
-            // 偶数なら後手有利
-            if player == Player::White {
-                10
-            } else {
-                -10
-            }
-        } else {
-            // 奇数なら先手有利
-            if player == Player::Black {
-                10
-            } else {
-                -10
+    #[test]
+    fn next_turn_search_finds_cached_entries() {
+        let config = EngineConfig {
+            max_nodes: Some(u64::MAX),
+            tt_keying: TtKeying::Zobrist,
+            ..EngineConfig::default()
+        };
+        let (board, player) = random_position_from_seed(4, 44);
+        let mut tt = FxHashMap::default();
+        let mut searched = board;
+        let (best_move, _) = searched.find_best_move_with_config(player, 6, &mut tt, &config);
+        let line = board.principal_variation(best_move.unwrap(), player, &tt, 2, config.tt_keying);
+
+        // 読み筋どおりに2手進めた局面は、前の探索で置換表に入っている
+        let mut next = board;
+        let mut side = player;
+        for pos in line.into_iter().flatten() {
+            if next.is_pass_required(side) {
+                side = side.opponent();
             }
+            next.make_move(pos, side);
+            side = side.opponent();
         }
+        assert!(tt.contains_key(&tt_key(&next, side, config.tt_keying)));
+
+        // 次の手番の探索で読む局面の多くは、前の探索の置換表に入っている
+        let mut visited = FxHashMap::default();
+        let mut fresh = next;
+        fresh.find_best_move_with_config(side, 4, &mut visited, &config);
+        let cached = visited.keys().filter(|key| tt.contains_key(key)).count();
+        assert!(cached * 2 > visited.len(), "{} / {}", cached, visited.len());
+
+        // 前の置換表を使い続けても、打てる手を返す
+        let mut reused = next;
+        let (best_move, _) = reused.find_best_move_with_config(side, 4, &mut tt, &config);
+        assert!(next.is_legal_move(best_move.unwrap(), side));
     }
 
-    /// 高速な手の評価（レベル1用）
-    fn evaluate_move_fast(&self, pos: usize, player: Player) -> i32 {
-        let row = pos / 8;
-        let col = pos % 8;
-        let mut score = POSITION_SCORE[row][col];
+    #[test]
+    fn node_limit_cutoff_returns_last_completed_depth() {
+        let unlimited = EngineConfig {
+            max_nodes: Some(u64::MAX),
+            ..EngineConfig::default()
+        };
+        let search = |board: BitBoard, player, depth, config: &EngineConfig| {
+            let mut board = board;
+            let mut tt = FxHashMap::default();
+            board.find_best_move_with_stats(player, depth, &mut tt, config)
+        };
 
-        // 角のボーナス
-        if pos == 0 || pos == 7 || pos == 56 || pos == 63 {
-            score += CORNER_WEIGHT;
+        // 深さ5と6で最善手が変わる局面で、深さ6の途中で打ち切る
+        let (board, player) = (0..)
+            .map(|seed| random_position_from_seed(seed, 40))
+            .find(|&(board, player)| {
+                search(board, player, 5, &unlimited).0 != search(board, player, 6, &unlimited).0
+            })
+            .unwrap();
+        let (shallow_move, shallow_eval, shallow_stats) = search(board, player, 5, &unlimited);
+        let (_, _, deep_stats) = search(board, player, 6, &unlimited);
+        assert!(deep_stats.nodes - shallow_stats.nodes > 4 * STOP_CHECK_INTERVAL);
+
+        let cutoff = EngineConfig {
+            max_nodes: Some((shallow_stats.nodes + deep_stats.nodes) / 2),
+            ..EngineConfig::default()
+        };
+        let (best_move, eval, stats) = search(board, player, 6, &cutoff);
+        assert_eq!(stats.depth_reached, 5);
+        assert_eq!((best_move, eval), (shallow_move, shallow_eval));
+    }
+
+    #[test]
+    fn endgame_solve_threshold_gates_exact_solving() {
+        let (board, player) = random_position_from_seed(4, 10);
+        let search = |endgame_solve_empties| {
+            let mut board = board;
+            let mut tt = FxHashMap::default();
+            let config = EngineConfig {
+                endgame_solve_empties,
+                max_nodes: Some(u64::MAX),
+                ..EngineConfig::default()
+            };
+            board.find_best_move_with_stats(player, 2, &mut tt, &config)
+        };
+
+        // 閾値が空きマス数以上なら、レベルによらず最後まで読み切る
+        let (_, eval, stats) = search(12);
+        assert_eq!(stats.depth_reached, 10);
+        let mut solved = board;
+        let diff = solved.solve_endgame(player, 10).unwrap();
+        assert_eq!(eval, Some(final_score(diff)));
+
+        // 0 なら完全読みを使わず、閾値より空きマスが多ければレベルの深さまでしか読まない
+        for endgame_solve_empties in [0, 8] {
+            let (best_move, _, stats) = search(endgame_solve_empties);
+            assert_eq!(stats.depth_reached, 2);
+            assert!(best_move.is_some());
         }
+    }
 
-        // ひっくり返す石の数
-        let flips = self.compute_flips(pos, player);
-        score += flips.count_ones() as i32 * 10;
+    #[test]
+    fn evaluate_move_prefers_corner_over_x_square() {
+        const CORNERS: [usize; 4] = [0, 7, 56, 63];
+        const X_SQUARES: [usize; 4] = [9, 14, 49, 54];
+
+        // 角とX打ちの両方が打てる局面を探す
+        let (board, player, corner, x_square) = (0..)
+            .find_map(|seed| {
+                let (board, player) = random_position_from_seed(seed, 30);
+                let corner = CORNERS
+                    .into_iter()
+                    .find(|&pos| board.is_legal_move(pos, player))?;
+                let x_square = X_SQUARES
+                    .into_iter()
+                    .find(|&pos| board.is_legal_move(pos, player))?;
+                Some((board, player, corner, x_square))
+            })
+            .unwrap();
+
+        let params = EvalParams::default();
+        assert!(
+            board.evaluate_move(corner, player, &params)
+                > board.evaluate_move(x_square, player, &params)
+        );
 
-        score
+        // 位置価値の重みを入れ替えると順位も入れ替わる
+        let inverted = EvalParams {
+            position_values: POSITION_VALUES.map(|value| -value),
+            corner_weight: 0,
+            ..EvalParams::default()
+        };
+        assert!(
+            board.evaluate_move(corner, player, &inverted)
+                < board.evaluate_move(x_square, player, &inverted)
+        );
+
+        // 打てない手は最低の値
+        let occupied = board.disc_positions(player).next().unwrap();
+        assert_eq!(board.evaluate_move(occupied, player, &params), i32::MIN);
     }
 
-    /// 通常の手の評価（レガシー）
-    pub fn evaluate_move(&self, _pos: usize, _player: Player) -> i32 {
-        0 // 現在は使用していない
+    #[test]
+    fn search_time_estimate_grows_with_depth_and_empties() {
+        for empty_count in [10, 20, 30, 40] {
+            for depth in 1..10 {
+                assert!(
+                    estimate_search_time(depth + 1, empty_count)
+                        > estimate_search_time(depth, empty_count)
+                );
+                assert!(
+                    estimate_search_time(depth, empty_count + 10)
+                        >= estimate_search_time(depth, empty_count)
+                );
+            }
+        }
+        // 残りの空きマス数より深くは読めない
+        assert_eq!(estimate_search_time(20, 8), estimate_search_time(8, 8));
+    }
+
+    #[test]
+    fn predicted_reply_is_legal_after_the_move() {
+        for seed in 0..4 {
+            let (board, player) = random_position_from_seed(seed, 40);
+            let opponent = player.opponent();
+            for pos in board.legal_moves_iter(player) {
+                let after = board.with_move(pos, player).unwrap();
+                match board.predict_opponent_reply(pos, opponent, 3) {
+                    Some(reply) => assert!(after.is_legal_move(reply, opponent)),
+                    None => assert_eq!(after.get_legal_moves(opponent), 0),
+                }
+            }
+        }
+
+        // 打てない手には予測を返さない
+        let (board, player) = random_position_from_seed(0, 40);
+        let occupied = board.disc_positions(player).next().unwrap();
+        assert_eq!(
+            board.predict_opponent_reply(occupied, player.opponent(), 3),
+            None
+        );
+    }
+
+    #[test]
+    fn personality_presets_differ_and_play_differently() {
+        for (i, personality) in Personality::ALL.iter().enumerate() {
+            let config = personality.engine_config();
+            let weights = config
+                .eval
+                .mobility_weight
+                .iter()
+                .chain(&config.eval.disc_diff_weight);
+            assert!(weights.copied().all(|weight| weight >= 0));
+            assert!(config.eval.corner_weight > 0 && config.randomness >= 0);
+            for other in &Personality::ALL[i + 1..] {
+                assert_ne!(
+                    config,
+                    other.engine_config(),
+                    "{:?} {:?}",
+                    personality,
+                    other
+                );
+            }
+        }
+        assert!(Personality::Wild.engine_config().randomness > 0);
+
+        // 石数重視と角・確定石重視で最善手が分かれる局面がある
+        let choose = |personality: Personality, board: &BitBoard, player| {
+            let config = EngineConfig {
+                max_nodes: Some(u64::MAX),
+                ..personality.engine_config()
+            };
+            board
+                .analyze_with_config(player, PERSONALITY_LEVEL, &config)
+                .best_move
+        };
+        assert!((0..10).any(|seed| {
+            let (board, player) = random_position_from_seed(seed, 30);
+            choose(Personality::Aggressive, &board, player)
+                != choose(Personality::Positional, &board, player)
+        }));
+    }
+
+    #[test]
+    fn stabilizing_edge_move_orders_first() {
+        // 1行目: c1 と f1 は同じ位置価値で、どちらも1個返す
+        // f1 は h1 の角から e1 までを黒の確定石にするが、c1 は確定石を増やさない
+        let board = BitBoard {
+            black: 0xFFFF_FF00_0000_0000 | 0x90,
+            white: 0x0000_00FF_FF00_0000 | 0x48,
+        };
+        let (c1, f1) = (2u8, 5u8);
+        let player = Player::Black;
+        assert_eq!(board.compute_flips(c1 as usize, player).count_ones(), 1);
+        assert_eq!(board.compute_flips(f1 as usize, player).count_ones(), 1);
+
+        let ctx = SearchContext::new(&EngineConfig::default());
+        let order = |depth| -> Vec<u8> {
+            board
+                .order_moves(board.get_legal_moves(player), player, depth, 0, &ctx)
+                .iter()
+                .map(|mv| mv.position)
+                .filter(|&pos| pos == c1 || pos == f1)
+                .collect()
+        };
+        // 浅い節点では確定石を数えないので、同点はマスの番号順
+        assert_eq!(order(STABILITY_ORDER_MIN_DEPTH - 1), [c1, f1]);
+        assert_eq!(order(STABILITY_ORDER_MIN_DEPTH), [f1, c1]);
+    }
+
+    #[test]
+    fn move_ordering_is_deterministic() {
+        let ctx = SearchContext::new(&EngineConfig::default());
+        for seed in 0..8 {
+            let (board, player) = random_position_from_seed(seed, 30);
+            let order = || board.order_moves(board.get_legal_moves(player), player, 4, 0, &ctx);
+            let moves = order();
+            assert_eq!(moves, order());
+            // 同点の手はマスの番号が小さい順
+            for pair in moves.windows(2) {
+                assert!(
+                    pair[0].score > pair[1].score
+                        || (pair[0].score == pair[1].score && pair[0].position < pair[1].position)
+                );
+            }
+        }
     }
 }
@@ -4,7 +4,395 @@ use std::fmt;
 const DEFAULT_BLACK: u64 = 0x0000000810000000; // 初期配置の黒石
 const DEFAULT_WHITE: u64 = 0x0000001008000000; // 初期配置の白石
 
-#[derive(Copy, Clone, Debug)]
+// テスト用に compute_flips の呼び出し回数を数える（スレッドごと）
+#[cfg(test)]
+thread_local! {
+    pub(crate) static COMPUTE_FLIPS_CALLS: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// ゲーム終了の理由
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameOverReason {
+    BoardFull,          // 盤面が全て埋まった
+    NoLegalMovesEither, // 空きマスはあるが両者とも打てない
+}
+
+/// 盤面が不正な理由
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BoardError {
+    Overlapping(u64),          // 黒白両方の石があるマス（ビットマスク）
+    TooFewDiscs(u32),          // 石の総数が初期配置の4個未満
+    OutOfBounds(usize, usize), // 盤面の外の座標（行, 列）
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::Overlapping(squares) => {
+                write!(
+                    f,
+                    "黒と白が重なっているマスがあります（{}マス）",
+                    squares.count_ones()
+                )
+            }
+            BoardError::TooFewDiscs(count) => {
+                write!(f, "石の数が少なすぎます（{}個）", count)
+            }
+            BoardError::OutOfBounds(row, col) => {
+                write!(f, "({},{}) は盤面の外です", row, col)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// 盤面の文字列（BitBoard::from_position_string）を読めなかった理由
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidLength(usize), // 64マスと手番の65文字でない（空白を除いた実際の文字数）
+    InvalidChar(char),    // 石として読めない文字がある
+    InvalidSide(char),    // 手番が 'X' でも 'O' でもない
+    InvalidBoard(BoardError), // 読めたが盤面として正しくない
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidLength(length) => {
+                write!(
+                    f,
+                    "盤面の文字列は64マスと手番の65文字必要です（{}文字）",
+                    length
+                )
+            }
+            ParseError::InvalidChar(c) => {
+                write!(f, "盤面の文字列に読めない文字 '{}' があります", c)
+            }
+            ParseError::InvalidSide(c) => {
+                write!(f, "手番 '{}' は X(黒) か O(白) で指定してください", c)
+            }
+            ParseError::InvalidBoard(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<BoardError> for ParseError {
+    fn from(e: BoardError) -> Self {
+        ParseError::InvalidBoard(e)
+    }
+}
+
+/// 棋譜の適用に失敗した理由（index は何手目か、0始まり）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApplyError {
+    InvalidNotation { index: usize, notation: String }, // 座標として読めない
+    IllegalMove { index: usize, notation: String },     // その手番では打てない
+    GameOver { index: usize },                          // 両者とも打てないのに手が残っている
+}
+
+impl fmt::Display for ApplyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ApplyError::InvalidNotation { index, notation } => {
+                write!(f, "{}手目 '{}' は座標として読めません", index + 1, notation)
+            }
+            ApplyError::IllegalMove { index, notation } => {
+                write!(f, "{}手目 '{}' は打てない手です", index + 1, notation)
+            }
+            ApplyError::GameOver { index } => {
+                write!(f, "{}手目の時点で対局は終了しています", index + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApplyError {}
+
+impl ApplyError {
+    /// 失敗した手の位置（0始まり）。それより前の手はすべて打てている
+    pub fn index(&self) -> usize {
+        match self {
+            ApplyError::InvalidNotation { index, .. }
+            | ApplyError::IllegalMove { index, .. }
+            | ApplyError::GameOver { index } => *index,
+        }
+    }
+}
+
+/// 盤面の位置（0〜63）を (行, 列) に変換する
+#[inline(always)]
+pub fn pos_to_rc(pos: usize) -> (usize, usize) {
+    debug_assert!(pos < 64, "ビット位置が範囲外です");
+    (pos / 8, pos % 8)
+}
+
+/// (行, 列) を盤面の位置（0〜63）に変換する（pos_to_rc の逆）
+#[inline(always)]
+pub fn rc_to_pos(row: usize, col: usize) -> usize {
+    debug_assert!(row < 8 && col < 8, "行または列が範囲外です");
+    row * 8 + col
+}
+
+/// ビットが立っている位置を小さい順に返す
+fn bit_positions(mut remaining: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if remaining == 0 {
+            return None;
+        }
+        let pos = remaining.trailing_zeros() as usize;
+        remaining &= remaining - 1;
+        Some(pos)
+    })
+}
+
+/// 斜めの線のマスク（anti が false なら左上-右下、true なら右上-左下の15本）
+const fn diagonal_lines(anti: bool) -> [u64; 15] {
+    let mut lines = [0u64; 15];
+    let mut pos = 0;
+    while pos < 64 {
+        let (row, col) = (pos / 8, pos % 8);
+        let index = if anti { row + col } else { row + 7 - col };
+        lines[index] |= 1u64 << pos;
+        pos += 1;
+    }
+    lines
+}
+
+const DIAGONAL_LINES: [u64; 15] = diagonal_lines(false);
+const ANTI_DIAGONAL_LINES: [u64; 15] = diagonal_lines(true);
+
+/// 石で埋まっている線上のマス（横・縦・左上-右下・右上-左下の順）
+fn full_lines(occupied: u64) -> [u64; 4] {
+    // 横: 各行の先頭のビットに、その行の8マスすべての AND を集める
+    let mut row = occupied;
+    row &= row >> 1;
+    row &= row >> 2;
+    row &= row >> 4;
+    let horizontal = (row & 0x0101_0101_0101_0101) * 0xff;
+
+    // 縦: 1行目に、各列の8マスすべての AND を集める
+    let mut col = occupied;
+    col &= col >> 8;
+    col &= col >> 16;
+    col &= col >> 32;
+    let vertical = (col & 0xff) * 0x0101_0101_0101_0101;
+
+    let full = |lines: &[u64; 15]| {
+        lines
+            .iter()
+            .filter(|&&line| occupied & line == line)
+            .fold(0, |acc, &line| acc | line)
+    };
+
+    [
+        horizontal,
+        vertical,
+        full(&DIAGONAL_LINES),
+        full(&ANTI_DIAGONAL_LINES),
+    ]
+}
+
+/// "f5" のような座標表記（列a-h, 行1-8）を盤面の位置に変換する
+pub fn parse_notation(notation: &str) -> Option<usize> {
+    let mut chars = notation.trim().chars();
+    let col = chars.next()?.to_ascii_lowercase();
+    let row = chars.next()?;
+    if chars.next().is_some() || !('a'..='h').contains(&col) || !('1'..='8').contains(&row) {
+        return None;
+    }
+
+    Some(rc_to_pos(
+        row as usize - '1' as usize,
+        col as usize - 'a' as usize,
+    ))
+}
+
+/// 棋譜でパスを表す表記（連結した形式でも2文字なので区切りなしで書ける）
+pub const PASS_NOTATION: &str = "--";
+
+/// 棋譜の1手がパスを表すかどうか（"--" のほか "pass" も受け付ける）
+pub fn is_pass_notation(notation: &str) -> bool {
+    let notation = notation.trim();
+    notation == PASS_NOTATION || notation.eq_ignore_ascii_case("pass")
+}
+
+/// 盤面の位置を "f5" のような座標表記（列a-h, 行1-8）に変換する（parse_notation の逆）
+pub fn notation(pos: usize) -> String {
+    let (row, col) = pos_to_rc(pos);
+    format!(
+        "{}{}",
+        (b'a' + col as u8) as char,
+        (b'1' + row as u8) as char
+    )
+}
+
+/// 盤面のマス（0〜63、pos_to_rc と同じ並び）
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Square(u8);
+
+impl Square {
+    /// 盤面の位置（0〜63）からマスを作る（範囲外なら None）
+    pub fn new(pos: usize) -> Option<Square> {
+        (pos < 64).then_some(Square(pos as u8))
+    }
+
+    /// "f5" のような座標表記からマスを作る
+    pub fn from_notation(text: &str) -> Option<Square> {
+        parse_notation(text).map(|pos| Square(pos as u8))
+    }
+
+    /// "f5" のような座標表記
+    pub fn to_notation(self) -> String {
+        notation(self.index())
+    }
+
+    /// 盤面の位置（0〜63）
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<(usize, usize)> for Square {
+    /// (行, 列) からマスを作る
+    fn from((row, col): (usize, usize)) -> Self {
+        Square(rc_to_pos(row, col) as u8)
+    }
+}
+
+impl From<Square> for (usize, usize) {
+    fn from(square: Square) -> Self {
+        pos_to_rc(square.index())
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_notation())
+    }
+}
+
+/// 棋譜の文字列を一手ずつの座標表記に分ける
+/// 空白・カンマ区切り（"f5 d6 c3"）と連結した形式（"f5d6c3"）のどちらも受け付ける
+/// パスは "--"（連結した形式の中でも可）か、区切られた "pass" で書く
+pub fn split_transcript(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .flat_map(|token| {
+            // 連結した形式は2文字ずつに分ける（読めない文字があればそのまま返してエラーにする）
+            let chunks: Vec<&str> = if token.is_ascii() && !is_pass_notation(token) {
+                (0..token.len())
+                    .step_by(2)
+                    .map(|i| &token[i..(i + 2).min(token.len())])
+                    .collect()
+            } else {
+                vec![token]
+            };
+            chunks
+        })
+        .collect()
+}
+
+// 固定の種から擬似乱数を作る（Zobrist の乱数表用、splitmix64）
+const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// Zobrist ハッシュの乱数表（[色][マス]、コンパイル時に作るので毎回同じ値になる）
+const ZOBRIST_DISCS: [[u64; 64]; 2] = {
+    let mut table = [[0u64; 64]; 2];
+    let mut i = 0;
+    while i < 128 {
+        table[i / 64][i % 64] = splitmix64(i as u64);
+        i += 1;
+    }
+    table
+};
+
+/// 白番のときに Zobrist ハッシュに混ぜる値（手番が変わるたびに XOR する）
+pub const ZOBRIST_WHITE_TO_MOVE: u64 = splitmix64(128);
+
+/// pos にある player の石の Zobrist の値
+/// 石を置く・取り除くときにハッシュに XOR すれば、全体を計算し直さずに更新できる
+/// （裏返すときは元の色と新しい色の両方を XOR する）
+#[inline(always)]
+pub fn zobrist_toggle(pos: usize, player: Player) -> u64 {
+    debug_assert!(pos < 64, "ビット位置が範囲外です");
+    match player {
+        Player::Black => ZOBRIST_DISCS[0][pos],
+        Player::White => ZOBRIST_DISCS[1][pos],
+    }
+}
+
+/// 上下を反転する（行 r → 7 - r）
+#[inline]
+fn flip_vertical(bits: u64) -> u64 {
+    bits.swap_bytes()
+}
+
+/// 左右を反転する（列 c → 7 - c）
+#[inline]
+fn mirror_horizontal(bits: u64) -> u64 {
+    const K1: u64 = 0x5555555555555555;
+    const K2: u64 = 0x3333333333333333;
+    const K4: u64 = 0x0f0f0f0f0f0f0f0f;
+    let bits = ((bits >> 1) & K1) | ((bits & K1) << 1);
+    let bits = ((bits >> 2) & K2) | ((bits & K2) << 2);
+    ((bits >> 4) & K4) | ((bits & K4) << 4)
+}
+
+/// 左上と右下を結ぶ対角線で反転する（(r, c) → (c, r)）
+#[inline]
+fn transpose(mut bits: u64) -> u64 {
+    const K1: u64 = 0x5500550055005500;
+    const K2: u64 = 0x3333000033330000;
+    const K4: u64 = 0x0f0f0f0f00000000;
+    let t = K4 & (bits ^ (bits << 28));
+    bits ^= t ^ (t >> 28);
+    let t = K2 & (bits ^ (bits << 14));
+    bits ^= t ^ (t >> 14);
+    let t = K1 & (bits ^ (bits << 7));
+    bits ^= t ^ (t >> 7);
+    bits
+}
+
+/// 手を戻すための情報（make_move_undo・pass が返す）
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MoveUndo {
+    player: Player,
+    placed: u64, // 置いた石（パスなら0）
+    flips: u64,  // ひっくり返した石（パスなら0）
+}
+
+impl MoveUndo {
+    /// パスの記録かどうか
+    pub fn is_pass(&self) -> bool {
+        self.placed == 0
+    }
+
+    /// 石を置いた位置（パスなら None）
+    pub fn pos(&self) -> Option<usize> {
+        (!self.is_pass()).then(|| self.placed.trailing_zeros() as usize)
+    }
+
+    /// ひっくり返した石のビットマスク
+    pub fn flips(&self) -> u64 {
+        self.flips
+    }
+
+    /// 手を打ったプレイヤー
+    pub fn player(&self) -> Player {
+        self.player
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BitBoard {
     pub black: u64,
     pub white: u64,
@@ -13,22 +401,23 @@ pub struct BitBoard {
 impl BitBoard {
     // 各方向でのシフト量とマスク (shift, mask, is_forward)
     // シフト量: ビットシフト量
-    // マスク: 盤面の端からのはみ出しを防ぐマスク
+    // マスク: シフトした後に掛けて、左右の端から反対側の列に回り込んだビットを消すマスク
+    //        （上下の端からはみ出したビットはシフトで消えるので、行は消さない）
     // is_forward: trueなら左上から右下へ（<<）、falseなら右下から左上へ（>>）
     const SHIFTS: [(u32, u64, bool); 8] = [
-        (1, 0x7f7f7f7f7f7f7f7f, false),  // 左
-        (1, 0xfefefefefefefefe, true),   // 右
-        (8, 0xffffffffffffff00, false),  // 上
-        (8, 0x00ffffffffffffff, true),   // 下
-        (9, 0x7f7f7f7f7f7f7f00, false),  // 左上
-        (7, 0xfefefefefefefe00, false),  // 右上
-        (7, 0x007f7f7f7f7f7f7f, true),   // 左下
-        (9, 0x00fefefefefefefefe, true), // 右下
+        (1, 0x7f7f7f7f7f7f7f7f, false), // 左
+        (1, 0xfefefefefefefefe, true),  // 右
+        (8, 0xffffffffffffffff, false), // 上
+        (8, 0xffffffffffffffff, true),  // 下
+        (9, 0x7f7f7f7f7f7f7f7f, false), // 左上
+        (7, 0xfefefefefefefefe, false), // 右上
+        (7, 0x7f7f7f7f7f7f7f7f, true),  // 左下
+        (9, 0xfefefefefefefefe, true),  // 右下
     ];
 
     // キャッシュ用の定数
-    const CORNER_MASK: u64 = 0x8100000000000081; // 角のマスク
-    const EDGE_MASK: u64 = 0xFF818181818181FF; // 辺のマスク
+    pub(crate) const CORNER_MASK: u64 = 0x8100000000000081; // 角のマスク
+    pub(crate) const EDGE_MASK: u64 = 0xFF818181818181FF; // 辺のマスク
 
     /// 新しいビットボードを初期配置で作成
     pub fn new() -> Self {
@@ -38,6 +427,32 @@ impl BitBoard {
         }
     }
 
+    /// 盤面が正しい状態かを検証する
+    /// 読み込んだ盤面などは探索・評価に渡す前に必ずこれを通す
+    pub fn validate(&self) -> Result<(), BoardError> {
+        let overlap = self.black & self.white;
+        if overlap != 0 {
+            return Err(BoardError::Overlapping(overlap));
+        }
+
+        // 石は減らないので、対局中の盤面は初期配置の4個以上ある
+        let total = (self.black | self.white).count_ones();
+        if total < 4 {
+            return Err(BoardError::TooFewDiscs(total));
+        }
+
+        Ok(())
+    }
+
+    /// player から見た (自分の石, 相手の石) のビットボード
+    #[inline(always)]
+    pub fn boards(&self, player: Player) -> (u64, u64) {
+        match player {
+            Player::Black => (self.black, self.white),
+            Player::White => (self.white, self.black),
+        }
+    }
+
     /// 指定位置にビットを設定する
     #[inline(always)]
     fn set_bit(&mut self, pos: usize, player: Player) {
@@ -58,7 +473,7 @@ impl BitBoard {
 
     /// 複数の石を一度にひっくり返す
     #[inline(always)]
-    fn flip_bits(&mut self, bits: u64, player: Player) {
+    pub(crate) fn flip_bits(&mut self, bits: u64, player: Player) {
         match player {
             Player::Black => {
                 self.black |= bits; // 黒石を置く
@@ -105,57 +520,90 @@ impl BitBoard {
         true
     }
 
-    /// ひっくり返し計算（修正版）
-    #[inline(always)]
-    pub fn compute_flips(&self, pos: usize, player: Player) -> u64 {
-        let (my, opp) = match player {
-            Player::Black => (self.black, self.white),
-            Player::White => (self.white, self.black),
+    /// 石を置いてひっくり返し、手を戻すための情報を返す（不正な手なら None で盤面は変えない）
+    ///
+    /// 盤面をコピーせずに探索を書くためのもの。undo_move は打った順の逆順（後に打った手から）
+    /// で呼ぶこと。順序を守らないと盤面が壊れる。
+    pub fn make_move_undo(&mut self, pos: usize, player: Player) -> Option<MoveUndo> {
+        debug_assert!(pos < 64, "ビット位置が範囲外です");
+
+        let placed = 1u64 << pos;
+        if (self.black | self.white) & placed != 0 {
+            return None;
+        }
+
+        let flips = self.compute_flips(pos, player);
+        if flips == 0 {
+            return None;
+        }
+
+        self.flip_bits(placed | flips, player);
+        Some(MoveUndo {
+            player,
+            placed,
+            flips,
+        })
+    }
+
+    /// 打てる手が無い player のパス（盤面は変わらない）
+    /// 手の履歴にパスを残すためのもので、undo_move に渡すと何もしない。打てる手があれば None
+    pub fn pass(&mut self, player: Player) -> Option<MoveUndo> {
+        self.is_pass_required(player).then_some(MoveUndo {
+            player,
+            placed: 0,
+            flips: 0,
+        })
+    }
+
+    /// make_move_undo・pass で打った手を戻す（打った順の逆順で呼ぶこと）
+    pub fn undo_move(&mut self, undo: MoveUndo) {
+        let (my, opp) = match undo.player {
+            Player::Black => (&mut self.black, &mut self.white),
+            Player::White => (&mut self.white, &mut self.black),
         };
+        debug_assert!(
+            *my & (undo.placed | undo.flips) == undo.placed | undo.flips,
+            "手を戻す順序が正しくありません"
+        );
 
-        let mut flips = 0u64;
-        let row = pos / 8;
-        let col = pos % 8;
+        *my &= !(undo.placed | undo.flips);
+        *opp |= undo.flips;
+    }
 
-        // 8方向をチェック
-        let directions = [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1), // 上左、上、上右
-            (0, -1),
-            (0, 1), // 左、右
-            (1, -1),
-            (1, 0),
-            (1, 1), // 下左、下、下右
-        ];
+    /// 手を打った後の盤面を返す（元の盤面は変更しない）
+    /// 不正な手なら None
+    pub fn with_move(&self, pos: usize, player: Player) -> Option<BitBoard> {
+        let mut board = *self;
+        board.make_move(pos, player).then_some(board)
+    }
 
-        for &(dr, dc) in &directions {
-            let mut direction_flips = 0u64;
-            let mut found_opponent = false;
-            let mut r = row as i32 + dr;
-            let mut c = col as i32 + dc;
+    /// ひっくり返し計算
+    /// 方向ごとに置いた位置から相手の石の並びをシフトでたどり、自分の石で挟めたときだけ加える
+    #[inline(always)]
+    pub fn compute_flips(&self, pos: usize, player: Player) -> u64 {
+        #[cfg(test)]
+        COMPUTE_FLIPS_CALLS.with(|calls| calls.set(calls.get() + 1));
 
-            while r >= 0 && r < 8 && c >= 0 && c < 8 {
-                let current_pos = (r * 8 + c) as usize;
-                let current_bit = 1u64 << current_pos;
+        let (my, opp) = self.boards(player);
+        let placed = 1u64 << pos;
+        let mut flips = 0u64;
 
-                if (opp & current_bit) != 0 {
-                    // 相手の石を発見
-                    direction_flips |= current_bit;
-                    found_opponent = true;
-                } else if (my & current_bit) != 0 {
-                    // 自分の石を発見
-                    if found_opponent {
-                        flips |= direction_flips; // この方向の石をひっくり返す
-                    }
-                    break;
+        for &(shift, dir_mask, is_forward) in Self::SHIFTS.iter() {
+            let step = |bits: u64| {
+                if is_forward {
+                    (bits << shift) & dir_mask
                 } else {
-                    // 空きマス
-                    break;
+                    (bits >> shift) & dir_mask
                 }
+            };
 
-                r += dr;
-                c += dc;
+            // 相手の石の並びは最長6個なので、5回伸ばせば足りる（分岐なしで展開できる）
+            let mut run = step(placed) & opp;
+            for _ in 0..5 {
+                run |= step(run) & opp;
+            }
+            if step(run) & my != 0 {
+                flips |= run;
             }
         }
 
@@ -175,10 +623,7 @@ impl BitBoard {
 
         // 事前計算を活用して高速判定
         // - 隣接する相手の石がなければ不正
-        let (_, opp) = match player {
-            Player::Black => (self.black, self.white),
-            Player::White => (self.white, self.black),
-        };
+        let (_, opp) = self.boards(player);
 
         // 周囲8方向に相手の石があるかチェック（一度に計算）
         let adjacent_mask = self.get_adjacent_mask(pos);
@@ -208,43 +653,140 @@ impl BitBoard {
         mask
     }
 
-    /// 合法手の一覧をビットボードとして取得（修正版）
+    /// 合法手の一覧をビットボードとして取得
+    /// 方向ごとに自分の石から相手の石の並びをシフトで伸ばし、その先の空きマスを合法手とする
     #[inline(always)]
     pub fn get_legal_moves(&self, player: Player) -> u64 {
+        let (my, opp) = self.boards(player);
+        let empty = !(my | opp);
         let mut legal_moves = 0u64;
-        let occupied = self.black | self.white;
-
-        // 全ての空きマスをチェック
-        for pos in 0..64 {
-            let pos_bit = 1u64 << pos;
 
-            // 既に石が置かれていればスキップ
-            if (occupied & pos_bit) != 0 {
-                continue;
-            }
+        for &(shift, dir_mask, is_forward) in Self::SHIFTS.iter() {
+            let step = |bits: u64| {
+                if is_forward {
+                    (bits << shift) & dir_mask
+                } else {
+                    (bits >> shift) & dir_mask
+                }
+            };
 
-            // この位置でひっくり返せる石があるかチェック
-            if self.compute_flips(pos, player) != 0 {
-                legal_moves |= pos_bit;
+            // 相手の石の並びは最長6個なので、5回伸ばせば足りる
+            let mut run = step(my) & opp;
+            for _ in 0..5 {
+                run |= step(run) & opp;
             }
+            legal_moves |= step(run) & empty;
         }
 
         legal_moves
     }
 
+    /// 合法手の位置を小さい順に返すイテレータ（Vecを確保しない）
+    pub fn legal_moves_iter(&self, player: Player) -> impl Iterator<Item = usize> {
+        bit_positions(self.get_legal_moves(player))
+    }
+
+    /// player の石がある位置を小さい順に返すイテレータ（立っているビットだけをたどる）
+    pub fn disc_positions(&self, player: Player) -> impl Iterator<Item = usize> {
+        bit_positions(self.boards(player).0)
+    }
+
     /// 合法手の一覧を座標のベクターとして取得
     pub fn get_legal_move_positions(&self, player: Player) -> Vec<usize> {
-        let legal_moves = self.get_legal_moves(player);
-        let mut positions = Vec::new();
+        self.legal_moves_iter(player).collect()
+    }
 
-        for pos in 0..64 {
-            let bit = 1u64 << pos;
-            if (legal_moves & bit) != 0 {
-                positions.push(pos);
+    /// 合法手ごとの (位置, 座標表記, ひっくり返る石の数) の一覧（位置の昇順）
+    pub fn legal_moves_detailed(&self, player: Player) -> Vec<(usize, String, u32)> {
+        self.legal_moves_iter(player)
+            .map(|pos| {
+                let flips = self.compute_flips(pos, player).count_ones();
+                (pos, notation(pos), flips)
+            })
+            .collect()
+    }
+
+    /// 合法手の数を取得（Vecを確保しない）
+    #[inline]
+    pub fn legal_move_count(&self, player: Player) -> u32 {
+        self.get_legal_moves(player).count_ones()
+    }
+
+    /// bits のいずれかに8方向で隣接するマス
+    #[inline(always)]
+    fn neighbors(bits: u64) -> u64 {
+        let mut adjacent = 0;
+        for &(shift, dir_mask, is_forward) in Self::SHIFTS.iter() {
+            if is_forward {
+                adjacent |= (bits << shift) & dir_mask;
+            } else {
+                adjacent |= (bits >> shift) & dir_mask;
             }
         }
+        adjacent
+    }
 
-        positions
+    /// 空きマスに隣接している player の石（フロンティア）
+    #[inline]
+    pub fn get_frontier_discs(&self, player: Player) -> u64 {
+        let empty = !(self.black | self.white);
+        let (my_board, _) = self.boards(player);
+
+        my_board & Self::neighbors(empty)
+    }
+
+    /// player の潜在的な着手可能数（相手の石に隣接している空きマスの数）
+    #[inline]
+    pub fn potential_mobility(&self, player: Player) -> i32 {
+        let (my_board, opp_board) = self.boards(player);
+        let empty = !(my_board | opp_board);
+
+        (empty & Self::neighbors(opp_board)).count_ones() as i32
+    }
+
+    /// player の確定石（今後どう打たれても裏返らない石）
+    ///
+    /// 横・縦・2つの斜めの4本の線それぞれで、線が石で埋まっているか、
+    /// 両隣のどちらかが盤の外か自分の確定石であれば確定とし、増えなくなるまで広げる。
+    /// 埋まった辺はすべて確定石になる。
+    pub fn stable_discs(&self, player: Player) -> u64 {
+        const COL_A: u64 = 0x0101_0101_0101_0101;
+        const COL_H: u64 = 0x8080_8080_8080_8080;
+        const ROW_1: u64 = 0x0000_0000_0000_00ff;
+        const ROW_8: u64 = 0xff00_0000_0000_0000;
+        const NOT_COL_A: u64 = !COL_A;
+        const NOT_COL_H: u64 = !COL_H;
+
+        let (my_board, _) = self.boards(player);
+        let [full_h, full_v, full_d, full_a] = full_lines(self.black | self.white);
+
+        let mut stable = 0u64;
+        loop {
+            // 各方向で、隣が盤の外か確定石なら（または線が埋まっていれば）その線では裏返らない
+            let horizontal =
+                full_h | COL_A | COL_H | ((stable << 1) & NOT_COL_A) | ((stable >> 1) & NOT_COL_H);
+            let vertical = full_v | ROW_1 | ROW_8 | (stable << 8) | (stable >> 8);
+            let diagonal = full_d
+                | COL_A
+                | ROW_1
+                | COL_H
+                | ROW_8
+                | ((stable << 9) & NOT_COL_A)
+                | ((stable >> 9) & NOT_COL_H);
+            let anti_diagonal = full_a
+                | COL_A
+                | ROW_1
+                | COL_H
+                | ROW_8
+                | ((stable << 7) & NOT_COL_H)
+                | ((stable >> 7) & NOT_COL_A);
+
+            let next = my_board & horizontal & vertical & diagonal & anti_diagonal;
+            if next == stable {
+                return stable;
+            }
+            stable = next;
+        }
     }
 
     /// 指定位置の石を取得（高速化版）
@@ -269,16 +811,37 @@ impl BitBoard {
         if row >= 8 || col >= 8 {
             return None;
         }
-        self.get_disc(row * 8 + col)
+        self.get_disc(rc_to_pos(row, col))
+    }
+
+    /// 指定したマスに石を置く、または取り除く（None なら空きマスにする）
+    /// 盤面の編集用で、石はひっくり返さない。黒と白が重ならないことは保たれる
+    pub fn set_disc_at(
+        &mut self,
+        row: usize,
+        col: usize,
+        disc: Option<Player>,
+    ) -> Result<(), BoardError> {
+        if row >= 8 || col >= 8 {
+            return Err(BoardError::OutOfBounds(row, col));
+        }
+
+        let pos = rc_to_pos(row, col);
+        match disc {
+            Some(player) => self.set_bit(pos, player),
+            None => {
+                let bit = 1u64 << pos;
+                self.black &= !bit;
+                self.white &= !bit;
+            }
+        }
+        Ok(())
     }
 
     /// 石の数をカウント（高速化版）
     #[inline(always)]
     pub fn count_discs(&self, player: Player) -> u32 {
-        match player {
-            Player::Black => self.black.count_ones(),
-            Player::White => self.white.count_ones(),
-        }
+        self.boards(player).0.count_ones()
     }
 
     /// 両プレイヤーの石の数を取得（高速化版）
@@ -287,6 +850,19 @@ impl BitBoard {
         (self.black.count_ones(), self.white.count_ones())
     }
 
+    /// 空きマスの数を取得
+    #[inline(always)]
+    pub fn count_empty(&self) -> u32 {
+        64 - (self.black | self.white).count_ones()
+    }
+
+    /// 盤面が埋まっている割合（0.0〜1.0）
+    #[inline]
+    pub fn board_fill_fraction(&self) -> f32 {
+        let (black_count, white_count) = self.count_all_discs();
+        (black_count + white_count) as f32 / 64.0
+    }
+
     /// パス判定（高速化版）
     #[inline(always)]
     pub fn is_pass_required(&self, player: Player) -> bool {
@@ -296,13 +872,234 @@ impl BitBoard {
     /// ゲーム終了判定（最適化版）
     #[inline]
     pub fn is_game_over(&self) -> bool {
+        self.game_over_reason().is_some()
+    }
+
+    /// ゲーム終了の理由を返す（終了していなければNone）
+    #[inline]
+    pub fn game_over_reason(&self) -> Option<GameOverReason> {
         // 空きマスがなければ終了
         if self.black | self.white == !0u64 {
-            return true;
+            return Some(GameOverReason::BoardFull);
         }
 
         // 両者にとって合法手がなければ終了
-        self.get_legal_moves(Player::Black) == 0 && self.get_legal_moves(Player::White) == 0
+        if Player::all()
+            .into_iter()
+            .all(|player| self.get_legal_moves(player) == 0)
+        {
+            return Some(GameOverReason::NoLegalMovesEither);
+        }
+
+        None
+    }
+
+    /// 盤面と手番の Zobrist ハッシュ（同じ局面・手番なら常に同じ値）
+    pub fn zobrist_hash(&self, player: Player) -> u64 {
+        let side = match player {
+            Player::Black => 0,
+            Player::White => ZOBRIST_WHITE_TO_MOVE,
+        };
+        Player::all()
+            .into_iter()
+            .flat_map(|color| self.disc_positions(color).map(move |pos| (pos, color)))
+            .fold(side, |hash, (pos, color)| hash ^ zobrist_toggle(pos, color))
+    }
+
+    /// depth 手先までの局面の数（合法手生成の検証用。パスも1手と数え、終局した局面はそこで数える）
+    pub fn perft(&self, player: Player, depth: u32) -> u64 {
+        if depth == 0 || self.is_game_over() {
+            return 1;
+        }
+
+        let legal_moves = self.get_legal_moves(player);
+        if legal_moves == 0 {
+            return self.perft(player.opponent(), depth - 1);
+        }
+
+        bit_positions(legal_moves)
+            .map(|pos| {
+                let mut child = *self;
+                child.make_move(pos, player);
+                child.perft(player.opponent(), depth - 1)
+            })
+            .sum()
+    }
+
+    /// 座標表記の手を順に打つ（打てる手が無い側は、パスの表記が無くても自動でパス）
+    /// 最後に手番となるプレイヤーを返す
+    /// 読めない手・打てない手（打てる手があるのにパスした場合も）があればそこで止まり、
+    /// 盤面はそれより前の手を打った状態のまま残る（打てた手の数は ApplyError::index で分かる）
+    pub fn apply_moves(
+        &mut self,
+        moves: &[&str],
+        start_player: Player,
+    ) -> Result<Player, ApplyError> {
+        let mut player = start_player;
+
+        for (index, &notation) in moves.iter().enumerate() {
+            if is_pass_notation(notation) {
+                if self.is_pass_required(player) && self.is_pass_required(player.opponent()) {
+                    return Err(ApplyError::GameOver { index });
+                }
+                if self.pass(player).is_none() {
+                    return Err(ApplyError::IllegalMove {
+                        index,
+                        notation: notation.to_string(),
+                    });
+                }
+                player = player.opponent();
+                continue;
+            }
+
+            let pos = parse_notation(notation).ok_or_else(|| ApplyError::InvalidNotation {
+                index,
+                notation: notation.to_string(),
+            })?;
+
+            if self.is_pass_required(player) {
+                if self.is_pass_required(player.opponent()) {
+                    return Err(ApplyError::GameOver { index });
+                }
+                player = player.opponent();
+            }
+
+            if !self.make_move(pos, player) {
+                return Err(ApplyError::IllegalMove {
+                    index,
+                    notation: notation.to_string(),
+                });
+            }
+            player = player.opponent();
+        }
+
+        // 次の手番がパスなら相手の番にする
+        if self.is_pass_required(player) && !self.is_pass_required(player.opponent()) {
+            player = player.opponent();
+        }
+
+        Ok(player)
+    }
+
+    /// 両方の色の石に同じビット変換を施した盤面
+    #[inline]
+    fn map_bits(&self, f: impl Fn(u64) -> u64) -> BitBoard {
+        BitBoard {
+            black: f(self.black),
+            white: f(self.white),
+        }
+    }
+
+    /// 時計回りに90度回転した盤面（(r, c) → (c, 7 - r)）
+    pub fn rotate_90(&self) -> BitBoard {
+        self.map_bits(|bits| mirror_horizontal(transpose(bits)))
+    }
+
+    /// 左右を反転した盤面（列 c → 7 - c）
+    pub fn flip_horizontal(&self) -> BitBoard {
+        self.map_bits(mirror_horizontal)
+    }
+
+    /// 上下を反転した盤面（行 r → 7 - r）
+    pub fn flip_vertical(&self) -> BitBoard {
+        self.map_bits(flip_vertical)
+    }
+
+    /// 左上と右下を結ぶ対角線で反転した盤面（(r, c) → (c, r)）
+    pub fn flip_diagonal(&self) -> BitBoard {
+        self.map_bits(transpose)
+    }
+
+    /// 8通りの回転・反転のうち id 番目を施した盤面（0は元の盤面、8以上は8で割った余りを使う）
+    /// 1ビット目で上下、2ビット目で左右、3ビット目で対角線の反転をこの順に行う
+    pub fn transform(&self, id: u8) -> BitBoard {
+        self.map_bits(|mut bits| {
+            if id & 1 != 0 {
+                bits = flip_vertical(bits);
+            }
+            if id & 2 != 0 {
+                bits = mirror_horizontal(bits);
+            }
+            if id & 4 != 0 {
+                bits = transpose(bits);
+            }
+            bits
+        })
+    }
+
+    /// 回転・反転した8通りの盤面（先頭は元の盤面、i 番目は transform(i)）
+    pub fn symmetries(&self) -> [BitBoard; 8] {
+        std::array::from_fn(|i| self.transform(i as u8))
+    }
+
+    /// 回転・反転で同じになる局面を同一視するための代表の盤面と手番
+    /// （8通りのうち (黒, 白) が最小のもの。回転・反転しても石の色は変わらないので手番はそのまま）
+    pub fn canonical(&self, player: Player) -> (BitBoard, Player) {
+        let board = self
+            .symmetries()
+            .into_iter()
+            .min_by_key(|board| (board.black, board.white))
+            .unwrap_or(*self);
+        (board, player)
+    }
+
+    /// 石の数から手番を推定する
+    /// 初期配置から交互に打った場合の手番（石の数が偶数なら黒）。その手番側が打てず相手が打てるならパスとみなして相手
+    /// （途中でパスがあった局面では実際の手番と異なることがある）
+    pub fn side_to_move(&self) -> Player {
+        let (black, white) = self.count_all_discs();
+        let player = if (black + white) % 2 == 0 {
+            Player::Black
+        } else {
+            Player::White
+        };
+        if self.get_legal_moves(player) == 0 && self.get_legal_moves(player.opponent()) != 0 {
+            player.opponent()
+        } else {
+            player
+        }
+    }
+
+    /// 盤面を保存・共有するための文字列にする（手番は side_to_move で推定する）
+    /// 左上から行ごとに64文字（黒 'X'・白 'O'・空き '.'）、空白1つ、手番（'X' か 'O'）
+    pub fn to_position_string(&self) -> String {
+        self.to_position_string_for(self.side_to_move())
+    }
+
+    /// to_position_string と同じ形式で、手番を指定して文字列にする
+    pub fn to_position_string_for(&self, player: Player) -> String {
+        let squares: String = (0..64)
+            .map(|pos| self.get_disc(pos).map_or('.', |player| player.to_char()))
+            .collect();
+        format!("{} {}", squares, player.to_char())
+    }
+
+    /// to_position_string の形式の文字列から盤面と手番を作る（行の間などの空白は無視する）
+    /// 黒は 'X'/'B'/'*'、白は 'O'/'W'、空きは '-'/'.'（大文字小文字は区別しない）
+    pub fn from_position_string(text: &str) -> Result<(BitBoard, Player), ParseError> {
+        let mut squares: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        if squares.len() != 65 {
+            return Err(ParseError::InvalidLength(squares.len()));
+        }
+        let player = match squares.pop() {
+            Some(c) if matches!(c.to_ascii_uppercase(), 'X' | 'B' | '*') => Player::Black,
+            Some(c) if matches!(c.to_ascii_uppercase(), 'O' | 'W') => Player::White,
+            Some(c) => return Err(ParseError::InvalidSide(c)),
+            None => unreachable!(),
+        };
+
+        let mut board = BitBoard { black: 0, white: 0 };
+        for (pos, &c) in squares.iter().enumerate() {
+            match c.to_ascii_uppercase() {
+                'X' | 'B' | '*' => board.black |= 1u64 << pos,
+                'O' | 'W' => board.white |= 1u64 << pos,
+                '-' | '.' => {}
+                _ => return Err(ParseError::InvalidChar(c)),
+            }
+        }
+
+        board.validate()?;
+        Ok((board, player))
     }
 
     /// 勝者を返す
@@ -349,3 +1146,606 @@ impl fmt::Display for BitBoard {
         writeln!(f, "黒(X): {} 白(O): {}", black_count, white_count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selftest::random_positions;
+
+    /// ランダム局面で比べる数
+    const EQUIVALENCE_BOARDS: usize = 3000;
+
+    /// シフトで求める前の合法手生成（空きマスを1つずつ調べる素朴な実装）
+    fn scalar_legal_moves(board: &BitBoard, player: Player) -> u64 {
+        let mut legal_moves = 0u64;
+        let occupied = board.black | board.white;
+
+        // 全ての空きマスをチェック
+        for pos in 0..64 {
+            let pos_bit = 1u64 << pos;
+
+            // 既に石が置かれていればスキップ
+            if (occupied & pos_bit) != 0 {
+                continue;
+            }
+
+            // この位置でひっくり返せる石があるかチェック
+            if scalar_flips(board, pos, player) != 0 {
+                legal_moves |= pos_bit;
+            }
+        }
+
+        legal_moves
+    }
+
+    /// シフトで求める前のひっくり返し計算（8方向に1マスずつたどる素朴な実装）
+    fn scalar_flips(board: &BitBoard, pos: usize, player: Player) -> u64 {
+        let (my, opp) = board.boards(player);
+
+        let mut flips = 0u64;
+        let (row, col) = pos_to_rc(pos);
+
+        // 8方向をチェック
+        let directions = [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1), // 上左、上、上右
+            (0, -1),
+            (0, 1), // 左、右
+            (1, -1),
+            (1, 0),
+            (1, 1), // 下左、下、下右
+        ];
+
+        for &(dr, dc) in &directions {
+            let mut direction_flips = 0u64;
+            let mut found_opponent = false;
+            let mut r = row as i32 + dr;
+            let mut c = col as i32 + dc;
+
+            while (0..8).contains(&r) && (0..8).contains(&c) {
+                let current_pos = rc_to_pos(r as usize, c as usize);
+                let current_bit = 1u64 << current_pos;
+
+                if (opp & current_bit) != 0 {
+                    // 相手の石を発見
+                    direction_flips |= current_bit;
+                    found_opponent = true;
+                } else if (my & current_bit) != 0 {
+                    // 自分の石を発見
+                    if found_opponent {
+                        flips |= direction_flips; // この方向の石をひっくり返す
+                    }
+                    break;
+                } else {
+                    // 空きマス
+                    break;
+                }
+
+                r += dr;
+                c += dc;
+            }
+        }
+
+        flips
+    }
+
+    #[test]
+    fn flips_match_the_scalar_version() {
+        for (i, (board, _)) in random_positions(EQUIVALENCE_BOARDS).into_iter().enumerate() {
+            let empty = !(board.black | board.white);
+            for pos in (0..64).filter(|&pos| empty & (1u64 << pos) != 0) {
+                for player in Player::all() {
+                    assert_eq!(
+                        board.compute_flips(pos, player),
+                        scalar_flips(&board, pos, player),
+                        "局面{} {} {:?}\n{}",
+                        i,
+                        notation(pos),
+                        player,
+                        board
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn legal_moves_match_the_scalar_generator() {
+        for (i, (board, _)) in random_positions(EQUIVALENCE_BOARDS).into_iter().enumerate() {
+            for player in Player::all() {
+                assert_eq!(
+                    board.get_legal_moves(player),
+                    scalar_legal_moves(&board, player),
+                    "局面{} {:?}\n{}",
+                    i,
+                    player,
+                    board
+                );
+            }
+        }
+
+        // 左右の端の列と上下の端の行をまたいで挟む手（シフトのマスクで回り込まないこと）
+        let edges = BitBoard::from_position_string(concat!(
+            "O.......",
+            "X......O",
+            "X......X",
+            "........",
+            "........",
+            "O......X",
+            "X......O",
+            "O....... X"
+        ))
+        .unwrap()
+        .0;
+        for player in Player::all() {
+            assert_eq!(
+                edges.get_legal_moves(player),
+                scalar_legal_moves(&edges, player)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_overlapping_and_accepts_valid_boards() {
+        assert!(BitBoard::new().validate().is_ok());
+
+        let overlapping = BitBoard {
+            black: BitBoard::new().black | (1u64 << 27),
+            white: BitBoard::new().white,
+        };
+        assert!(matches!(
+            overlapping.validate(),
+            Err(BoardError::Overlapping(squares)) if squares == 1u64 << 27
+        ));
+
+        let empty = BitBoard { black: 0, white: 0 };
+        assert!(matches!(empty.validate(), Err(BoardError::TooFewDiscs(0))));
+
+        // 編集で石を置き換えても黒と白は重ならない
+        let mut edited = BitBoard::new();
+        edited.set_disc_at(3, 3, Some(Player::Black)).unwrap();
+        assert!(edited.validate().is_ok());
+        assert!(edited.set_disc_at(8, 0, None).is_err());
+    }
+
+    #[test]
+    fn position_string_round_trips() {
+        let mut board = BitBoard::new();
+        let mut player = Player::Black;
+        let mut passed = false;
+        while !board.is_game_over() {
+            // 手番を推定した文字列からも同じ盤面に戻る（まだパスがなく手番側が打てるなら手番も一致する）
+            let (parsed, side) =
+                BitBoard::from_position_string(&board.to_position_string()).unwrap();
+            assert_eq!((parsed.black, parsed.white), (board.black, board.white));
+            if !passed && board.get_legal_moves(player) != 0 {
+                assert_eq!(side, player);
+            }
+
+            let text = board.to_position_string_for(player);
+            assert_eq!(text.len(), 66);
+            assert_eq!(
+                BitBoard::from_position_string(&text).unwrap(),
+                (board, player)
+            );
+
+            // 行ごとに改行を入れても読める
+            let rows: Vec<&str> = (0..8).map(|row| &text[row * 8..row * 8 + 8]).collect();
+            let spaced = format!("{}\n{}", rows.join("\n"), &text[65..]);
+            assert_eq!(
+                BitBoard::from_position_string(&spaced).unwrap(),
+                (board, player)
+            );
+
+            match board.legal_moves_iter(player).last() {
+                Some(pos) => {
+                    board.make_move(pos, player);
+                }
+                None => passed = true,
+            }
+            player = player.opponent();
+        }
+        assert!(matches!(
+            BitBoard::from_position_string("X"),
+            Err(ParseError::InvalidLength(1))
+        ));
+        assert!(matches!(
+            BitBoard::from_position_string(&format!("{} ?", ".".repeat(64))),
+            Err(ParseError::InvalidSide('?'))
+        ));
+        assert!(matches!(
+            BitBoard::from_position_string(&format!("{} X", "X".repeat(2) + &".".repeat(62))),
+            Err(ParseError::InvalidBoard(BoardError::TooFewDiscs(2)))
+        ));
+    }
+
+    #[test]
+    fn game_over_reason_distinguishes_full_board_and_no_moves() {
+        assert_eq!(BitBoard::new().game_over_reason(), None);
+
+        let full = BitBoard {
+            black: 0x0000_0000_FFFF_FFFF,
+            white: 0xFFFF_FFFF_0000_0000,
+        };
+        assert_eq!(full.game_over_reason(), Some(GameOverReason::BoardFull));
+
+        // 白の石が無くなると、空きマスが残っていても両者とも打てない
+        let wiped_out = BitBoard {
+            black: DEFAULT_BLACK | DEFAULT_WHITE,
+            white: 0,
+        };
+        assert_eq!(
+            wiped_out.game_over_reason(),
+            Some(GameOverReason::NoLegalMovesEither)
+        );
+        assert!(wiped_out.is_game_over());
+    }
+
+    #[test]
+    fn board_fill_fraction_counts_both_colors() {
+        assert_eq!(BitBoard::new().board_fill_fraction(), 4.0 / 64.0);
+
+        let full = BitBoard {
+            black: 0x5555_5555_5555_5555,
+            white: 0xAAAA_AAAA_AAAA_AAAA,
+        };
+        assert_eq!(full.board_fill_fraction(), 1.0);
+    }
+
+    #[test]
+    fn apply_moves_plays_legal_sequence() {
+        let mut board = BitBoard::new();
+        assert_eq!(
+            board.apply_moves(&["f5", "d6", "c3"], Player::Black),
+            Ok(Player::White)
+        );
+        assert_eq!(board.count_all_discs(), (5, 2));
+    }
+
+    #[test]
+    fn apply_moves_inserts_required_pass() {
+        // 8手目の後に黒は打てないので、白が続けて c3 に打つ
+        let moves = ["e6", "f6", "g6", "g7", "c4", "h6", "h8", "f8", "c3"];
+        let mut board = BitBoard::new();
+        assert_eq!(board.apply_moves(&moves, Player::Black), Ok(Player::Black));
+
+        // パスを明示しても同じ盤面になる
+        let mut explicit = BitBoard::new();
+        let mut with_pass = moves.to_vec();
+        with_pass.insert(8, PASS_NOTATION);
+        assert_eq!(
+            explicit.apply_moves(&with_pass, Player::Black),
+            Ok(Player::Black)
+        );
+        assert_eq!(explicit, board);
+    }
+
+    #[test]
+    fn apply_moves_reports_the_failing_move() {
+        let mut board = BitBoard::new();
+        assert_eq!(
+            board.apply_moves(&["f5", "f5"], Player::Black),
+            Err(ApplyError::IllegalMove {
+                index: 1,
+                notation: "f5".to_string()
+            })
+        );
+        // 打てた手までは盤面に残る
+        assert_eq!(board.count_all_discs(), (4, 1));
+
+        let mut board = BitBoard::new();
+        assert_eq!(
+            board.apply_moves(&["z9"], Player::Black),
+            Err(ApplyError::InvalidNotation {
+                index: 0,
+                notation: "z9".to_string()
+            })
+        );
+        // 打てる手があるのにパスはできない
+        assert!(matches!(
+            board.apply_moves(&[PASS_NOTATION], Player::Black),
+            Err(ApplyError::IllegalMove { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn legal_move_count_matches_position_list() {
+        let mut board = BitBoard::new();
+        let mut player = Player::Black;
+        while !board.is_game_over() {
+            for side in Player::all() {
+                assert_eq!(
+                    board.legal_move_count(side) as usize,
+                    board.get_legal_move_positions(side).len()
+                );
+            }
+            if let Some(pos) = board.legal_moves_iter(player).next() {
+                board.make_move(pos, player);
+            }
+            player = player.opponent();
+        }
+        assert_eq!(board.legal_move_count(Player::Black), 0);
+    }
+
+    #[test]
+    fn set_disc_at_cycles_a_square() {
+        let mut board = BitBoard::new();
+        let (row, col) = (2, 5);
+        let bit = 1u64 << rc_to_pos(row, col);
+
+        for disc in [
+            Some(Player::Black),
+            Some(Player::White),
+            None,
+            Some(Player::White),
+        ] {
+            board.set_disc_at(row, col, disc).unwrap();
+            assert_eq!(board.get_disc_at(row, col), disc);
+            assert_eq!(board.black & bit != 0, disc == Some(Player::Black));
+            assert_eq!(board.white & bit != 0, disc == Some(Player::White));
+            assert_eq!(board.black & board.white, 0);
+        }
+        // 他のマスはそのまま
+        assert_eq!(board.black, BitBoard::new().black);
+        assert_eq!(board.white, BitBoard::new().white | bit);
+
+        assert_eq!(
+            board.set_disc_at(0, 8, Some(Player::Black)),
+            Err(BoardError::OutOfBounds(0, 8))
+        );
+        assert_eq!(board.get_disc_at(8, 0), None);
+    }
+
+    #[test]
+    fn pos_and_rc_conversions_are_inverses() {
+        for pos in 0..64 {
+            let (row, col) = pos_to_rc(pos);
+            assert!(row < 8 && col < 8);
+            assert_eq!(rc_to_pos(row, col), pos);
+        }
+        for row in 0..8 {
+            for col in 0..8 {
+                assert_eq!(pos_to_rc(rc_to_pos(row, col)), (row, col));
+            }
+        }
+        // 行が上位、列が下位になる並び
+        assert_eq!(pos_to_rc(1), (0, 1));
+        assert_eq!(pos_to_rc(8), (1, 0));
+        assert_eq!(rc_to_pos(7, 7), 63);
+    }
+
+    #[test]
+    fn disc_positions_yield_exactly_the_set_bits() {
+        let mut board = BitBoard::new();
+        board
+            .apply_moves(&["f5", "d6", "c3", "d3", "c4"], Player::Black)
+            .unwrap();
+        let corners = BitBoard {
+            black: 1 | 1u64 << 63,
+            white: 1u64 << 7 | 1u64 << 56,
+        };
+
+        for board in [BitBoard::new(), board, corners] {
+            for player in Player::all() {
+                let positions: Vec<usize> = board.disc_positions(player).collect();
+                let mask = positions.iter().fold(0u64, |mask, &pos| mask | 1u64 << pos);
+                assert_eq!(mask, board.boards(player).0);
+                assert_eq!(positions.len() as u32, board.count_discs(player));
+                assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(positions
+                    .iter()
+                    .all(|&pos| board.get_disc(pos) == Some(player)));
+            }
+        }
+    }
+
+    #[test]
+    fn transforms_keep_disc_counts_and_rotate_four_times_to_identity() {
+        let mut board = BitBoard::new();
+        board
+            .apply_moves(&["f5", "d6", "c3", "d3", "c4", "f4"], Player::Black)
+            .unwrap();
+        let corner = BitBoard {
+            black: 1,
+            white: 1u64 << 9,
+        };
+
+        for board in [board, corner] {
+            let rotated = board.rotate_90();
+            assert_ne!(rotated, board);
+            assert_eq!(rotated.rotate_90().rotate_90().rotate_90(), board);
+
+            let (black, white) = board.count_all_discs();
+            for transformed in [
+                rotated,
+                board.flip_horizontal(),
+                board.flip_vertical(),
+                board.flip_diagonal(),
+            ]
+            .into_iter()
+            .chain(board.symmetries())
+            {
+                assert_eq!(transformed.count_all_discs(), (black, white));
+            }
+            // 反転は2回で元に戻る
+            assert_eq!(board.flip_horizontal().flip_horizontal(), board);
+            assert_eq!(board.flip_vertical().flip_vertical(), board);
+            assert_eq!(board.flip_diagonal().flip_diagonal(), board);
+        }
+
+        // 左上の角は回転すると右上の角に移る
+        assert_eq!(corner.rotate_90().get_disc_at(0, 7), Some(Player::Black));
+        assert_eq!(
+            corner.flip_horizontal().get_disc_at(0, 7),
+            Some(Player::Black)
+        );
+        assert_eq!(
+            corner.flip_vertical().get_disc_at(7, 0),
+            Some(Player::Black)
+        );
+        assert_eq!(corner.transform(0), corner);
+    }
+
+    #[test]
+    fn zobrist_hash_is_stable_and_tracks_flips() {
+        // 違う手順で同じ局面にしても同じハッシュになる
+        let mut a = BitBoard::new();
+        a.apply_moves(&["f5", "f6", "e6"], Player::Black).unwrap();
+        let mut b = BitBoard::new();
+        b.apply_moves(&["e6", "f6", "f5"], Player::Black).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.zobrist_hash(Player::White), b.zobrist_hash(Player::White));
+        assert_ne!(a.zobrist_hash(Player::White), a.zobrist_hash(Player::Black));
+
+        // 石を1つ裏返すとハッシュが変わり、差分の XOR で同じ値に更新できる
+        let pos = a.disc_positions(Player::White).next().unwrap();
+        let mut flipped = a;
+        let (row, col) = pos_to_rc(pos);
+        flipped.set_disc_at(row, col, Some(Player::Black)).unwrap();
+        let hash = a.zobrist_hash(Player::White);
+        assert_ne!(flipped.zobrist_hash(Player::White), hash);
+        assert_eq!(
+            flipped.zobrist_hash(Player::White),
+            hash ^ zobrist_toggle(pos, Player::White) ^ zobrist_toggle(pos, Player::Black)
+        );
+
+        // 着手も置いた石と裏返した石の XOR と手番の切り替えで更新できる
+        let player = Player::White;
+        let mut child = a;
+        let undo = child.make_move_undo(rc_to_pos(3, 5), player).unwrap();
+        let incremental = bit_positions(undo.flips()).fold(
+            hash ^ zobrist_toggle(rc_to_pos(3, 5), player) ^ ZOBRIST_WHITE_TO_MOVE,
+            |hash, pos| hash ^ zobrist_toggle(pos, player) ^ zobrist_toggle(pos, Player::Black),
+        );
+        assert_eq!(child.zobrist_hash(Player::Black), incremental);
+    }
+
+    #[test]
+    fn equal_boards_hash_equally() {
+        use std::collections::hash_map::RandomState;
+        use std::collections::HashSet;
+        use std::hash::BuildHasher;
+
+        let mut a = BitBoard::new();
+        a.apply_moves(&["f5", "f6", "e6"], Player::Black).unwrap();
+        let mut b = BitBoard::new();
+        b.apply_moves(&["e6", "f6", "f5"], Player::Black).unwrap();
+        let mut c = BitBoard::new();
+        c.apply_moves(&["f5", "d6", "c3"], Player::Black).unwrap();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(BitBoard::new(), a);
+
+        let set: HashSet<BitBoard> = [a, b, c, BitBoard::new()].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&b));
+
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(a), state.hash_one(b));
+    }
+
+    #[test]
+    fn symmetric_positions_share_a_canonical_form() {
+        let mut board = BitBoard::new();
+        board
+            .apply_moves(&["f5", "d6", "c3", "d3", "c4"], Player::Black)
+            .unwrap();
+
+        // 回転の逆は3回の回転、反転の逆は同じ反転
+        let rotated = board.rotate_90();
+        assert_eq!(rotated.rotate_90().rotate_90().rotate_90(), board);
+        assert_eq!(board.flip_diagonal().flip_diagonal(), board);
+
+        let (canonical, player) = board.canonical(Player::White);
+        assert_eq!(player, Player::White);
+        for transformed in board.symmetries() {
+            assert_eq!(
+                transformed.canonical(Player::White),
+                (canonical, Player::White)
+            );
+            assert!((canonical.black, canonical.white) <= (transformed.black, transformed.white));
+        }
+        assert!(board.symmetries().contains(&canonical));
+
+        // 初手の4通りはどれも同じ局面になる
+        let first_moves: Vec<BitBoard> = BitBoard::new()
+            .legal_moves_iter(Player::Black)
+            .map(|pos| BitBoard::new().with_move(pos, Player::Black).unwrap())
+            .collect();
+        assert_eq!(first_moves.len(), 4);
+        let canonical = first_moves[0].canonical(Player::White);
+        assert!(first_moves
+            .iter()
+            .all(|board| board.canonical(Player::White) == canonical));
+        // 対称でない局面とは一致しない
+        assert_ne!(board.canonical(Player::White).0, canonical.0);
+    }
+
+    #[test]
+    fn all_squares_round_trip_through_notation() {
+        for pos in 0..64 {
+            let square = Square::new(pos).unwrap();
+            let text = square.to_notation();
+            assert_eq!(Square::from_notation(&text), Some(square));
+            assert_eq!(Square::from_notation(&text.to_uppercase()), Some(square));
+            let (row, col): (usize, usize) = square.into();
+            assert_eq!((row, col), pos_to_rc(pos));
+            assert_eq!(Square::from((row, col)), square);
+        }
+
+        // 列が a〜h、行が 1〜8
+        assert_eq!(Square::from_notation("a1"), Square::new(0));
+        assert_eq!(Square::from_notation("h1"), Square::new(7));
+        assert_eq!(
+            Square::from_notation("f5").map(Square::index),
+            Some(rc_to_pos(4, 5))
+        );
+        for text in ["", "a", "i1", "a0", "a9", "f55", "5f"] {
+            assert_eq!(Square::from_notation(text), None, "{:?}", text);
+        }
+        assert_eq!(Square::new(64), None);
+    }
+
+    #[test]
+    fn boards_returns_mine_then_theirs() {
+        let mut board = BitBoard::new();
+        board
+            .apply_moves(&["f5", "d6", "c3"], Player::Black)
+            .unwrap();
+        assert_ne!(board.black.count_ones(), board.white.count_ones());
+
+        assert_eq!(board.boards(Player::Black), (board.black, board.white));
+        assert_eq!(board.boards(Player::White), (board.white, board.black));
+        for player in Player::all() {
+            let (mine, theirs) = board.boards(player);
+            assert_eq!(board.boards(player.opponent()), (theirs, mine));
+            assert_eq!(mine.count_ones(), board.count_discs(player));
+        }
+    }
+
+    #[test]
+    fn legal_moves_detailed_matches_compute_flips() {
+        let mut board = BitBoard::new();
+        let player = board
+            .apply_moves(&["f5", "d6", "c3", "d3", "c4"], Player::Black)
+            .unwrap();
+
+        for player in [player, player.opponent()] {
+            let detailed = board.legal_moves_detailed(player);
+            let positions: Vec<usize> = detailed.iter().map(|&(pos, _, _)| pos).collect();
+            assert_eq!(positions, board.get_legal_move_positions(player));
+            for (pos, name, flips) in detailed {
+                assert_eq!(name, notation(pos));
+                assert_eq!(flips, board.compute_flips(pos, player).count_ones());
+                assert!(flips > 0);
+            }
+        }
+        // 1つの手で複数の石を返す手も含まれている
+        assert!(board
+            .legal_moves_detailed(Player::White)
+            .iter()
+            .any(|&(_, _, flips)| flips > 1));
+    }
+}
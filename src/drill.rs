@@ -0,0 +1,202 @@
+use crate::board::BitBoard;
+use crate::player::Player;
+use rand::Rng;
+
+/// 終盤練習で使う空きマス数の既定値
+pub const DEFAULT_DRILL_EMPTIES: u32 = 10;
+
+/// 完全読みで練習できる空きマス数の上限（これより多いと1手ごとの解析が重い）
+pub const MAX_DRILL_EMPTIES: u32 = 14;
+
+// 条件に合う局面が見つかるまで試す回数
+const MAX_GENERATION_ATTEMPTS: usize = 1000;
+
+/// 人間の1手と、完全読みによる最善手との比較
+#[derive(Debug, Clone, Copy)]
+pub struct DrillMove {
+    pub board: BitBoard, // 着手前の盤面
+    pub played: usize,   // 人間が打った手
+    pub best_move: usize,
+    pub loss: i32, // 最善手と比べて失った最終石差（0なら最善）
+}
+
+/// 練習の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrillResult {
+    pub optimal_diff: i32,  // 開始局面で最善を尽くした場合の人間から見た最終石差
+    pub achieved_diff: i32, // 実際の人間から見た最終石差
+}
+
+impl DrillResult {
+    /// 理論上の最善の結果を出せたかどうか（相手も最善なので上回ることはない）
+    pub fn is_perfect(&self) -> bool {
+        self.achieved_diff >= self.optimal_diff
+    }
+}
+
+/// 完全読みのAIを相手にした終盤の練習
+#[derive(Debug, Clone)]
+pub struct EndgameDrill {
+    pub start: BitBoard,
+    pub human: Player,
+    pub optimal_diff: i32,
+    pub moves: Vec<DrillMove>, // 人間の手の記録
+}
+
+impl EndgameDrill {
+    /// 開始局面と人間の手番から練習を作る（完全読みできない局面なら None）
+    pub fn new(start: BitBoard, human: Player) -> Option<Self> {
+        if start.validate().is_err() || start.count_empty() > MAX_DRILL_EMPTIES {
+            return None;
+        }
+        let mut solver_board = start;
+        let optimal_diff = solver_board.solve_endgame(human, MAX_DRILL_EMPTIES)?;
+
+        Some(Self {
+            start,
+            human,
+            optimal_diff,
+            moves: Vec::new(),
+        })
+    }
+
+    /// 初期配置からランダムに打ち進めて、空きマスが empties の局面で練習を作る
+    /// 途中で終局した場合や、手番側が打てない局面になった場合はやり直す
+    pub fn random(rng: &mut impl Rng, empties: u32) -> Option<Self> {
+        let empties = empties.min(MAX_DRILL_EMPTIES);
+
+        for _ in 0..MAX_GENERATION_ATTEMPTS {
+            let mut board = BitBoard::new();
+            let mut player = Player::Black;
+
+            while board.count_empty() > empties && !board.is_game_over() {
+                let moves = board.get_legal_move_positions(player);
+                if !moves.is_empty() {
+                    board.make_move(moves[rng.gen_range(0..moves.len())], player);
+                }
+                player = player.opponent();
+            }
+
+            if board.count_empty() == empties && board.get_legal_moves(player) != 0 {
+                return Self::new(board, player);
+            }
+        }
+
+        None
+    }
+
+    /// 人間の手を完全読みの最善手と比べて記録し、失った石差を返す（不正な手なら None）
+    pub fn record_human_move(&mut self, board: &BitBoard, played: usize) -> Option<i32> {
+        let played_diff = exact_move_value(board, played, self.human)?;
+        let (best_move, best_diff) = board.solve_endgame_best_move(self.human)?;
+        let loss = (best_diff - played_diff).max(0);
+
+        self.moves.push(DrillMove {
+            board: *board,
+            played,
+            best_move,
+            loss,
+        });
+        Some(loss)
+    }
+
+    /// 終局した盤面から結果を求める
+    pub fn result(&self, final_board: &BitBoard) -> DrillResult {
+        let (black, white) = final_board.count_all_discs();
+        let black_diff = black as i32 - white as i32;
+
+        DrillResult {
+            optimal_diff: self.optimal_diff,
+            achieved_diff: match self.human {
+                Player::Black => black_diff,
+                Player::White => -black_diff,
+            },
+        }
+    }
+
+    /// 最善でなかった人間の手
+    pub fn mistakes(&self) -> impl Iterator<Item = &DrillMove> {
+        self.moves.iter().filter(|m| m.loss > 0)
+    }
+}
+
+/// pos に打った後、両者が最善を尽くした場合の player から見た最終石差
+fn exact_move_value(board: &BitBoard, pos: usize, player: Player) -> Option<i32> {
+    let mut child = board.with_move(pos, player)?;
+    let opponent_diff = child.solve_endgame(player.opponent(), MAX_DRILL_EMPTIES)?;
+    Some(-opponent_diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// 人間の最初の手だけ first_move にして、あとは両者とも完全読みの最善手で終局まで打つ
+    fn play_out(drill: &mut EndgameDrill, first_move: usize) -> BitBoard {
+        let mut board = drill.start;
+        let mut player = drill.human;
+        let mut first = Some(first_move);
+
+        while !board.is_game_over() {
+            if board.is_pass_required(player) {
+                player = player.opponent();
+                continue;
+            }
+            let pos = if player == drill.human {
+                let pos = first
+                    .take()
+                    .unwrap_or_else(|| board.solve_endgame_best_move(player).unwrap().0);
+                drill.record_human_move(&board, pos).unwrap();
+                pos
+            } else {
+                board.solve_endgame_best_move(player).unwrap().0
+            };
+            board.make_move(pos, player);
+            player = player.opponent();
+        }
+        board
+    }
+
+    #[test]
+    fn drill_tells_optimal_from_suboptimal_play() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let template = EndgameDrill::random(&mut rng, 8).unwrap();
+        assert_eq!(template.start.count_empty(), 8);
+
+        // 最善手だけを打てば、理論上の最善の結果になり、失敗も記録されない
+        let (best_move, best_diff) = template
+            .start
+            .solve_endgame_best_move(template.human)
+            .unwrap();
+        assert_eq!(best_diff, template.optimal_diff);
+        let mut drill = template.clone();
+        let final_board = play_out(&mut drill, best_move);
+        let result = drill.result(&final_board);
+        assert_eq!(result.achieved_diff, template.optimal_diff);
+        assert!(result.is_perfect());
+        assert_eq!(drill.mistakes().count(), 0);
+
+        // 最善でない手を1つ打つと、その損失の分だけ結果が悪くなる
+        let (worst_move, loss) = template
+            .start
+            .get_legal_move_positions(template.human)
+            .into_iter()
+            .map(|pos| {
+                let value = exact_move_value(&template.start, pos, template.human).unwrap();
+                (pos, template.optimal_diff - value)
+            })
+            .max_by_key(|&(_, loss)| loss)
+            .unwrap();
+        assert!(loss > 0, "最善でない手がある局面を選ぶ");
+        let mut drill = template.clone();
+        let final_board = play_out(&mut drill, worst_move);
+        let result = drill.result(&final_board);
+        assert_eq!(result.achieved_diff, template.optimal_diff - loss);
+        assert!(!result.is_perfect());
+        let mistakes: Vec<&DrillMove> = drill.mistakes().collect();
+        assert_eq!(mistakes.len(), 1);
+        assert_eq!((mistakes[0].played, mistakes[0].loss), (worst_move, loss));
+    }
+}
@@ -1,21 +1,95 @@
-use crate::board::BitBoard;
+use crate::ai::{
+    estimate_search_time, tt_entries_memory, win_probability, ChunkedSearch, EngineConfig,
+    Personality, SearchStats, BLITZ_LEVEL, BYTES_PER_MB, PERSONALITY_LEVEL, SEARCH_TIME_WARNING,
+    WLD_ANNOUNCE_EMPTIES,
+};
+use crate::board::{
+    is_pass_notation, notation, parse_notation, pos_to_rc, rc_to_pos, split_transcript, BitBoard,
+    GameOverReason, PASS_NOTATION,
+};
+use crate::gui::engine_tuner::EngineTuner;
 use crate::gui::game_view::GameView;
 use crate::gui::plot_viewer::PlotViewer;
-use crate::player::{Player, PlayerType};
-use crate::stats::{GameResult, GameStats};
+use crate::language::Language;
+use crate::player::{Entry, Player, PlayerType};
+use crate::selfplay::fast_forward;
+use crate::stats::{
+    format_duration, DrawAgreement, GameResult, GameStats, MoveReview, REVIEW_DEPTH,
+};
 use eframe::egui;
-use std::cell::RefCell;
+use fxhash::FxHashMap;
+use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum Language {
-    Japanese,
-    English,
+// 評価バーの幅
+const EVAL_BAR_WIDTH: f32 = 20.0;
+
+// AIの思考結果（着手できたか, 着手位置, 評価値, 探索の統計）
+type AiMoveResult = (bool, Option<(usize, usize)>, Option<i32>, SearchStats);
+
+// AIの置換表（思考スレッドに渡し、結果と一緒に返してもらって次の手番でも使う）
+type TranspositionTable = FxHashMap<(u64, u64, u8), Entry>;
+
+// スレッドを使えない環境で、1フレームに探索するノード数
+const CHUNKED_SEARCH_NODES: u64 = 20_000;
+
+// 引き分けの申し出をAIが検討するときの探索深さ
+const DRAW_OFFER_EVAL_DEPTH: usize = 4;
+
+// メニューの設定グループの幅の範囲（狭い画面では画面幅まで縮める）
+const MENU_MIN_WIDTH: f32 = 260.0;
+const MENU_MAX_WIDTH: f32 = 560.0;
+
+// プレイヤー選択のコンボボックスの幅の範囲
+const MENU_COMBO_MIN_WIDTH: f32 = 120.0;
+const MENU_COMBO_MAX_WIDTH: f32 = 260.0;
+
+// メニューの余白の範囲
+const MENU_MIN_SPACING: f32 = 4.0;
+const MENU_MAX_SPACING: f32 = 50.0;
+
+/// 利用できる幅からメニューの（設定グループの幅, コンボボックスの幅）を求める
+/// どちらも利用できる幅を超えない
+fn menu_widths(available_width: f32) -> (f32, f32) {
+    let available_width = available_width.max(0.0);
+    let group_width =
+        (available_width * 0.9).clamp(MENU_MIN_WIDTH.min(available_width), MENU_MAX_WIDTH);
+    let combo_width = (group_width * 0.5)
+        .clamp(MENU_COMBO_MIN_WIDTH, MENU_COMBO_MAX_WIDTH)
+        .min(group_width);
+    (group_width, combo_width)
+}
+
+/// 利用できる高さに対する割合からメニューの余白を求める
+fn menu_spacing(available_height: f32, fraction: f32) -> f32 {
+    (available_height * fraction).clamp(MENU_MIN_SPACING, MENU_MAX_SPACING)
 }
 
+/// 評価バーの黒の部分の割合（0.0〜1.0、黒から見た評価値が無ければ互角の 0.5）
+fn eval_bar_black_fraction(evaluation: Option<i32>) -> f32 {
+    evaluation.map_or(0.5, |eval| win_probability(eval) as f32)
+}
+
+// 合法手の評価値を表示するときの探索深さ
+const MOVE_EVAL_DEPTH: usize = 4;
+
+// 合法手ごとの評価値（良い手から順）
+type MoveEvals = Vec<(usize, i32)>;
+
+// ヒントを求めるときの探索深さと思考時間の上限
+const HINT_DEPTH: usize = 6;
+const HINT_TIME_LIMIT: Duration = Duration::from_millis(500);
+
+// メニューで設定できる1手の思考時間の上限（ミリ秒）
+const MAX_MOVE_TIME_BUDGET_MS: u64 = 30_000;
+
+// 指導モードで応手を予測するときの探索深さ（UIを止めないよう浅くする）
+const TEACHING_PREDICTION_DEPTH: usize = 3;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GameState {
     Menu,
@@ -35,6 +109,10 @@ pub enum PlayerTypeSelection {
     AI11,
     AI13,
     Custom,
+    Aggressive,
+    Positional,
+    Wild,
+    Blitz,
 }
 
 impl PlayerTypeSelection {
@@ -49,48 +127,63 @@ impl PlayerTypeSelection {
             Self::AI11 => "AI レベル11 (超超超上級)",
             Self::AI13 => "AI レベル13 (超超超超上級)",
             Self::Custom => "カスタム",
+            Self::Aggressive => "AI 攻撃型",
+            Self::Positional => "AI 堅実型",
+            Self::Wild => "AI 気まぐれ型",
+            Self::Blitz => "AI ブリッツ",
+        }
+    }
+
+    /// 個性派AIなら対応する個性を返す
+    fn personality(&self) -> Option<Personality> {
+        match self {
+            Self::Aggressive => Some(Personality::Aggressive),
+            Self::Positional => Some(Personality::Positional),
+            Self::Wild => Some(Personality::Wild),
+            _ => None,
         }
     }
 
-    fn to_player_type(&self, custom_depth: usize) -> PlayerType {
+    fn to_player_type(&self, custom_depth: usize, config: EngineConfig) -> PlayerType {
         match self {
             Self::Human => PlayerType::Human,
-            Self::AI1 => PlayerType::AI {
-                level: 1,
-                tt: RefCell::new(HashMap::default()),
-            },
-            Self::AI3 => PlayerType::AI {
-                level: 3,
-                tt: RefCell::new(HashMap::default()),
-            },
-            Self::AI5 => PlayerType::AI {
-                level: 5,
-                tt: RefCell::new(HashMap::default()),
-            },
-            Self::AI7 => PlayerType::AI {
-                level: 7,
-                tt: RefCell::new(HashMap::default()),
-            },
-            Self::AI9 => PlayerType::AI {
-                level: 9,
-                tt: RefCell::new(HashMap::default()),
-            },
-            Self::AI11 => PlayerType::AI {
-                level: 11,
-                tt: RefCell::new(HashMap::default()),
-            },
-            Self::AI13 => PlayerType::AI {
-                level: 13,
-                tt: RefCell::new(HashMap::default()),
-            },
-            Self::Custom => PlayerType::AI {
-                level: custom_depth,
-                tt: RefCell::new(HashMap::default()),
-            },
+            Self::AI1 => PlayerType::new_ai(1, config),
+            Self::AI3 => PlayerType::new_ai(3, config),
+            Self::AI5 => PlayerType::new_ai(5, config),
+            Self::AI7 => PlayerType::new_ai(7, config),
+            Self::AI9 => PlayerType::new_ai(9, config),
+            Self::AI11 => PlayerType::new_ai(11, config),
+            Self::AI13 => PlayerType::new_ai(13, config),
+            Self::Custom => PlayerType::new_ai(custom_depth, config),
+            Self::Aggressive | Self::Positional | Self::Wild => {
+                // 重み付けは個性に従い、完全読みと思考時間の設定は共通のものを使う
+                let personality_config = self
+                    .personality()
+                    .map(|personality| personality.engine_config())
+                    .unwrap_or_default();
+                PlayerType::new_ai(
+                    PERSONALITY_LEVEL,
+                    EngineConfig {
+                        endgame_solve_empties: config.endgame_solve_empties,
+                        move_time_limit: config.move_time_limit,
+                        ..personality_config
+                    },
+                )
+            }
+            Self::Blitz => PlayerType::new_ai(BLITZ_LEVEL, EngineConfig::blitz()),
         }
     }
 }
 
+/// 待ったで戻すための1手分の記録（着手前の盤面・手番・連続パス数と、記録済みの手の数）
+#[derive(Debug, Clone, Copy)]
+struct UndoEntry {
+    board: BitBoard,
+    player: Player,
+    pass_count: usize,
+    recorded_moves: usize,
+}
+
 pub struct OthelloApp {
     state: GameState,
     language: Language,
@@ -100,6 +193,11 @@ pub struct OthelloApp {
     white_player_type: PlayerTypeSelection,
     black_custom_depth: usize,
     white_custom_depth: usize,
+    engine_config: EngineConfig,
+    record_snapshots: bool,
+    teaching_mode: bool,
+    show_move_evals: bool,
+    draw_agreement: DrawAgreement,
 
     // ゲーム状態
     board: BitBoard,
@@ -107,6 +205,10 @@ pub struct OthelloApp {
     black_player: Option<PlayerType>,
     white_player: Option<PlayerType>,
     pass_count: usize,
+    agreed_draw: bool,              // 合意による引き分けで終局した
+    draw_offer_declined: bool,      // 直前の引き分けの申し出が断られた
+    import_warning: Option<String>, // 棋譜の読み込みが途中で止まった理由
+    undo_stack: Vec<UndoEntry>,     // 待ったで戻すための、各手の直前の状態
 
     // 統計
     game_stats: GameStats,
@@ -115,14 +217,25 @@ pub struct OthelloApp {
     // UI状態
     selected_position: Option<(usize, usize)>,
     status_message: String,
+    reply_prediction: Option<(BitBoard, usize, Option<usize>)>, // (盤面, 仮の手, 予測した応手)
+    forced_outcome: Option<(BitBoard, Player, Ordering)>, // 勝敗読み切りの結果（手番側から見た）
 
     // AI思考の非同期処理
     ai_thinking: bool,
-    ai_move_receiver: Option<mpsc::Receiver<(bool, Option<(usize, usize)>, Option<i32>)>>,
+    ai_paused: bool, // AI同士の対局を自動で打ち進めない（「1手進める」で1手ずつ打つ）
+    ai_move_receiver: Option<mpsc::Receiver<(AiMoveResult, TranspositionTable)>>,
+    ai_workers: Vec<(Arc<AtomicBool>, thread::JoinHandle<()>)>, // 思考スレッドと中止の合図
+    chunked_search: Option<ChunkedSearch>,                      // スレッドを使わない探索（wasm用）
+    last_ai_search: Option<(usize, SearchStats)>,               // 直前のAIの指定レベルと探索の統計
+    move_evals: Option<(BitBoard, Player, MoveEvals)>, // 合法手の評価値を計算した盤面・手番と結果
+    move_eval_receiver: Option<(BitBoard, Player, mpsc::Receiver<MoveEvals>)>, // 計算中の盤面・手番
+    hint: Option<(BitBoard, Player, Option<usize>)>,   // ヒントを求めた盤面・手番と勧める手
+    hint_receiver: Option<(BitBoard, Player, mpsc::Receiver<Option<usize>>)>, // 計算中の盤面・手番
 
     // ゲームビューアとプロットビューア
     game_view: GameView,
     plot_viewer: PlotViewer,
+    engine_tuner: EngineTuner,
 
     // グラフ用データ保存
     stored_game_stats: Option<GameStats>,
@@ -131,6 +244,18 @@ pub struct OthelloApp {
     // ウィンドウ管理
     show_stats_window: bool,
     show_plot_window: bool,
+    show_engine_window: bool,
+
+    // 棋譜の読み込み
+    transcript_text: String,
+
+    // 注釈の編集
+    annotation_move: usize,
+    annotation_text: String,
+    review_board: bool,
+
+    // 対局後の手の検討結果
+    move_review: Option<Vec<MoveReview>>,
 }
 
 impl Default for OthelloApp {
@@ -142,23 +267,49 @@ impl Default for OthelloApp {
             white_player_type: PlayerTypeSelection::AI3,
             black_custom_depth: 5,
             white_custom_depth: 5,
+            engine_config: EngineConfig::default(),
+            record_snapshots: false,
+            teaching_mode: false,
+            show_move_evals: false,
+            draw_agreement: DrawAgreement::default(),
             board: BitBoard::new(),
             current_player: Player::Black,
             black_player: None,
             white_player: None,
             pass_count: 0,
+            agreed_draw: false,
+            draw_offer_declined: false,
+            import_warning: None,
+            undo_stack: Vec::new(),
             game_stats: GameStats::new(),
             thinking_time: Duration::new(0, 0),
             selected_position: None,
             status_message: String::new(),
+            reply_prediction: None,
+            forced_outcome: None,
             ai_thinking: false,
+            ai_paused: false,
             ai_move_receiver: None,
+            ai_workers: Vec::new(),
+            chunked_search: None,
+            last_ai_search: None,
+            move_evals: None,
+            move_eval_receiver: None,
+            hint: None,
+            hint_receiver: None,
             game_view: GameView::new(),
             plot_viewer: PlotViewer::new(),
+            engine_tuner: EngineTuner::new(),
             stored_game_stats: None,
             stored_game_result: None,
             show_stats_window: false,
             show_plot_window: false,
+            show_engine_window: false,
+            transcript_text: String::new(),
+            annotation_move: 1,
+            annotation_text: String::new(),
+            review_board: false,
+            move_review: None,
         }
     }
 }
@@ -195,6 +346,16 @@ impl OthelloApp {
             (Language::English, "ai_level13") => "AI Level 13 (Ultimate)".to_string(),
             (Language::Japanese, "custom") => "カスタム".to_string(),
             (Language::English, "custom") => "Custom".to_string(),
+            (Language::Japanese, "ai_aggressive") => "AI 攻撃型 (石数重視)".to_string(),
+            (Language::English, "ai_aggressive") => "AI Aggressive (Disc-Heavy)".to_string(),
+            (Language::Japanese, "ai_positional") => "AI 堅実型 (角・確定石重視)".to_string(),
+            (Language::English, "ai_positional") => {
+                "AI Positional (Corners & Stability)".to_string()
+            }
+            (Language::Japanese, "ai_wild") => "AI 気まぐれ型 (ランダム性大)".to_string(),
+            (Language::English, "ai_wild") => "AI Wild (High Noise)".to_string(),
+            (Language::Japanese, "ai_blitz") => "AI ブリッツ (1手100ms)".to_string(),
+            (Language::English, "ai_blitz") => "AI Blitz (100ms per Move)".to_string(),
 
             // Menu
             (Language::Japanese, "player_settings") => "プレイヤー設定".to_string(),
@@ -207,24 +368,100 @@ impl OthelloApp {
             (Language::Japanese, "white_custom_depth") => "白カスタム深さ: ".to_string(),
             (Language::English, "black_custom_depth") => "Black Custom Depth: ".to_string(),
             (Language::English, "white_custom_depth") => "White Custom Depth: ".to_string(),
+            (Language::Japanese, "endgame_solve_empties") => {
+                "完全読み開始 (空きマス数): ".to_string()
+            }
+            (Language::English, "endgame_solve_empties") => {
+                "Exact Solve From (Empties): ".to_string()
+            }
+            (Language::Japanese, "move_time_budget") => {
+                "1手の思考時間 (ミリ秒、0で深さに応じて自動): ".to_string()
+            }
+            (Language::English, "move_time_budget") => {
+                "Time per Move (ms, 0 = by depth): ".to_string()
+            }
+            (Language::Japanese, "record_snapshots") => "各手の盤面を記録".to_string(),
+            (Language::English, "record_snapshots") => "Record Board Snapshots".to_string(),
+            (Language::Japanese, "teaching_mode") => "指導モード（AIの応手を予測）".to_string(),
+            (Language::English, "teaching_mode") => "Teaching Mode (Predict AI Reply)".to_string(),
+            (Language::Japanese, "show_move_evals") => "全ての合法手の評価値を表示".to_string(),
+            (Language::English, "show_move_evals") => "Show Evals for All Legal Moves".to_string(),
+            (Language::Japanese, "draw_agreement_moves") => {
+                "合意引き分け（評価値が0付近の手が続く数、0で無効）:".to_string()
+            }
+            (Language::English, "draw_agreement_moves") => {
+                "Agreed Draw (consecutive near-zero evals, 0 = off):".to_string()
+            }
+            (Language::Japanese, "draw_eval_threshold") => "0付近とみなす評価値:".to_string(),
+            (Language::English, "draw_eval_threshold") => "Near-zero eval threshold:".to_string(),
+            (Language::Japanese, "offer_draw") => "引き分けを申し出る".to_string(),
+            (Language::English, "offer_draw") => "Offer Draw".to_string(),
+            (Language::Japanese, "fast_forward") => "終局まで早送り".to_string(),
+            (Language::English, "fast_forward") => "Fast-forward to End".to_string(),
+            (Language::Japanese, "pause_ai") => "一時停止".to_string(),
+            (Language::English, "pause_ai") => "Pause".to_string(),
+            (Language::Japanese, "resume_ai") => "再開".to_string(),
+            (Language::English, "resume_ai") => "Resume".to_string(),
+            (Language::Japanese, "step_ai") => "1手進める".to_string(),
+            (Language::English, "step_ai") => "Step".to_string(),
+            (Language::Japanese, "undo") => "待った".to_string(),
+            (Language::English, "undo") => "Undo".to_string(),
+            (Language::Japanese, "hint") => "ヒント".to_string(),
+            (Language::English, "hint") => "Hint".to_string(),
+            (Language::Japanese, "ai_solving") => "AI完全読み中...".to_string(),
+            (Language::English, "ai_solving") => "AI solving endgame...".to_string(),
             (Language::Japanese, "start_game") => "ゲーム開始".to_string(),
             (Language::English, "start_game") => "Start Game".to_string(),
+            (Language::Japanese, "import_transcript") => "棋譜から開始".to_string(),
+            (Language::English, "import_transcript") => "Start from Transcript".to_string(),
+            (Language::Japanese, "start_from_transcript") => "棋譜を読み込んで開始".to_string(),
+            (Language::English, "start_from_transcript") => "Load and Start".to_string(),
             (Language::Japanese, "language") => "言語 / Language".to_string(),
             (Language::English, "language") => "Language / 言語".to_string(),
 
             // Game
             (Language::Japanese, "game_info") => "ゲーム情報".to_string(),
             (Language::English, "game_info") => "Game Info".to_string(),
+            (Language::Japanese, "eval_bar") => "評価".to_string(),
+            (Language::English, "eval_bar") => "Eval".to_string(),
             (Language::Japanese, "ai_thinking") => "AI思考中...".to_string(),
             (Language::English, "ai_thinking") => "AI thinking...".to_string(),
             (Language::Japanese, "return_to_menu") => "メニューに戻る".to_string(),
             (Language::English, "return_to_menu") => "Return to Menu".to_string(),
+            (Language::Japanese, "stop_ai") => "AIの思考を打ち切る".to_string(),
+            (Language::English, "stop_ai") => "Stop AI Thinking".to_string(),
             (Language::Japanese, "show_stats_graphs") => "統計・グラフ表示".to_string(),
             (Language::English, "show_stats_graphs") => "Show Stats & Graphs".to_string(),
             (Language::Japanese, "new_game") => "新しいゲーム".to_string(),
             (Language::English, "new_game") => "New Game".to_string(),
             (Language::Japanese, "stats_window") => "統計ウィンドウ".to_string(),
             (Language::English, "stats_window") => "Statistics Window".to_string(),
+            (Language::Japanese, "engine_tuning") => "エンジン調整".to_string(),
+            (Language::English, "engine_tuning") => "Engine".to_string(),
+
+            // Annotations
+            (Language::Japanese, "annotations") => "注釈".to_string(),
+            (Language::English, "annotations") => "Annotations".to_string(),
+            (Language::Japanese, "annotation_move") => "手数:".to_string(),
+            (Language::English, "annotation_move") => "Move:".to_string(),
+            (Language::Japanese, "review_board") => "この手の盤面を表示".to_string(),
+            (Language::English, "review_board") => "Show Board at This Move".to_string(),
+            (Language::Japanese, "save_annotation") => "注釈を保存".to_string(),
+            (Language::English, "save_annotation") => "Save Note".to_string(),
+
+            // Move review
+            (Language::Japanese, "review_moves") => "人間の手を検討".to_string(),
+            (Language::English, "review_moves") => "Review Human Moves".to_string(),
+            (Language::Japanese, "review_header") => "手数,手番,着手,最善手,損失,評価".to_string(),
+            (Language::English, "review_header") => {
+                "Move,Side,Played,Best,Loss,Verdict".to_string()
+            }
+            (Language::Japanese, "move_history") => "棋譜".to_string(),
+            (Language::English, "move_history") => "Move History".to_string(),
+            (Language::Japanese, "history_header") => "手数,手番,着手,評価値".to_string(),
+            (Language::English, "history_header") => "Move,Side,Played,Eval".to_string(),
+            (Language::Japanese, "no_human_moves") => "検討できる人間の手がありません".to_string(),
+            (Language::English, "no_human_moves") => "No human moves to review".to_string(),
 
             // Statistics
             (Language::Japanese, "game_statistics") => "ゲーム統計".to_string(),
@@ -243,25 +480,56 @@ impl OthelloApp {
         }
     }
 
+    /// 前の対局のプレイヤーが同じ設定ならリセットして使い回し、違えば新しいプレイヤーを使う
+    fn reuse_player(previous: Option<PlayerType>, next: PlayerType) -> PlayerType {
+        match previous {
+            Some(previous) if previous.same_settings(&next) => {
+                previous.reset();
+                previous
+            }
+            _ => next,
+        }
+    }
+
     fn start_new_game(&mut self) {
         self.board = BitBoard::new();
         self.current_player = Player::Black;
         self.pass_count = 0;
+        self.agreed_draw = false;
+        self.draw_offer_declined = false;
+        self.import_warning = None;
+        self.undo_stack.clear();
+        self.hint = None;
+        self.hint_receiver = None;
         self.game_stats = GameStats::new();
+        self.game_stats.set_record_snapshots(self.record_snapshots);
+        self.game_stats.set_players(
+            self.black_player_type.to_string(),
+            self.white_player_type.to_string(),
+        );
         self.thinking_time = Duration::new(0, 0);
         self.selected_position = None;
+        self.reply_prediction = None;
+        self.forced_outcome = None;
         self.ai_thinking = false;
         self.ai_move_receiver = None;
-
-        // プレイヤータイプを設定
-        self.black_player = Some(
-            self.black_player_type
-                .to_player_type(self.black_custom_depth),
-        );
-        self.white_player = Some(
-            self.white_player_type
-                .to_player_type(self.white_custom_depth),
-        );
+        self.cancel_ai_workers(false);
+        self.chunked_search = None;
+        self.last_ai_search = None;
+        self.annotation_move = 1;
+        self.annotation_text.clear();
+        self.review_board = false;
+        self.move_review = None;
+
+        // プレイヤータイプを設定（前の対局と同じ設定なら使い回し、状態だけ初期化する）
+        let black_player = self
+            .black_player_type
+            .to_player_type(self.black_custom_depth, self.engine_config);
+        self.black_player = Some(Self::reuse_player(self.black_player.take(), black_player));
+        let white_player = self
+            .white_player_type
+            .to_player_type(self.white_custom_depth, self.engine_config);
+        self.white_player = Some(Self::reuse_player(self.white_player.take(), white_player));
 
         self.state = GameState::Playing;
         self.status_message = match self.language {
@@ -270,13 +538,72 @@ impl OthelloApp {
         };
     }
 
+    /// 棋譜を読み込んで、その局面から新しいゲームを始める
+    /// 打てない手があれば、そこまでの手を打った局面で始めて警告を出す
+    fn import_transcript(&mut self) {
+        let text = self.transcript_text.clone();
+        let moves = split_transcript(&text);
+
+        // まず盤面だけで検証して、打てる手の数を調べる
+        let mut board = BitBoard::new();
+        let failure = board.apply_moves(&moves, Player::Black).err();
+        let applied = failure.as_ref().map_or(moves.len(), |e| e.index());
+
+        self.start_new_game();
+        for notation in &moves[..applied] {
+            // 棋譜に書かれたパスも、書かれていないパスも同じように記録する
+            if is_pass_notation(notation) {
+                self.pass_turn();
+                continue;
+            }
+            let Some(position) = parse_notation(notation) else {
+                break;
+            };
+            if self.board.is_pass_required(self.current_player) {
+                self.pass_turn();
+            }
+            let (row, col) = pos_to_rc(position);
+            self.handle_human_move(row, col);
+        }
+
+        self.import_warning = failure.map(|e| match self.language {
+            Language::Japanese => format!("棋譜の読み込みを{}手で止めました: {}", applied, e),
+            Language::English => format!(
+                "Transcript import stopped after {} moves: move {} could not be played",
+                applied,
+                applied + 1
+            ),
+        });
+    }
+
+    /// 打てる手が無い手番のプレイヤーのパスを記録して、相手の番にする
+    fn pass_turn(&mut self) {
+        self.push_undo();
+        let (black_count, white_count) = self.board.count_all_discs();
+        self.game_stats.record_move(
+            self.current_player,
+            None,
+            Duration::ZERO,
+            black_count,
+            white_count,
+            None,
+        );
+        self.game_stats.record_mobility(&self.board);
+        self.current_player = self.current_player.opponent();
+        self.pass_count += 1;
+    }
+
     fn handle_human_move(&mut self, row: usize, col: usize) -> bool {
-        let position = row * 8 + col;
+        let position = rc_to_pos(row, col);
         let legal_moves = self.board.get_legal_moves(self.current_player);
 
         if (legal_moves & (1u64 << position)) != 0 {
+            self.hint = None;
+            self.hint_receiver = None;
+            self.push_undo();
             let start = Instant::now();
             if self.board.make_move(position, self.current_player) {
+                self.draw_offer_declined = false;
                 let elapsed = start.elapsed();
                 self.thinking_time += elapsed;
 
@@ -289,6 +616,8 @@ impl OthelloApp {
                     white_count,
                     None,
                 );
+                self.game_stats.record_mobility(&self.board);
+                self.game_stats.record_snapshot(&self.board);
 
                 self.current_player = self.current_player.opponent();
                 self.pass_count = 0;
@@ -308,79 +637,204 @@ impl OthelloApp {
             Player::White => self.white_player.as_ref(),
         };
 
-        if let Some(PlayerType::AI { level, tt: _ }) = player_type {
+        if let Some(PlayerType::AI { level, config, tt }) = player_type {
             self.ai_thinking = true;
             let mut board_copy = self.board.clone();
             let current_player = self.current_player;
             let level = *level;
+            let config = **config;
+
+            // 時間がかかりそうな深さなら警告を表示（時間制限付きなら不要）
+            let empty_count = self.board.count_empty();
+            let estimate = estimate_search_time(level, empty_count);
+            if config.move_time_limit.is_none()
+                && !config.should_solve_exactly(empty_count)
+                && estimate > SEARCH_TIME_WARNING
+            {
+                self.status_message = match self.language {
+                    Language::Japanese => format!(
+                        "⚠ 深度{}の探索は時間がかかる可能性があります（推定{:.1}秒）",
+                        level,
+                        estimate.as_secs_f64()
+                    ),
+                    Language::English => format!(
+                        "⚠ Depth {} may take a long time (est. {:.1}s); consider a lower level or a time cap",
+                        level,
+                        estimate.as_secs_f64()
+                    ),
+                };
+            } else {
+                self.status_message = match self.language {
+                    Language::Japanese => format!("{}の手番です", self.current_player.to_string()),
+                    Language::English => format!("{}'s turn", self.current_player.to_string()),
+                };
+            }
+
+            // 前の手番までの置換表を探索に渡す（キーは Zobrist ハッシュで、深さは使う時に確かめる）
+            let mut tt = std::mem::take(&mut *tt.borrow_mut());
+
+            // wasm ではスレッドを作れないので、フレームごとに少しずつ探索する
+            if cfg!(target_arch = "wasm32") {
+                self.chunked_search = Some(ChunkedSearch::new(
+                    board_copy,
+                    current_player,
+                    level,
+                    config,
+                    tt,
+                ));
+                return;
+            }
 
             let (tx, rx) = mpsc::channel();
             self.ai_move_receiver = Some(rx);
 
-            thread::spawn(move || {
+            // 終わったスレッドは片付けておく
+            self.ai_workers.retain(|(_, handle)| !handle.is_finished());
+            let cancel = Arc::new(AtomicBool::new(false));
+            let worker_cancel = Arc::clone(&cancel);
+
+            let handle = thread::spawn(move || {
                 let start = Instant::now();
-                let mut tt = HashMap::default();
-                let (best_move, evaluation) =
-                    board_copy.find_best_move_with_tt(current_player, level, &mut tt);
+                let (best_move, evaluation, stats) = board_copy.find_best_move_with_cancel(
+                    current_player,
+                    level,
+                    &mut tt,
+                    &config,
+                    &worker_cancel,
+                );
                 let _elapsed = start.elapsed();
 
                 if let Some(position) = best_move {
-                    let row = position / 8;
-                    let col = position % 8;
+                    let (row, col) = pos_to_rc(position);
                     let success = board_copy.make_move(position, current_player);
-                    tx.send((success, Some((row, col)), evaluation)).ok();
+                    tx.send(((success, Some((row, col)), evaluation, stats), tt))
+                        .ok();
                 } else {
-                    tx.send((false, None, evaluation)).ok();
+                    tx.send(((false, None, evaluation, stats), tt)).ok();
                 }
             });
+            self.ai_workers.push((cancel, handle));
         }
     }
 
-    fn check_ai_move(&mut self) {
-        if let Some(ref receiver) = self.ai_move_receiver {
-            if let Ok((success, move_position, evaluation)) = receiver.try_recv() {
-                self.ai_thinking = false;
-                self.ai_move_receiver = None;
+    /// 思考中のAIスレッドに中止を伝える（join が true なら終わるまで待つ）
+    fn cancel_ai_workers(&mut self, join: bool) {
+        for (cancel, _) in &self.ai_workers {
+            cancel.store(true, AtomicOrdering::Relaxed);
+        }
+        if join {
+            for (_, handle) in self.ai_workers.drain(..) {
+                handle.join().ok();
+            }
+        }
+    }
 
-                let start = Instant::now();
+    /// 思考中のAIに中止を伝える（結果は受け取るので、読み終えた深さの最善手を打つ）
+    fn stop_ai_search(&mut self) {
+        if let Some((cancel, _)) = self.ai_workers.last() {
+            cancel.store(true, AtomicOrdering::Relaxed);
+        }
+    }
 
-                if success {
-                    if let Some((row, col)) = move_position {
-                        let position = row * 8 + col;
-                        self.board.make_move(position, self.current_player);
+    /// メニューに戻る（思考中のAIは止めて、結果も捨てる）
+    fn return_to_menu(&mut self) {
+        self.ai_thinking = false;
+        self.ai_move_receiver = None;
+        self.chunked_search = None;
+        self.cancel_ai_workers(false);
+        self.state = GameState::Menu;
+    }
 
-                        let elapsed = start.elapsed();
-                        self.thinking_time += elapsed;
+    /// AIの思考結果があれば受け取る（分割探索ならこのフレームの分だけ進める）
+    fn poll_ai_result(&mut self) -> Option<AiMoveResult> {
+        if let Some(search) = self.chunked_search.as_mut() {
+            let (best_move, evaluation) = search.step(CHUNKED_SEARCH_NODES)?;
+            let stats = search.stats();
+            if let Some(search) = self.chunked_search.take() {
+                self.return_tt_to_current_ai(search.into_tt());
+            }
+            let move_position = best_move
+                .filter(|&pos| self.board.is_legal_move(pos, self.current_player))
+                .map(pos_to_rc);
+            return Some((move_position.is_some(), move_position, evaluation, stats));
+        }
 
-                        let (black_count, white_count) = self.board.count_all_discs();
-                        self.game_stats.record_move(
-                            self.current_player,
-                            Some((row, col)),
-                            elapsed,
-                            black_count,
-                            white_count,
-                            evaluation,
-                        );
+        let (result, tt) = self.ai_move_receiver.as_ref()?.try_recv().ok()?;
+        self.return_tt_to_current_ai(tt);
+        Some(result)
+    }
+
+    /// 置換表は次の手番でも使えるよう、手番のAIに戻す
+    fn return_tt_to_current_ai(&self, tt: TranspositionTable) {
+        let current_ai = match self.current_player {
+            Player::Black => self.black_player.as_ref(),
+            Player::White => self.white_player.as_ref(),
+        };
+        if let Some(PlayerType::AI { tt: player_tt, .. }) = current_ai {
+            *player_tt.borrow_mut() = tt;
+        }
+    }
+
+    fn check_ai_move(&mut self) {
+        if let Some((success, move_position, evaluation, stats)) = self.poll_ai_result() {
+            self.ai_thinking = false;
+            self.ai_move_receiver = None;
+
+            let current_ai = match self.current_player {
+                Player::Black => self.black_player.as_ref(),
+                Player::White => self.white_player.as_ref(),
+            };
+            if let Some(PlayerType::AI { level, .. }) = current_ai {
+                self.last_ai_search = Some((*level, stats));
+            }
+
+            self.push_undo();
+            let start = Instant::now();
+
+            if success {
+                if let Some((row, col)) = move_position {
+                    let position = rc_to_pos(row, col);
+                    self.board.make_move(position, self.current_player);
 
-                        self.current_player = self.current_player.opponent();
-                        self.pass_count = 0;
-                    }
-                } else {
-                    // パス
                     let elapsed = start.elapsed();
+                    self.thinking_time += elapsed;
+
                     let (black_count, white_count) = self.board.count_all_discs();
                     self.game_stats.record_move(
                         self.current_player,
-                        None,
+                        Some((row, col)),
                         elapsed,
                         black_count,
                         white_count,
                         evaluation,
                     );
+                    self.game_stats.record_mobility(&self.board);
+                    // 分割探索（wasm）では時間の内訳を測らない
+                    if stats.timing.compute_time() > Duration::ZERO {
+                        self.game_stats.record_timing(stats.timing);
+                    }
+                    self.game_stats.record_snapshot(&self.board);
 
                     self.current_player = self.current_player.opponent();
-                    self.pass_count += 1;
+                    self.pass_count = 0;
                 }
+            } else {
+                // パス
+                let elapsed = start.elapsed();
+                let (black_count, white_count) = self.board.count_all_discs();
+                self.game_stats.record_move(
+                    self.current_player,
+                    None,
+                    elapsed,
+                    black_count,
+                    white_count,
+                    evaluation,
+                );
+                self.game_stats.record_mobility(&self.board);
+                self.game_stats.record_snapshot(&self.board);
+
+                self.current_player = self.current_player.opponent();
+                self.pass_count += 1;
             }
         }
     }
@@ -412,15 +866,162 @@ impl OthelloApp {
                     format!("Draw! (Black:{} White:{})", black_count, white_count)
                 }
             };
+
+            let reason_text = match (self.board.game_over_reason(), self.language) {
+                (Some(GameOverReason::BoardFull), Language::Japanese) => "盤面が全て埋まりました",
+                (Some(GameOverReason::BoardFull), Language::English) => "the board is full",
+                (Some(GameOverReason::NoLegalMovesEither), Language::Japanese)
+                | (None, Language::Japanese) => "両者とも打てる場所がありません",
+                (Some(GameOverReason::NoLegalMovesEither), Language::English)
+                | (None, Language::English) => "neither player can move",
+            };
+            self.status_message = format!("{} - {}", self.status_message, reason_text);
+        }
+    }
+
+    /// 合意による引き分けで終局する
+    fn end_with_agreed_draw(&mut self) {
+        self.state = GameState::GameOver;
+        self.agreed_draw = true;
+
+        let (black_count, white_count) = self.board.count_all_discs();
+        self.status_message = match self.language {
+            Language::Japanese => {
+                format!("合意により引き分け (黒:{} 白:{})", black_count, white_count)
+            }
+            Language::English => format!(
+                "Draw by agreement (Black:{} White:{})",
+                black_count, white_count
+            ),
+        };
+    }
+
+    /// 黒も白もAIかどうか
+    fn is_ai_vs_ai(&self) -> bool {
+        matches!(self.black_player, Some(PlayerType::AI { .. }))
+            && matches!(self.white_player, Some(PlayerType::AI { .. }))
+    }
+
+    /// 指定したプレイヤーがAIかどうか
+    fn player_is_ai(&self, player: Player) -> bool {
+        match player {
+            Player::Black => matches!(self.black_player, Some(PlayerType::AI { .. })),
+            Player::White => matches!(self.white_player, Some(PlayerType::AI { .. })),
+        }
+    }
+
+    /// 手番のプレイヤーがAIかどうか
+    fn current_player_is_ai(&self) -> bool {
+        self.player_is_ai(self.current_player)
+    }
+
+    /// 着手やパスの直前の状態を待ったの記録に積む
+    fn push_undo(&mut self) {
+        self.undo_stack.push(UndoEntry {
+            board: self.board,
+            player: self.current_player,
+            pass_count: self.pass_count,
+            recorded_moves: self.game_stats.moves.len(),
+        });
+    }
+
+    /// 待ったできるかどうか（AIの思考中や、人間の手番まで戻れない場合はできない）
+    fn can_undo(&self) -> bool {
+        self.state == GameState::Playing
+            && !self.ai_thinking
+            && self
+                .undo_stack
+                .iter()
+                .any(|entry| !self.player_is_ai(entry.player))
+    }
+
+    /// 直前の人間の手（とその後のAIの手やパス）を取り消して、人間の手番に戻す
+    fn undo_move(&mut self) {
+        if !self.can_undo() {
+            return;
+        }
+
+        while let Some(entry) = self.undo_stack.pop() {
+            self.board = entry.board;
+            self.current_player = entry.player;
+            self.pass_count = entry.pass_count;
+            self.game_stats.truncate_moves(entry.recorded_moves);
+            if !self.player_is_ai(entry.player) {
+                break;
+            }
+        }
+
+        self.selected_position = None;
+        self.draw_offer_declined = false;
+        self.import_warning = None;
+    }
+
+    /// 手番のAIに自動で思考を始めさせるかどうか（AI同士の対局を一時停止中なら始めない）
+    fn should_auto_start_ai(&self) -> bool {
+        self.current_player_is_ai() && !(self.ai_paused && self.is_ai_vs_ai())
+    }
+
+    /// 一時停止中のAI同士の対局で、手番のAIに1手だけ打たせる（打ち終えたら一時停止のまま）
+    fn step_ai(&mut self) {
+        if self.state != GameState::Playing
+            || !self.ai_paused
+            || !self.is_ai_vs_ai()
+            || self.ai_thinking
+        {
+            return;
+        }
+        self.start_ai_thinking();
+    }
+
+    /// AI同士の対局を、待ち時間なしで終局まで打ち進める（思考中の探索は捨てる）
+    fn fast_forward(&mut self) {
+        self.ai_thinking = false;
+        self.ai_move_receiver = None;
+        self.chunked_search = None;
+        self.cancel_ai_workers(false);
+
+        let (Some(black), Some(white)) = (&self.black_player, &self.white_player) else {
+            return;
+        };
+
+        if fast_forward(
+            &mut self.board,
+            &mut self.current_player,
+            [black, white],
+            &mut self.game_stats,
+        ) {
+            self.pass_count = 0;
+            self.check_game_over();
+        }
+    }
+
+    /// 人間からの引き分けの申し出を相手のAIが検討する
+    /// AIから見た評価値がしきい値以下（AIが優勢でない）なら受け入れる
+    fn offer_draw(&mut self) {
+        let ai_view = self
+            .board
+            .analyze(self.current_player, DRAW_OFFER_EVAL_DEPTH)
+            .score
+            .map(|score| -score);
+
+        match ai_view {
+            Some(score) if score <= self.draw_agreement.eval_threshold => {
+                self.end_with_agreed_draw();
+            }
+            _ => self.draw_offer_declined = true,
         }
     }
 
     fn generate_and_show_graphs(&mut self) {
         let (black_count, white_count) = self.board.count_all_discs();
-        let winner = self.board.get_winner();
-        let game_result = self
-            .game_stats
-            .finalize_game(winner, black_count, white_count);
+        let game_result = if self.agreed_draw {
+            self.game_stats
+                .finalize_agreed_draw(black_count, white_count)
+        } else {
+            let winner = self.board.get_winner();
+            self.game_stats
+                .finalize_game(winner, black_count, white_count)
+        };
 
         // Store data for plot viewer
         self.stored_game_stats = Some(self.game_stats.clone_for_plotting());
@@ -435,6 +1036,13 @@ impl OthelloApp {
     }
 }
 
+// ウィンドウを閉じたら、思考中のAIスレッドを止めてから終了する
+impl Drop for OthelloApp {
+    fn drop(&mut self) {
+        self.cancel_ai_workers(true);
+    }
+}
+
 impl eframe::App for OthelloApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // AI思考のチェック
@@ -444,16 +1052,17 @@ impl eframe::App for OthelloApp {
 
         // ゲーム状態の更新
         if self.state == GameState::Playing && !self.ai_thinking {
-            self.check_game_over();
+            if self.game_stats.agreed_draw_reached(&self.draw_agreement) {
+                self.end_with_agreed_draw();
+            } else {
+                self.check_game_over();
+            }
 
             if self.state == GameState::Playing {
                 // 現在のプレイヤーがAIで、まだ思考中でない場合は思考開始
-                let is_ai = match self.current_player {
-                    Player::Black => matches!(self.black_player, Some(PlayerType::AI { .. })),
-                    Player::White => matches!(self.white_player, Some(PlayerType::AI { .. })),
-                };
+                let is_ai = self.current_player_is_ai();
 
-                if is_ai {
+                if self.should_auto_start_ai() {
                     self.start_ai_thinking();
                 }
 
@@ -466,8 +1075,7 @@ impl eframe::App for OthelloApp {
                         }
                         Language::English => format!("{} passes", self.current_player.to_string()),
                     };
-                    self.current_player = self.current_player.opponent();
-                    self.pass_count += 1;
+                    self.pass_turn();
                 } else if !is_ai {
                     self.status_message = match self.language {
                         Language::Japanese => {
@@ -498,21 +1106,33 @@ impl eframe::App for OthelloApp {
                     match self.language {
                         Language::Japanese => {
                             ui.label(format!("総手数: {}", move_count));
-                            ui.label(format!("思考時間: {:.2?}", self.thinking_time));
+                            ui.label(format!(
+                                "思考時間: {}",
+                                format_duration(self.thinking_time, self.language)
+                            ));
                             if move_count > 0 {
                                 ui.label(format!(
-                                    "平均思考時間: {:.2?}",
-                                    self.thinking_time / move_count as u32
+                                    "平均思考時間: {}",
+                                    format_duration(
+                                        self.thinking_time / move_count as u32,
+                                        self.language
+                                    )
                                 ));
                             }
                         }
                         Language::English => {
                             ui.label(format!("Total moves: {}", move_count));
-                            ui.label(format!("Thinking time: {:.2?}", self.thinking_time));
+                            ui.label(format!(
+                                "Thinking time: {}",
+                                format_duration(self.thinking_time, self.language)
+                            ));
                             if move_count > 0 {
                                 ui.label(format!(
-                                    "Average thinking time: {:.2?}",
-                                    self.thinking_time / move_count as u32
+                                    "Average thinking time: {}",
+                                    format_duration(
+                                        self.thinking_time / move_count as u32,
+                                        self.language
+                                    )
                                 ));
                             }
                         }
@@ -545,6 +1165,23 @@ impl eframe::App for OthelloApp {
                 });
         }
 
+        // エンジン調整ウィンドウ（適用するまで対局中のAIには影響しない）
+        if self.show_engine_window {
+            let mut show_engine = self.show_engine_window;
+            let mut applied = false;
+            egui::Window::new(Self::t(self.language, "engine_tuning"))
+                .open(&mut show_engine)
+                .show(ctx, |ui| {
+                    applied =
+                        self.engine_tuner
+                            .show(ui, self.language, &self.board, self.current_player);
+                });
+            self.show_engine_window = show_engine;
+            if applied {
+                self.apply_engine_weights();
+            }
+        }
+
         // 必要な時のみ更新を要求
         if self.ai_thinking {
             ctx.request_repaint();
@@ -557,166 +1194,291 @@ impl eframe::App for OthelloApp {
 
 impl OthelloApp {
     fn show_menu(&mut self, ui: &mut egui::Ui) {
-        ui.vertical_centered(|ui| {
-            ui.add_space(50.0);
-            ui.heading(Self::t(self.language, "title"));
-            ui.add_space(20.0);
-
-            // Language selector
-            ui.horizontal(|ui| {
-                ui.label(Self::t(self.language, "language"));
-                if ui.button("日本語").clicked() {
-                    self.language = Language::Japanese;
-                }
-                if ui.button("English").clicked() {
-                    self.language = Language::English;
-                }
-            });
+        // 画面の大きさに合わせて余白と幅を決める（収まらなければスクロールする）
+        let available = ui.available_size();
+        let (group_width, combo_width) = menu_widths(available.x);
+        let space = |fraction: f32| menu_spacing(available.y, fraction);
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(space(0.07));
+                    ui.heading(Self::t(self.language, "title"));
+                    ui.add_space(space(0.03));
+
+                    // Language selector
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(Self::t(self.language, "language"));
+                        if ui.button("日本語").clicked() {
+                            self.language = Language::Japanese;
+                        }
+                        if ui.button("English").clicked() {
+                            self.language = Language::English;
+                        }
+                    });
 
-            ui.add_space(30.0);
+                    ui.add_space(space(0.04));
+
+                    ui.group(|ui| {
+                        ui.set_max_width(group_width);
+                        ui.vertical(|ui| {
+                            ui.label(Self::t(self.language, "player_settings"));
+                            ui.add_space(space(0.015));
+
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(Self::t(self.language, "black_player"));
+                                egui::ComboBox::from_id_source("black_player")
+                                    .width(combo_width)
+                                    .selected_text(Self::get_player_type_text(
+                                        self.language,
+                                        self.black_player_type,
+                                    ))
+                                    .show_ui(ui, |ui| {
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::Human,
+                                            Self::t(self.language, "human"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::AI1,
+                                            Self::t(self.language, "ai_level1"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::AI3,
+                                            Self::t(self.language, "ai_level3"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::AI5,
+                                            Self::t(self.language, "ai_level5"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::AI7,
+                                            Self::t(self.language, "ai_level7"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::AI9,
+                                            Self::t(self.language, "ai_level9"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::AI11,
+                                            Self::t(self.language, "ai_level11"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::AI13,
+                                            Self::t(self.language, "ai_level13"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::Custom,
+                                            Self::t(self.language, "custom"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::Aggressive,
+                                            Self::t(self.language, "ai_aggressive"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::Positional,
+                                            Self::t(self.language, "ai_positional"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::Wild,
+                                            Self::t(self.language, "ai_wild"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.black_player_type,
+                                            PlayerTypeSelection::Blitz,
+                                            Self::t(self.language, "ai_blitz"),
+                                        );
+                                    });
+                            });
 
-            ui.group(|ui| {
-                ui.vertical(|ui| {
-                    ui.label(Self::t(self.language, "player_settings"));
-                    ui.add_space(10.0);
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(Self::t(self.language, "white_player"));
+                                egui::ComboBox::from_id_source("white_player")
+                                    .width(combo_width)
+                                    .selected_text(Self::get_player_type_text(
+                                        self.language,
+                                        self.white_player_type,
+                                    ))
+                                    .show_ui(ui, |ui| {
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::Human,
+                                            Self::t(self.language, "human"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::AI1,
+                                            Self::t(self.language, "ai_level1"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::AI3,
+                                            Self::t(self.language, "ai_level3"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::AI5,
+                                            Self::t(self.language, "ai_level5"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::AI7,
+                                            Self::t(self.language, "ai_level7"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::AI9,
+                                            Self::t(self.language, "ai_level9"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::AI11,
+                                            Self::t(self.language, "ai_level11"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::AI13,
+                                            Self::t(self.language, "ai_level13"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::Custom,
+                                            Self::t(self.language, "custom"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::Aggressive,
+                                            Self::t(self.language, "ai_aggressive"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::Positional,
+                                            Self::t(self.language, "ai_positional"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::Wild,
+                                            Self::t(self.language, "ai_wild"),
+                                        );
+                                        ui.selectable_value(
+                                            &mut self.white_player_type,
+                                            PlayerTypeSelection::Blitz,
+                                            Self::t(self.language, "ai_blitz"),
+                                        );
+                                    });
+                            });
 
-                    ui.horizontal(|ui| {
-                        ui.label(Self::t(self.language, "black_player"));
-                        egui::ComboBox::from_id_source("black_player")
-                            .selected_text(Self::get_player_type_text(
-                                self.language,
-                                self.black_player_type,
-                            ))
-                            .show_ui(ui, |ui| {
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::Human,
-                                    Self::t(self.language, "human"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::AI1,
-                                    Self::t(self.language, "ai_level1"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::AI3,
-                                    Self::t(self.language, "ai_level3"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::AI5,
-                                    Self::t(self.language, "ai_level5"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::AI7,
-                                    Self::t(self.language, "ai_level7"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::AI9,
-                                    Self::t(self.language, "ai_level9"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::AI11,
-                                    Self::t(self.language, "ai_level11"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::AI13,
-                                    Self::t(self.language, "ai_level13"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.black_player_type,
-                                    PlayerTypeSelection::Custom,
-                                    Self::t(self.language, "custom"),
-                                );
+                            if self.black_player_type == PlayerTypeSelection::Custom
+                                || self.white_player_type == PlayerTypeSelection::Custom
+                            {
+                                if self.black_player_type == PlayerTypeSelection::Custom {
+                                    ui.horizontal_wrapped(|ui| {
+                                        ui.label(Self::t(self.language, "black_custom_depth"));
+                                        ui.add(egui::Slider::new(
+                                            &mut self.black_custom_depth,
+                                            1..=15,
+                                        ));
+                                    });
+                                }
+                                if self.white_player_type == PlayerTypeSelection::Custom {
+                                    ui.horizontal_wrapped(|ui| {
+                                        ui.label(Self::t(self.language, "white_custom_depth"));
+                                        ui.add(egui::Slider::new(
+                                            &mut self.white_custom_depth,
+                                            1..=15,
+                                        ));
+                                    });
+                                }
+                            }
+
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(Self::t(self.language, "endgame_solve_empties"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.engine_config.endgame_solve_empties,
+                                    0..=20,
+                                ));
                             });
-                    });
 
-                    ui.horizontal(|ui| {
-                        ui.label(Self::t(self.language, "white_player"));
-                        egui::ComboBox::from_id_source("white_player")
-                            .selected_text(Self::get_player_type_text(
-                                self.language,
-                                self.white_player_type,
-                            ))
-                            .show_ui(ui, |ui| {
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::Human,
-                                    Self::t(self.language, "human"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::AI1,
-                                    Self::t(self.language, "ai_level1"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::AI3,
-                                    Self::t(self.language, "ai_level3"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::AI5,
-                                    Self::t(self.language, "ai_level5"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::AI7,
-                                    Self::t(self.language, "ai_level7"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::AI9,
-                                    Self::t(self.language, "ai_level9"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::AI11,
-                                    Self::t(self.language, "ai_level11"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::AI13,
-                                    Self::t(self.language, "ai_level13"),
-                                );
-                                ui.selectable_value(
-                                    &mut self.white_player_type,
-                                    PlayerTypeSelection::Custom,
-                                    Self::t(self.language, "custom"),
-                                );
+                            // 指定すると深さごとの既定の時間の代わりに使う（ブリッツは固定）
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(Self::t(self.language, "move_time_budget"));
+                                let mut millis = self
+                                    .engine_config
+                                    .move_time_limit
+                                    .map_or(0, |limit| limit.as_millis() as u64);
+                                if ui
+                                    .add(egui::Slider::new(
+                                        &mut millis,
+                                        0..=MAX_MOVE_TIME_BUDGET_MS,
+                                    ))
+                                    .changed()
+                                {
+                                    self.engine_config.move_time_limit =
+                                        (millis > 0).then(|| Duration::from_millis(millis));
+                                }
                             });
-                    });
 
-                    if self.black_player_type == PlayerTypeSelection::Custom
-                        || self.white_player_type == PlayerTypeSelection::Custom
-                    {
-                        if self.black_player_type == PlayerTypeSelection::Custom {
-                            ui.horizontal(|ui| {
-                                ui.label(Self::t(self.language, "black_custom_depth"));
-                                ui.add(egui::Slider::new(&mut self.black_custom_depth, 1..=15));
+                            ui.checkbox(
+                                &mut self.record_snapshots,
+                                Self::t(self.language, "record_snapshots"),
+                            );
+                            ui.checkbox(
+                                &mut self.teaching_mode,
+                                Self::t(self.language, "teaching_mode"),
+                            );
+
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(Self::t(self.language, "draw_agreement_moves"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.draw_agreement.consecutive_moves,
+                                    0..=30,
+                                ));
                             });
-                        }
-                        if self.white_player_type == PlayerTypeSelection::Custom {
-                            ui.horizontal(|ui| {
-                                ui.label(Self::t(self.language, "white_custom_depth"));
-                                ui.add(egui::Slider::new(&mut self.white_custom_depth, 1..=15));
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(Self::t(self.language, "draw_eval_threshold"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.draw_agreement.eval_threshold,
+                                    0..=100,
+                                ));
                             });
+                        });
+                    });
+
+                    ui.add_space(space(0.015));
+
+                    ui.collapsing(Self::t(self.language, "import_transcript"), |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.transcript_text)
+                                .hint_text("f5 d6 c3 d3 c4")
+                                .desired_rows(3),
+                        );
+                        if ui
+                            .button(Self::t(self.language, "start_from_transcript"))
+                            .clicked()
+                        {
+                            self.import_transcript();
                         }
+                    });
+
+                    ui.add_space(space(0.03));
+
+                    if ui.button(Self::t(self.language, "start_game")).clicked() {
+                        self.start_new_game();
                     }
                 });
             });
-
-            ui.add_space(30.0);
-
-            if ui.button(Self::t(self.language, "start_game")).clicked() {
-                self.start_new_game();
-            }
-        });
     }
 
     fn get_player_type_text(language: Language, player_type: PlayerTypeSelection) -> String {
@@ -730,10 +1492,37 @@ impl OthelloApp {
             PlayerTypeSelection::AI11 => Self::t(language, "ai_level11"),
             PlayerTypeSelection::AI13 => Self::t(language, "ai_level13"),
             PlayerTypeSelection::Custom => Self::t(language, "custom"),
+            PlayerTypeSelection::Aggressive => Self::t(language, "ai_aggressive"),
+            PlayerTypeSelection::Positional => Self::t(language, "ai_positional"),
+            PlayerTypeSelection::Wild => Self::t(language, "ai_wild"),
+            PlayerTypeSelection::Blitz => Self::t(language, "ai_blitz"),
         }
     }
 
-    fn show_game(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context) {
+    fn show_game(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        // 終局後の検討中は選択した手の盤面を表示
+        let displayed_board = if self.state == GameState::GameOver && self.review_board {
+            self.game_stats
+                .board_at_move(self.annotation_move)
+                .unwrap_or(self.board)
+        } else {
+            self.board
+        };
+
+        // 合法手の評価値の表示（検討中の盤面には出さない）
+        let move_evals = if self.review_board {
+            Vec::new()
+        } else {
+            self.current_move_evals(ctx)
+        };
+        self.game_view.set_move_evals(move_evals);
+        let hint = if self.review_board {
+            None
+        } else {
+            self.current_hint(ctx)
+        };
+        self.game_view.set_hint(hint);
+
         ui.horizontal(|ui| {
             // ゲームボード
             ui.vertical(|ui| {
@@ -749,16 +1538,23 @@ impl OthelloApp {
                     }
                 };
 
-                if let Some((row, col)) =
-                    self.game_view
-                        .show(&self.board, self.current_player, ui, self.language)
-                {
+                let last_move = self.last_move_position();
+                if let Some((row, col)) = self.game_view.show(
+                    &displayed_board,
+                    self.current_player,
+                    last_move,
+                    ui,
+                    self.language,
+                ) {
                     if self.state == GameState::Playing && !self.ai_thinking && is_human {
                         self.handle_human_move(row, col);
                     }
                 }
             });
 
+            // 評価バー
+            self.show_eval_bar(ui);
+
             ui.separator();
 
             // サイドパネル
@@ -780,10 +1576,90 @@ impl OthelloApp {
                             }
                         }
 
+                        // 盤面の埋まり具合
+                        let fill = self.board.board_fill_fraction();
+                        let fill_text = match self.language {
+                            Language::Japanese => {
+                                format!("進行度: {}/64", black_count + white_count)
+                            }
+                            Language::English => {
+                                format!("Progress: {}/64", black_count + white_count)
+                            }
+                        };
+                        ui.add(
+                            egui::ProgressBar::new(fill)
+                                .desired_width(160.0)
+                                .text(fill_text),
+                        );
+
                         if self.ai_thinking {
-                            ui.label(Self::t(self.language, "ai_thinking"));
+                            let current_ai = match self.current_player {
+                                Player::Black => self.black_player.as_ref(),
+                                Player::White => self.white_player.as_ref(),
+                            };
+                            let solving = match current_ai {
+                                Some(PlayerType::AI { config, .. }) => {
+                                    config.should_solve_exactly(self.board.count_empty())
+                                }
+                                _ => false,
+                            };
+                            if solving {
+                                ui.label(Self::t(self.language, "ai_solving"));
+                            } else {
+                                ui.label(Self::t(self.language, "ai_thinking"));
+                            }
                             ui.spinner();
                         }
+
+                        // 指定レベルと実際に読んだ深さ（時間切れで浅くなることがある）
+                        if let Some((level, stats)) = self.last_ai_search {
+                            ui.label(match self.language {
+                                Language::Japanese => format!(
+                                    "直前のAI: レベル{} / 読んだ深さ{}",
+                                    level, stats.depth_reached
+                                ),
+                                Language::English => format!(
+                                    "Last AI move: level {} / depth reached {}",
+                                    level, stats.depth_reached
+                                ),
+                            });
+                            // 探索ノード数と速度（分割探索では時間を測らないのでノード数だけ）
+                            ui.label(match (self.language, stats.nodes_per_second()) {
+                                (Language::Japanese, Some(nps)) => {
+                                    format!("探索ノード数: {} ({:.0} nps)", stats.nodes, nps)
+                                }
+                                (Language::Japanese, None) => {
+                                    format!("探索ノード数: {}", stats.nodes)
+                                }
+                                (Language::English, Some(nps)) => {
+                                    format!("Nodes searched: {} ({:.0} nps)", stats.nodes, nps)
+                                }
+                                (Language::English, None) => {
+                                    format!("Nodes searched: {}", stats.nodes)
+                                }
+                            });
+                            // 置換表の大きさとメモリ使用量の目安
+                            let tt_mb = tt_entries_memory(stats.tt_entries) as f64 / BYTES_PER_MB;
+                            ui.label(match self.language {
+                                Language::Japanese => {
+                                    format!("置換表: {}件 (約{:.1}MB)", stats.tt_entries, tt_mb)
+                                }
+                                Language::English => format!(
+                                    "Transposition table: {} entries (~{:.1} MB)",
+                                    stats.tt_entries, tt_mb
+                                ),
+                            });
+                        }
+
+                        if let Some(outcome) = self.forced_outcome_text() {
+                            ui.add_space(5.0);
+                            ui.label(outcome);
+                        }
+
+                        if let Some(hint) = self.teaching_hint() {
+                            ui.add_space(5.0);
+                            ui.label(hint);
+                        }
                     });
                 });
 
@@ -793,7 +1669,95 @@ impl OthelloApp {
                     .button(Self::t(self.language, "return_to_menu"))
                     .clicked()
                 {
-                    self.state = GameState::Menu;
+                    self.return_to_menu();
+                }
+
+                // 思考中のAIを打ち切って、それまでに読み終えた深さの最善手を打たせる
+                if self.ai_thinking
+                    && self.chunked_search.is_none()
+                    && ui.button(Self::t(self.language, "stop_ai")).clicked()
+                {
+                    self.stop_ai_search();
+                }
+
+                // 人間の手番で相手がAIなら引き分けを申し出られる
+                let human_vs_ai = match self.current_player {
+                    Player::Black => {
+                        matches!(self.black_player, Some(PlayerType::Human))
+                            && matches!(self.white_player, Some(PlayerType::AI { .. }))
+                    }
+                    Player::White => {
+                        matches!(self.white_player, Some(PlayerType::Human))
+                            && matches!(self.black_player, Some(PlayerType::AI { .. }))
+                    }
+                };
+                if self.state == GameState::Playing
+                    && !self.ai_thinking
+                    && human_vs_ai
+                    && ui.button(Self::t(self.language, "offer_draw")).clicked()
+                {
+                    self.offer_draw();
+                }
+
+                // 人間の手番で打てる手があれば、AIに勧める手を聞ける
+                if self.state == GameState::Playing
+                    && !self.is_ai_vs_ai()
+                    && ui
+                        .add_enabled(
+                            self.can_request_hint(),
+                            egui::Button::new(Self::t(self.language, "hint")),
+                        )
+                        .clicked()
+                {
+                    self.request_hint();
+                }
+
+                // 人間の手番に戻せるなら待ったできる（AIの思考中はできない）
+                if self.state == GameState::Playing
+                    && !self.is_ai_vs_ai()
+                    && ui
+                        .add_enabled(
+                            self.can_undo(),
+                            egui::Button::new(Self::t(self.language, "undo")),
+                        )
+                        .clicked()
+                {
+                    self.undo_move();
+                }
+
+                // AI同士の対局なら終局まで一気に打ち進めたり、一時停止して1手ずつ進めたりできる
+                if self.state == GameState::Playing && self.is_ai_vs_ai() {
+                    ui.horizontal(|ui| {
+                        if ui.button(Self::t(self.language, "fast_forward")).clicked() {
+                            self.fast_forward();
+                        }
+                        let pause_key = if self.ai_paused {
+                            "resume_ai"
+                        } else {
+                            "pause_ai"
+                        };
+                        if ui.button(Self::t(self.language, pause_key)).clicked() {
+                            self.ai_paused = !self.ai_paused;
+                        }
+                        if ui
+                            .add_enabled(
+                                self.ai_paused && !self.ai_thinking,
+                                egui::Button::new(Self::t(self.language, "step_ai")),
+                            )
+                            .clicked()
+                        {
+                            self.step_ai();
+                        }
+                    });
+                }
+                if let Some(warning) = &self.import_warning {
+                    ui.colored_label(egui::Color32::from_rgb(200, 120, 0), warning);
+                }
+                if self.draw_offer_declined {
+                    ui.label(match self.language {
+                        Language::Japanese => "AIは引き分けの申し出を断りました",
+                        Language::English => "The AI declined the draw offer",
+                    });
                 }
 
                 if self.state == GameState::GameOver {
@@ -808,15 +1772,480 @@ impl OthelloApp {
                     if ui.button(Self::t(self.language, "new_game")).clicked() {
                         self.start_new_game();
                     }
+
+                    ui.add_space(10.0);
+                    self.show_annotation_editor(ui);
+
+                    ui.add_space(10.0);
+                    self.show_move_review(ui);
                 }
 
+                ui.checkbox(
+                    &mut self.show_move_evals,
+                    Self::t(self.language, "show_move_evals"),
+                );
+
                 if ui.button(Self::t(self.language, "stats_window")).clicked() {
                     self.show_stats_window = true;
                 }
+
+                if ui.button(Self::t(self.language, "engine_tuning")).clicked() {
+                    self.show_engine_window = true;
+                }
+            });
+
+            ui.separator();
+
+            // 棋譜パネル
+            self.show_move_history(ui);
+        });
+    }
+
+    /// 直前のAIの評価値を黒から見た値で返す（終局後の検討中は選択した手までで最も新しいもの）
+    fn latest_evaluation(&self) -> Option<i32> {
+        let reviewing = self.state == GameState::GameOver && self.review_board;
+        self.game_stats
+            .moves
+            .iter()
+            .rev()
+            .filter(|m| !reviewing || m.move_number <= self.annotation_move)
+            .find_map(|m| m.normalized_evaluation())
+    }
+
+    /// 盤面に印を付ける直前の手のマス（終局後の検討中は選択した手）
+    fn last_move_position(&self) -> Option<(usize, usize)> {
+        let reviewing = self.state == GameState::GameOver && self.review_board;
+        self.game_stats
+            .moves
+            .iter()
+            .rev()
+            .filter(|m| !reviewing || m.move_number == self.annotation_move)
+            .find_map(|m| m.position)
+    }
+
+    /// これまでの手の一覧（AIの手には評価値も表示する）
+    /// 終局後に行をクリックすると、その手の後の盤面を表示する
+    fn show_move_history(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            ui.label(Self::t(self.language, "move_history"));
+            ui.add_space(5.0);
+
+            let reviewing = self.state == GameState::GameOver;
+            let mut clicked = None;
+            egui::ScrollArea::vertical()
+                .id_source("move_history")
+                .max_height(400.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    egui::Grid::new("move_history_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in Self::t(self.language, "history_header").split(',') {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+
+                            for record in &self.game_stats.moves {
+                                let selected = reviewing
+                                    && self.review_board
+                                    && record.position.is_some()
+                                    && record.move_number == self.annotation_move;
+                                let played = record
+                                    .position
+                                    .map_or(PASS_NOTATION.to_string(), |(row, col)| {
+                                        notation(rc_to_pos(row, col))
+                                    });
+                                if ui
+                                    .selectable_label(selected, record.move_number.to_string())
+                                    .clicked()
+                                    && record.position.is_some()
+                                {
+                                    clicked = Some(record.move_number);
+                                }
+                                ui.label(record.player.to_char().to_string());
+                                ui.label(played);
+                                ui.label(
+                                    record
+                                        .evaluation
+                                        .map_or(String::new(), |eval| eval.to_string()),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            if let Some(move_number) = clicked.filter(|_| reviewing) {
+                self.annotation_move = move_number;
+                self.review_board = true;
+            }
+        });
+    }
+
+    /// ヒントを求められるかどうか（人間の手番で打てる手があり、まだ求めていない場合）
+    fn can_request_hint(&self) -> bool {
+        let requested = |board: &BitBoard, player: Player| {
+            player == self.current_player && *board == self.board
+        };
+        self.state == GameState::Playing
+            && !self.ai_thinking
+            && !self.current_player_is_ai()
+            && !self.board.is_pass_required(self.current_player)
+            && !matches!(&self.hint, Some((board, player, _)) if requested(board, *player))
+            && !matches!(&self.hint_receiver, Some((board, player, _)) if requested(board, *player))
+    }
+
+    /// 現在の局面で勧める手を別スレッドで探索する
+    fn request_hint(&mut self) {
+        if !self.can_request_hint() {
+            return;
+        }
+
+        let mut board = self.board;
+        let player = self.current_player;
+        if cfg!(target_arch = "wasm32") {
+            // スレッドを使えない環境ではその場で探索する
+            let (best_move, _) = board.find_best_move_with_tt(
+                player,
+                HINT_DEPTH,
+                &mut HashMap::default(),
+                HINT_TIME_LIMIT,
+            );
+            self.hint = Some((self.board, player, best_move));
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.hint_receiver = Some((board, player, rx));
+        thread::spawn(move || {
+            let (best_move, _) = board.find_best_move_with_tt(
+                player,
+                HINT_DEPTH,
+                &mut HashMap::default(),
+                HINT_TIME_LIMIT,
+            );
+            tx.send(best_move).ok();
+        });
+    }
+
+    /// 現在の局面のヒントで勧める手（求めていない、または計算中なら None）
+    fn current_hint(&mut self, ctx: &egui::Context) -> Option<usize> {
+        // 計算が終わっていれば受け取る
+        if let Some((board, player, receiver)) = &self.hint_receiver {
+            match receiver.try_recv() {
+                Ok(best_move) => {
+                    self.hint = Some((*board, *player, best_move));
+                    self.hint_receiver = None;
+                }
+                Err(_) => ctx.request_repaint(),
+            }
+        }
+
+        match &self.hint {
+            Some((board, player, best_move))
+                if *player == self.current_player && *board == self.board =>
+            {
+                *best_move
+            }
+            _ => None,
+        }
+    }
+
+    /// エンジン調整で決めた重みを、対局中のAIとこれから始めるゲームの設定に反映する
+    fn apply_engine_weights(&mut self) {
+        self.engine_config = self.engine_tuner.apply_to(&self.engine_config);
+        for player in [&mut self.black_player, &mut self.white_player] {
+            if let Some(PlayerType::AI { config, .. }) = player {
+                **config = self.engine_tuner.apply_to(config);
+            }
+        }
+
+        self.status_message = match self.language {
+            Language::Japanese => "評価の重みをAIに適用しました".to_string(),
+            Language::English => "Applied the evaluation weights to the AI".to_string(),
+        };
+    }
+
+    /// 現在の局面の合法手ごとの評価値（表示が無効、AIの手番、または計算中なら空）
+    /// 計算は別スレッドで行い、終わるまでは入力を止めない
+    fn current_move_evals(&mut self, ctx: &egui::Context) -> MoveEvals {
+        if !self.show_move_evals || self.state != GameState::Playing || self.current_player_is_ai()
+        {
+            return Vec::new();
+        }
+
+        let is_current = |board: &BitBoard, player: Player| {
+            player == self.current_player && *board == self.board
+        };
+
+        // 計算が終わっていれば受け取る
+        if let Some((board, player, receiver)) = &self.move_eval_receiver {
+            if let Ok(evals) = receiver.try_recv() {
+                self.move_evals = Some((*board, *player, evals));
+                self.move_eval_receiver = None;
+            }
+        }
+
+        if let Some((board, player, evals)) = &self.move_evals {
+            if is_current(board, *player) {
+                return evals.clone();
+            }
+        }
+
+        // 局面が変わっていたら計算し直す
+        let pending = matches!(
+            &self.move_eval_receiver,
+            Some((board, player, _)) if is_current(board, *player)
+        );
+        if !pending {
+            let board = self.board;
+            let player = self.current_player;
+            if cfg!(target_arch = "wasm32") {
+                // スレッドを使えない環境では浅い探索をその場で行う
+                let evals = board.analyze_multipv(player, MOVE_EVAL_DEPTH);
+                self.move_evals = Some((board, player, evals.clone()));
+                return evals;
+            }
+
+            let (tx, rx) = mpsc::channel();
+            self.move_eval_receiver = Some((board, player, rx));
+            thread::spawn(move || {
+                tx.send(board.analyze_multipv(player, MOVE_EVAL_DEPTH)).ok();
+            });
+        }
+
+        ctx.request_repaint();
+        Vec::new()
+    }
+
+    /// 盤面の横に直前のAIの評価値を評価バーで表示（黒が有利なほど黒い部分が伸びる）
+    fn show_eval_bar(&self, ui: &mut egui::Ui) {
+        let evaluation = self.latest_evaluation();
+        let black_fraction = eval_bar_black_fraction(evaluation);
+
+        ui.vertical(|ui| {
+            ui.label(Self::t(self.language, "eval_bar"));
+
+            let bar_size = egui::Vec2::new(EVAL_BAR_WIDTH, self.game_view.board_size());
+            let (rect, response) = ui.allocate_exact_size(bar_size, egui::Sense::hover());
+            let painter = ui.painter();
+
+            // 白を背景に、下から黒の割合だけ塗る
+            painter.rect_filled(rect, 2.0, egui::Color32::WHITE);
+            let black_rect = egui::Rect::from_min_max(
+                egui::Pos2::new(rect.min.x, rect.max.y - rect.height() * black_fraction),
+                rect.max,
+            );
+            painter.rect_filled(black_rect, 2.0, egui::Color32::BLACK);
+            painter.rect_stroke(rect, 2.0, egui::Stroke::new(1.0, egui::Color32::GRAY));
+
+            response.on_hover_text(match (self.language, evaluation) {
+                (Language::Japanese, Some(eval)) => format!(
+                    "黒の勝率(目安): {:.0}% (評価値 {})",
+                    black_fraction * 100.0,
+                    eval
+                ),
+                (Language::English, Some(eval)) => format!(
+                    "Black win chance (est.): {:.0}% (eval {})",
+                    black_fraction * 100.0,
+                    eval
+                ),
+                (Language::Japanese, None) => "AIの評価値はまだありません".to_string(),
+                (Language::English, None) => "No AI evaluation yet".to_string(),
+            });
+        });
+    }
+
+    /// 終盤で勝敗を読み切れたら「必勝」の表示を返す
+    fn forced_outcome_text(&mut self) -> Option<String> {
+        if self.state != GameState::Playing
+            || self.board.count_empty() > WLD_ANNOUNCE_EMPTIES
+            || self.board.is_game_over()
+        {
+            return None;
+        }
+
+        // 同じ局面の結果は使い回す
+        let outcome = match self.forced_outcome {
+            Some((board, player, outcome))
+                if player == self.current_player && board == self.board =>
+            {
+                outcome
+            }
+            _ => {
+                let outcome = self.board.solve_wld(self.current_player);
+                self.forced_outcome = Some((self.board, self.current_player, outcome));
+                outcome
+            }
+        };
+
+        let winner = match outcome {
+            Ordering::Greater => Some(self.current_player),
+            Ordering::Less => Some(self.current_player.opponent()),
+            Ordering::Equal => None,
+        };
+
+        Some(match (self.language, winner) {
+            (Language::Japanese, Some(winner)) => format!("★ {}の必勝", winner.to_string()),
+            (Language::English, Some(Player::Black)) => "★ Forced win for Black".to_string(),
+            (Language::English, Some(Player::White)) => "★ Forced win for White".to_string(),
+            (Language::Japanese, None) => "★ 最善で引き分け".to_string(),
+            (Language::English, None) => "★ Draw with best play".to_string(),
+        })
+    }
+
+    /// 指導モードで、マウスを乗せた手に対するAIの応手予測を文章にする
+    fn teaching_hint(&mut self) -> Option<String> {
+        if !self.teaching_mode || self.state != GameState::Playing || self.ai_thinking {
+            return None;
+        }
+
+        let opponent_is_ai = match self.current_player.opponent() {
+            Player::Black => matches!(self.black_player, Some(PlayerType::AI { .. })),
+            Player::White => matches!(self.white_player, Some(PlayerType::AI { .. })),
+        };
+        if !opponent_is_ai {
+            return None;
+        }
+
+        let (row, col) = self.game_view.hovered_cell()?;
+        let position = rc_to_pos(row, col);
+        if !self.board.is_legal_move(position, self.current_player) {
+            return None;
+        }
+
+        // 同じ盤面・同じ手の予測は使い回す
+        let reply = match self.reply_prediction {
+            Some((board, pos, reply)) if pos == position && board == self.board => reply,
+            _ => {
+                let reply = self.board.predict_opponent_reply(
+                    position,
+                    self.current_player.opponent(),
+                    TEACHING_PREDICTION_DEPTH,
+                );
+                self.reply_prediction = Some((self.board, position, reply));
+                reply
+            }
+        };
+
+        Some(match (self.language, reply.map(pos_to_rc)) {
+            (Language::Japanese, Some((reply_row, reply_col))) => format!(
+                "({},{})に打つと、AIは({},{})に打ちそうです",
+                row, col, reply_row, reply_col
+            ),
+            (Language::English, Some((reply_row, reply_col))) => format!(
+                "If you play ({},{}), the AI will likely respond at ({},{})",
+                row, col, reply_row, reply_col
+            ),
+            (Language::Japanese, None) => format!("({},{})に打つと、AIはパスになります", row, col),
+            (Language::English, None) => {
+                format!("If you play ({},{}), the AI will have to pass", row, col)
+            }
+        })
+    }
+
+    fn show_annotation_editor(&mut self, ui: &mut egui::Ui) {
+        let move_count = self.game_stats.get_move_count();
+        if move_count == 0 {
+            return;
+        }
+
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.label(Self::t(self.language, "annotations"));
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(Self::t(self.language, "annotation_move"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.annotation_move).clamp_range(1..=move_count),
+                    );
+                });
+                ui.checkbox(
+                    &mut self.review_board,
+                    Self::t(self.language, "review_board"),
+                );
+                ui.text_edit_singleline(&mut self.annotation_text);
+
+                if ui
+                    .button(Self::t(self.language, "save_annotation"))
+                    .clicked()
+                {
+                    // 空の注釈は削除として扱う
+                    let annotation = Some(self.annotation_text.trim().to_string());
+                    self.game_stats
+                        .annotate_move(self.annotation_move, annotation.clone());
+                    if let Some(stats) = self.stored_game_stats.as_mut() {
+                        stats.annotate_move(self.annotation_move, annotation);
+                    }
+                    self.annotation_text.clear();
+                }
+
+                for record in self.game_stats.get_annotated_moves() {
+                    if let Some(note) = &record.annotation {
+                        let label = match self.language {
+                            Language::Japanese => {
+                                format!("📝 {}手目: {}", record.move_number, note)
+                            }
+                            Language::English => {
+                                format!("📝 Move {}: {}", record.move_number, note)
+                            }
+                        };
+                        ui.label(label);
+                    }
+                }
             });
         });
     }
 
+    /// 人間の手をエンジンの最善手と比べた表
+    fn show_move_review(&mut self, ui: &mut egui::Ui) {
+        if ui.button(Self::t(self.language, "review_moves")).clicked() {
+            let human_players: Vec<Player> = Player::all()
+                .into_iter()
+                .filter(|player| {
+                    let player_type = match player {
+                        Player::Black => &self.black_player,
+                        Player::White => &self.white_player,
+                    };
+                    matches!(player_type, Some(PlayerType::Human))
+                })
+                .collect();
+            self.move_review = Some(self.game_stats.review_moves(&human_players, REVIEW_DEPTH));
+        }
+
+        let Some(reviews) = &self.move_review else {
+            return;
+        };
+        if reviews.is_empty() {
+            ui.label(Self::t(self.language, "no_human_moves"));
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_source("move_review")
+            .max_height(240.0)
+            .show(ui, |ui| {
+                egui::Grid::new("move_review_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in Self::t(self.language, "review_header").split(',') {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for review in reviews {
+                            ui.label(review.move_number.to_string());
+                            ui.label(review.player.to_char().to_string());
+                            ui.label(review.played_notation());
+                            ui.label(review.best_notation());
+                            ui.label(review.eval_loss.to_string());
+                            ui.label(review.quality.label(self.language));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
     fn show_stats(&mut self, ui: &mut egui::Ui) {
         match self.language {
             Language::Japanese => ui.label("統計表示（開発中）"),
@@ -836,3 +2265,103 @@ impl OthelloApp {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_bar_fraction_follows_black_advantage() {
+        assert_eq!(eval_bar_black_fraction(None), 0.5);
+        assert_eq!(eval_bar_black_fraction(Some(0)), 0.5);
+
+        let evals = [-20000, -1000, -300, -50, 0, 50, 300, 1000, 20000];
+        let fractions: Vec<f32> = evals
+            .iter()
+            .map(|&eval| eval_bar_black_fraction(Some(eval)))
+            .collect();
+        assert!(fractions
+            .iter()
+            .all(|fraction| (0.0..=1.0).contains(fraction)));
+        assert!(fractions.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(fractions[3] < 0.5 && fractions[5] > 0.5);
+        // 黒と白を入れ替えると、バーも反対側から同じだけ伸びる
+        for eval in evals {
+            let sum = eval_bar_black_fraction(Some(eval)) + eval_bar_black_fraction(Some(-eval));
+            assert!((sum - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn menu_widths_stay_within_available_width() {
+        for available in [0.0, 100.0, 200.0, 260.0, 320.0, 480.0, 800.0, 1920.0] {
+            let (group, combo) = menu_widths(available);
+            assert!(group <= available && combo <= group, "幅 {}", available);
+            assert!(group <= MENU_MAX_WIDTH && combo <= MENU_COMBO_MAX_WIDTH);
+        }
+        // 狭い画面では画面幅いっぱいまで使い、広い画面では上限で止まる
+        assert_eq!(menu_widths(200.0).0, 200.0);
+        assert_eq!(menu_widths(1920.0), (MENU_MAX_WIDTH, MENU_COMBO_MAX_WIDTH));
+        assert_eq!(menu_widths(-10.0), (0.0, 0.0));
+
+        assert_eq!(menu_spacing(100.0, 0.01), MENU_MIN_SPACING);
+        assert_eq!(menu_spacing(2000.0, 0.1), MENU_MAX_SPACING);
+    }
+
+    #[test]
+    fn step_plays_exactly_one_ai_move_and_stays_paused() {
+        let mut app = OthelloApp::default();
+        app.black_player_type = PlayerTypeSelection::AI1;
+        app.white_player_type = PlayerTypeSelection::AI1;
+        app.ai_paused = true;
+        app.start_new_game();
+        assert!(!app.should_auto_start_ai());
+
+        app.step_ai();
+        assert!(app.ai_thinking);
+        // 思考中にもう一度押しても2手目は始まらない
+        app.step_ai();
+
+        let start = Instant::now();
+        while app.ai_thinking {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(5));
+            app.check_ai_move();
+        }
+
+        assert_eq!(app.game_stats.moves.len(), 1);
+        assert_eq!(app.current_player, Player::White);
+        assert_eq!(app.board.count_empty(), 59);
+        assert!(app.ai_paused && !app.should_auto_start_ai());
+
+        // 一時停止していなければ「1手進める」は何もしない
+        app.ai_paused = false;
+        app.step_ai();
+        assert!(!app.ai_thinking);
+    }
+
+    #[test]
+    fn import_stops_at_first_illegal_move() {
+        let mut app = OthelloApp::default();
+        app.white_player_type = PlayerTypeSelection::Human;
+        // 4手目の d5 は既に石がある場所なので打てない
+        app.transcript_text = "f5d6c3d5c4f4".to_string();
+        app.import_transcript();
+
+        assert_eq!(app.game_stats.moves.len(), 3);
+        assert_eq!(app.game_stats.moves_as_notation(), ["f5", "d6", "c3"]);
+        assert_eq!(app.current_player, Player::White);
+        let mut expected = BitBoard::new();
+        expected
+            .apply_moves(&["f5", "d6", "c3"], Player::Black)
+            .unwrap();
+        assert_eq!(app.board, expected);
+        assert!(app.import_warning.as_ref().unwrap().contains('3'));
+
+        // 最後まで打てる棋譜なら警告は出ない
+        app.transcript_text = "f5d6c3".to_string();
+        app.import_transcript();
+        assert_eq!(app.game_stats.moves.len(), 3);
+        assert!(app.import_warning.is_none());
+    }
+}
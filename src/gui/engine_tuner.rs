@@ -0,0 +1,179 @@
+use crate::ai::{Analysis, EngineConfig, EvalParams};
+use crate::board::{pos_to_rc, BitBoard};
+use crate::language::Language;
+use crate::player::Player;
+use eframe::egui;
+
+// 調整中の重みで局面を解析するときの探索深さ（スライダー操作のたびに読むので浅くする）
+const TUNING_ANALYSIS_DEPTH: usize = 4;
+
+/// 評価の重みを調整して、その場で局面を解析し直すパネル
+pub struct EngineTuner {
+    weights: EvalParams,
+    analysis: Option<(BitBoard, Player, EvalParams, Analysis)>, // 解析した盤面・手番・重みと結果
+}
+
+impl Default for EngineTuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EngineTuner {
+    pub fn new() -> Self {
+        Self {
+            weights: EvalParams::default(),
+            analysis: None,
+        }
+    }
+
+    /// config の評価の重みだけを調整中の重みに置き換えた設定
+    pub fn apply_to(&self, config: &EngineConfig) -> EngineConfig {
+        EngineConfig {
+            eval: self.weights,
+            ..*config
+        }
+    }
+
+    /// 現在の重みでの解析結果（盤面・手番・重みが変わったときだけ読み直す）
+    fn analyze(&mut self, board: &BitBoard, player: Player) -> Analysis {
+        match self.analysis {
+            Some((analyzed, analyzed_player, weights, analysis))
+                if analyzed_player == player && analyzed == *board && weights == self.weights =>
+            {
+                analysis
+            }
+            _ => {
+                let config = self.apply_to(&EngineConfig::default());
+                let analysis = board.analyze_with_config(player, TUNING_ANALYSIS_DEPTH, &config);
+                self.analysis = Some((*board, player, self.weights, analysis));
+                analysis
+            }
+        }
+    }
+
+    /// 序盤・中盤・終盤の3つの重みのスライダー
+    fn phase_sliders(ui: &mut egui::Ui, label: &str, values: &mut [i32; 3], max: i32) {
+        ui.label(label);
+        for (value, phase) in values.iter_mut().zip(["1", "2", "3"]) {
+            ui.add(egui::Slider::new(value, 0..=max).text(phase));
+        }
+    }
+
+    /// パネルを表示する。「適用」が押されたら true を返す
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        language: Language,
+        board: &BitBoard,
+        player: Player,
+    ) -> bool {
+        let (mobility, disc_diff, disc_ramp, corner, pass, frontier, stability) = match language {
+            Language::Japanese => (
+                "モビリティ (序盤/中盤/終盤)",
+                "石数差 (序盤/中盤/終盤)",
+                "終盤の石数差の重みを少しずつ上げる",
+                "角",
+                "パスさせたボーナス",
+                "フロンティア",
+                "確定石",
+            ),
+            Language::English => (
+                "Mobility (early/mid/end)",
+                "Disc difference (early/mid/end)",
+                "Ramp the endgame disc weight as the board fills",
+                "Corner",
+                "Pass bonus",
+                "Frontier",
+                "Stability",
+            ),
+        };
+
+        Self::phase_sliders(ui, mobility, &mut self.weights.mobility_weight, 100);
+        Self::phase_sliders(ui, disc_diff, &mut self.weights.disc_diff_weight, 2000);
+        ui.checkbox(&mut self.weights.disc_weight_ramp, disc_ramp);
+        ui.add(egui::Slider::new(&mut self.weights.corner_weight, 0..=1000).text(corner));
+        ui.add(egui::Slider::new(&mut self.weights.pass_bonus, 0..=200).text(pass));
+        ui.add(egui::Slider::new(&mut self.weights.frontier_weight, 0..=50).text(frontier));
+        ui.add(egui::Slider::new(&mut self.weights.stability_weight, 0..=20).text(stability));
+
+        ui.separator();
+
+        // 調整中の重みでの最善手と評価値
+        let analysis = self.analyze(board, player);
+        ui.label(match (language, analysis.best_move.map(pos_to_rc)) {
+            (Language::Japanese, Some((row, col))) => format!(
+                "{}の最善手: ({},{}) 評価値: {:?}",
+                player.to_string(),
+                row,
+                col,
+                analysis.score
+            ),
+            (Language::English, Some((row, col))) => format!(
+                "Best move for {}: ({},{}) eval: {:?}",
+                player.to_char(),
+                row,
+                col,
+                analysis.score
+            ),
+            (Language::Japanese, None) => format!("{}は打てる手がありません", player.to_string()),
+            (Language::English, None) => format!("{} has no legal move", player.to_char()),
+        });
+
+        ui.separator();
+
+        let mut applied = false;
+        ui.horizontal(|ui| {
+            if ui
+                .button(match language {
+                    Language::Japanese => "対局中のAIに適用",
+                    Language::English => "Apply to AI players",
+                })
+                .clicked()
+            {
+                applied = true;
+            }
+            if ui
+                .button(match language {
+                    Language::Japanese => "初期設定に戻す",
+                    Language::English => "Reset",
+                })
+                .clicked()
+            {
+                self.weights = EvalParams::default();
+            }
+        });
+
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slider_values_map_into_engine_config() {
+        let mut tuner = EngineTuner::new();
+        tuner.weights.mobility_weight = [40, 30, 20];
+        tuner.weights.disc_diff_weight = [1, 2, 3];
+        tuner.weights.disc_weight_ramp = false;
+        tuner.weights.corner_weight = 777;
+        tuner.weights.pass_bonus = 55;
+        tuner.weights.frontier_weight = 12;
+        tuner.weights.stability_weight = 4;
+
+        // 評価の重みだけが置き換わり、時間制限などの設定はそのまま残る
+        let blitz = EngineConfig::blitz();
+        let applied = tuner.apply_to(&blitz);
+        assert_eq!(applied.eval, tuner.weights);
+        assert_eq!(applied.eval.corner_weight, 777);
+        assert_eq!(
+            EngineConfig {
+                eval: blitz.eval,
+                ..applied
+            },
+            blitz
+        );
+    }
+}
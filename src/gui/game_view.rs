@@ -1,25 +1,272 @@
-use crate::board::BitBoard;
-use crate::gui::app::Language;
+use crate::board::{pos_to_rc, BitBoard};
+use crate::language::Language;
 use crate::player::Player;
 use eframe::egui;
 
+// 合法手の印の色
+const LEGAL_MOVE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 215, 0);
+
+// 直前に打たれた石の印の色
+const LAST_MOVE_COLOR: egui::Color32 = egui::Color32::from_rgb(220, 40, 40);
+
+// ヒントで勧める手の枠の色
+const HINT_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 200, 255);
+
+/// 合法手の印の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegalMoveMarker {
+    Ring,      // 金色の輪と中央の点
+    Dot,       // 中央の点だけ
+    Highlight, // マス全体を薄く塗る
+    Hidden,    // 表示しない
+}
+
+impl LegalMoveMarker {
+    pub const ALL: [LegalMoveMarker; 4] = [
+        LegalMoveMarker::Ring,
+        LegalMoveMarker::Dot,
+        LegalMoveMarker::Highlight,
+        LegalMoveMarker::Hidden,
+    ];
+
+    /// 設定画面に表示する名前
+    pub fn label(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (LegalMoveMarker::Ring, Language::Japanese) => "輪",
+            (LegalMoveMarker::Ring, Language::English) => "Ring",
+            (LegalMoveMarker::Dot, Language::Japanese) => "点",
+            (LegalMoveMarker::Dot, Language::English) => "Dot",
+            (LegalMoveMarker::Highlight, Language::Japanese) => "マスを強調",
+            (LegalMoveMarker::Highlight, Language::English) => "Highlight Cell",
+            (LegalMoveMarker::Hidden, Language::Japanese) => "表示しない",
+            (LegalMoveMarker::Hidden, Language::English) => "None",
+        }
+    }
+}
+
+/// 合法手のマスに描く図形を求める（radius は石の半径）
+pub fn legal_move_marker_shapes(
+    marker: LegalMoveMarker,
+    cell_rect: egui::Rect,
+    radius: f32,
+) -> Vec<egui::Shape> {
+    let center = cell_rect.center();
+    let dot = egui::Shape::circle_filled(center, 3.0, LEGAL_MOVE_COLOR);
+
+    match marker {
+        LegalMoveMarker::Ring => vec![
+            egui::Shape::circle_stroke(
+                center,
+                radius * 0.6,
+                egui::Stroke::new(2.0, LEGAL_MOVE_COLOR),
+            ),
+            dot,
+        ],
+        LegalMoveMarker::Dot => vec![dot],
+        LegalMoveMarker::Highlight => vec![egui::Shape::rect_filled(
+            cell_rect.shrink(2.0),
+            2.0,
+            LEGAL_MOVE_COLOR.gamma_multiply(0.35),
+        )],
+        LegalMoveMarker::Hidden => Vec::new(),
+    }
+}
+
+/// 盤面の見た目の設定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardAppearance {
+    pub felt_color: egui::Color32,          // 盤面の背景色
+    pub grid_color: egui::Color32,          // マス目の線の色
+    pub grid_width: f32,                    // マス目の線の太さ
+    pub disc_radius_ratio: f32,             // マスの大きさに対する石の半径の比率
+    pub legal_move_marker: LegalMoveMarker, // 合法手の印
+}
+
+impl Default for BoardAppearance {
+    fn default() -> Self {
+        Self {
+            felt_color: egui::Color32::from_rgb(34, 139, 34),
+            grid_color: egui::Color32::BLACK,
+            grid_width: 1.0,
+            disc_radius_ratio: 0.35,
+            legal_move_marker: LegalMoveMarker::Ring,
+        }
+    }
+}
+
+impl BoardAppearance {
+    /// 石の半径を求める
+    pub fn disc_radius(&self, cell_size: f32) -> f32 {
+        cell_size * self.disc_radius_ratio
+    }
+}
+
+/// 盤面内の指定したマスの矩形を求める
+fn cell_rect(board_rect: egui::Rect, cell_size: f32, row: usize, col: usize) -> egui::Rect {
+    egui::Rect::from_min_size(
+        board_rect.min + egui::Vec2::new(col as f32 * cell_size, row as f32 * cell_size),
+        egui::Vec2::new(cell_size, cell_size),
+    )
+}
+
+/// 評価値を最善 best から最悪 worst までの中での位置（1.0 が最善、0.0 が最悪）にする
+fn eval_grade(score: i32, best: i32, worst: i32) -> f32 {
+    if best == worst {
+        1.0
+    } else {
+        (score - worst) as f32 / (best - worst) as f32
+    }
+}
+
+/// 評価値の良し悪しを色で表す（1.0 が最善で緑、0.0 が最悪で赤）
+fn eval_grade_color(grade: f32) -> egui::Color32 {
+    let grade = grade.clamp(0.0, 1.0);
+    egui::Color32::from_rgb(
+        (230.0 * (1.0 - grade)) as u8,
+        (60.0 + 170.0 * grade) as u8,
+        40,
+    )
+}
+
 pub struct GameView {
     cell_size: f32,
+    hovered_cell: Option<(usize, usize)>,
+    appearance: BoardAppearance,
+    move_evals: Vec<(usize, i32)>, // 合法手ごとの評価値（表示しない場合は空）
+    hint: Option<usize>,           // ヒントで勧める手
+}
+
+impl Default for GameView {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameView {
     pub fn new() -> Self {
-        Self { cell_size: 50.0 }
+        Self {
+            cell_size: 50.0,
+            hovered_cell: None,
+            appearance: BoardAppearance::default(),
+            move_evals: Vec::new(),
+            hint: None,
+        }
+    }
+
+    /// 合法手の上に表示する評価値を設定する（空なら通常の合法手の印を表示）
+    pub fn set_move_evals(&mut self, evals: Vec<(usize, i32)>) {
+        self.move_evals = evals;
+    }
+
+    /// ヒントで勧める手を設定する（None なら表示しない）
+    pub fn set_hint(&mut self, hint: Option<usize>) {
+        self.hint = hint;
+    }
+
+    /// 盤面の一辺の大きさ（ピクセル）
+    pub fn board_size(&self) -> f32 {
+        self.cell_size * 8.0
+    }
+
+    /// 直前の描画でマウスが乗っていたセル
+    pub fn hovered_cell(&self) -> Option<(usize, usize)> {
+        self.hovered_cell
+    }
+
+    /// 画面上の座標から盤面のセルを求める
+    fn cell_at(&self, board_rect: egui::Rect, pos: egui::Pos2) -> Option<(usize, usize)> {
+        let board_size = self.cell_size * 8.0;
+        let rel_x = pos.x - board_rect.min.x;
+        let rel_y = pos.y - board_rect.min.y;
+
+        if rel_x >= 0.0 && rel_y >= 0.0 && rel_x < board_size && rel_y < board_size {
+            let col = (rel_x / self.cell_size) as usize;
+            let row = (rel_y / self.cell_size) as usize;
+
+            if row < 8 && col < 8 {
+                return Some((row, col));
+            }
+        }
+        None
+    }
+
+    /// 盤面の見た目の設定グループ
+    fn show_appearance_settings(&mut self, ui: &mut egui::Ui, language: Language) {
+        let (title, felt, grid, width, disc, marker) = match language {
+            Language::Japanese => (
+                "盤面の見た目",
+                "盤の色:",
+                "線の色:",
+                "線の太さ:",
+                "石の大きさ:",
+                "合法手の印:",
+            ),
+            Language::English => (
+                "Board Appearance",
+                "Felt Color:",
+                "Grid Color:",
+                "Grid Width:",
+                "Disc Size:",
+                "Legal Move Marker:",
+            ),
+        };
+
+        ui.collapsing(title, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(felt);
+                ui.color_edit_button_srgba(&mut self.appearance.felt_color);
+                ui.label(grid);
+                ui.color_edit_button_srgba(&mut self.appearance.grid_color);
+            });
+            ui.horizontal(|ui| {
+                ui.label(width);
+                ui.add(egui::Slider::new(
+                    &mut self.appearance.grid_width,
+                    0.5..=4.0,
+                ));
+            });
+            ui.horizontal(|ui| {
+                ui.label(disc);
+                ui.add(egui::Slider::new(
+                    &mut self.appearance.disc_radius_ratio,
+                    0.2..=0.48,
+                ));
+            });
+            ui.horizontal(|ui| {
+                ui.label(marker);
+                egui::ComboBox::from_id_source("legal_move_marker")
+                    .selected_text(self.appearance.legal_move_marker.label(language))
+                    .show_ui(ui, |ui| {
+                        for option in LegalMoveMarker::ALL {
+                            ui.selectable_value(
+                                &mut self.appearance.legal_move_marker,
+                                option,
+                                option.label(language),
+                            );
+                        }
+                    });
+            });
+            if ui
+                .button(match language {
+                    Language::Japanese => "初期設定に戻す",
+                    Language::English => "Reset",
+                })
+                .clicked()
+            {
+                self.appearance = BoardAppearance::default();
+            }
+        });
     }
 
+    /// 盤面を描画し、クリックされたセルを返す（last_move に直前に打たれたマスの印を付ける）
     pub fn show(
         &mut self,
         board: &BitBoard,
         current_player: Player,
+        last_move: Option<(usize, usize)>,
         ui: &mut egui::Ui,
         language: Language,
     ) -> Option<(usize, usize)> {
-        let legal_moves = board.get_legal_moves(current_player);
         let mut clicked_cell = None;
 
         ui.horizontal(|ui| {
@@ -31,6 +278,8 @@ impl GameView {
             ui.add(egui::Slider::new(&mut self.cell_size, 30.0..=80.0).text("px"));
         });
 
+        self.show_appearance_settings(ui, language);
+
         ui.add_space(10.0);
 
         // ボード描画
@@ -46,76 +295,101 @@ impl GameView {
         );
 
         // 背景
-        painter.rect_filled(board_rect, 0.0, egui::Color32::from_rgb(34, 139, 34));
+        painter.rect_filled(board_rect, 0.0, self.appearance.felt_color);
+
+        // 評価値の色分けのための最善・最悪の値
+        let best_eval = self.move_evals.iter().map(|&(_, score)| score).max();
+        let worst_eval = self.move_evals.iter().map(|&(_, score)| score).min();
 
         // グリッド線とセル
+        let grid_stroke = egui::Stroke::new(self.appearance.grid_width, self.appearance.grid_color);
         for row in 0..8 {
             for col in 0..8 {
-                let cell_rect = egui::Rect::from_min_size(
-                    board_rect.min
-                        + egui::Vec2::new(col as f32 * self.cell_size, row as f32 * self.cell_size),
-                    egui::Vec2::new(self.cell_size, self.cell_size),
+                painter.rect_stroke(
+                    cell_rect(board_rect, self.cell_size, row, col),
+                    0.0,
+                    grid_stroke,
                 );
+            }
+        }
+
+        // 石の描画
+        let radius = self.appearance.disc_radius(self.cell_size);
+        for (player, fill, outline) in [
+            (Player::Black, egui::Color32::BLACK, egui::Color32::GRAY),
+            (Player::White, egui::Color32::WHITE, egui::Color32::BLACK),
+        ] {
+            for position in board.disc_positions(player) {
+                let (row, col) = pos_to_rc(position);
+                let center = cell_rect(board_rect, self.cell_size, row, col).center();
+                painter.circle_filled(center, radius, fill);
+                painter.circle_stroke(center, radius, egui::Stroke::new(1.0, outline));
+            }
+        }
 
-                // セルの境界線
-                painter.rect_stroke(cell_rect, 0.0, egui::Stroke::new(1.0, egui::Color32::BLACK));
-
-                // 石の描画
-                let position = row * 8 + col;
-                let black_pieces = board.black;
-                let white_pieces = board.white;
-
-                let center = cell_rect.center();
-                let radius = self.cell_size * 0.35;
-
-                if (black_pieces & (1u64 << position)) != 0 {
-                    // 黒石
-                    painter.circle_filled(center, radius, egui::Color32::BLACK);
-                    painter.circle_stroke(
-                        center,
-                        radius,
-                        egui::Stroke::new(1.0, egui::Color32::GRAY),
-                    );
-                } else if (white_pieces & (1u64 << position)) != 0 {
-                    // 白石
-                    painter.circle_filled(center, radius, egui::Color32::WHITE);
-                    painter.circle_stroke(
-                        center,
-                        radius,
-                        egui::Stroke::new(1.0, egui::Color32::BLACK),
-                    );
-                } else if (legal_moves & (1u64 << position)) != 0 {
-                    // 合法手の表示
-                    painter.circle_stroke(
-                        center,
-                        radius * 0.6,
-                        egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 215, 0)),
-                    );
-
-                    // 小さな点を中央に
-                    painter.circle_filled(center, 3.0, egui::Color32::from_rgb(255, 215, 0));
-                }
+        // 直前に打たれた石の印
+        if let Some((row, col)) = last_move {
+            let center = cell_rect(board_rect, self.cell_size, row, col).center();
+            painter.circle_stroke(
+                center,
+                radius * 0.45,
+                egui::Stroke::new(2.0, LAST_MOVE_COLOR),
+            );
+        }
+
+        // 合法手の表示
+        for position in board.legal_moves_iter(current_player) {
+            let (row, col) = pos_to_rc(position);
+            let cell_rect = cell_rect(board_rect, self.cell_size, row, col);
+
+            if let (Some(&(_, score)), Some(best), Some(worst)) = (
+                self.move_evals.iter().find(|&&(pos, _)| pos == position),
+                best_eval,
+                worst_eval,
+            ) {
+                // 合法手の評価値を、最善から最悪まで色分けして表示
+                let grade = eval_grade(score, best, worst);
+                painter.rect_filled(cell_rect.shrink(2.0), 2.0, eval_grade_color(grade));
+                painter.text(
+                    cell_rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    score.to_string(),
+                    egui::FontId::proportional(self.cell_size * 0.28),
+                    egui::Color32::WHITE,
+                );
+            } else {
+                // 設定した種類の印
+                painter.extend(legal_move_marker_shapes(
+                    self.appearance.legal_move_marker,
+                    cell_rect,
+                    radius,
+                ));
             }
         }
 
+        // ヒントで勧める手
+        if let Some(position) = self.hint {
+            let (row, col) = pos_to_rc(position);
+            painter.rect_stroke(
+                cell_rect(board_rect, self.cell_size, row, col).shrink(2.0),
+                2.0,
+                egui::Stroke::new(3.0, HINT_COLOR),
+            );
+        }
+
         // クリック処理
         if response.clicked() {
             if let Some(click_pos) = response.interact_pointer_pos() {
                 // どのセルがクリックされたかを判定
-                let rel_x = click_pos.x - board_rect.min.x;
-                let rel_y = click_pos.y - board_rect.min.y;
-
-                if rel_x >= 0.0 && rel_y >= 0.0 && rel_x < board_size && rel_y < board_size {
-                    let col = (rel_x / self.cell_size) as usize;
-                    let row = (rel_y / self.cell_size) as usize;
-
-                    if row < 8 && col < 8 {
-                        clicked_cell = Some((row, col));
-                    }
-                }
+                clicked_cell = self.cell_at(board_rect, click_pos);
             }
         }
 
+        // マウスが乗っているセル
+        self.hovered_cell = response
+            .hover_pos()
+            .and_then(|pos| self.cell_at(board_rect, pos));
+
         // 座標ラベル
         for i in 0..8 {
             // 行番号（左側）
@@ -170,7 +444,7 @@ impl GameView {
         );
 
         // 合法手の数を表示
-        let legal_move_count = legal_moves.count_ones();
+        let legal_move_count = board.legal_move_count(current_player);
         if legal_move_count > 0 {
             let moves_text = match language {
                 Language::Japanese => format!("打てる場所: {}箇所", legal_move_count),
@@ -190,3 +464,74 @@ impl GameView {
         clicked_cell
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_eval_is_green_and_worst_is_red() {
+        let best = eval_grade_color(eval_grade(120, 120, -80));
+        let worst = eval_grade_color(eval_grade(-80, 120, -80));
+        assert!(best.g() > best.r());
+        assert!(worst.r() > worst.g());
+        // 全ての手が同じ評価なら最善として表示する
+        assert_eq!(eval_grade(5, 5, 5), 1.0);
+    }
+
+    #[test]
+    fn cell_geometry_follows_appearance() {
+        let appearance = BoardAppearance {
+            disc_radius_ratio: 0.4,
+            ..BoardAppearance::default()
+        };
+        assert_eq!(appearance.disc_radius(50.0), 20.0);
+        assert_eq!(BoardAppearance::default().disc_radius(40.0), 14.0);
+
+        let board_rect =
+            egui::Rect::from_min_size(egui::pos2(10.0, 20.0), egui::vec2(400.0, 400.0));
+        let rect = cell_rect(board_rect, 50.0, 2, 3);
+        assert_eq!(rect.min, egui::pos2(160.0, 120.0));
+        assert_eq!(rect.size(), egui::vec2(50.0, 50.0));
+
+        // セルの中心からそのセルを逆算できる
+        let view = GameView::new();
+        assert_eq!(view.cell_at(board_rect, rect.center()), Some((2, 3)));
+        assert_eq!(view.cell_at(board_rect, egui::pos2(5.0, 25.0)), None);
+    }
+
+    #[test]
+    fn legal_move_marker_shapes_follow_the_style() {
+        let cell = egui::Rect::from_min_size(egui::pos2(100.0, 100.0), egui::vec2(50.0, 50.0));
+        let radius = 17.5;
+        let shapes = |marker| legal_move_marker_shapes(marker, cell, radius);
+
+        // 輪は中心に半径の6割の円と点、点だけなら中心の小さな円だけ
+        match shapes(LegalMoveMarker::Ring).as_slice() {
+            [egui::Shape::Circle(ring), egui::Shape::Circle(dot)] => {
+                assert_eq!(ring.center, cell.center());
+                assert_eq!(ring.radius, radius * 0.6);
+                assert_eq!(ring.fill, egui::Color32::TRANSPARENT);
+                assert_eq!(dot.center, cell.center());
+                assert_eq!(dot.fill, LEGAL_MOVE_COLOR);
+            }
+            other => panic!("輪の図形が違います: {:?}", other),
+        }
+        match shapes(LegalMoveMarker::Dot).as_slice() {
+            [egui::Shape::Circle(dot)] => {
+                assert_eq!(dot.center, cell.center());
+                assert!(dot.radius < radius);
+            }
+            other => panic!("点の図形が違います: {:?}", other),
+        }
+        // 強調はマスの内側を塗る
+        match shapes(LegalMoveMarker::Highlight).as_slice() {
+            [egui::Shape::Rect(rect)] => {
+                assert!(cell.contains_rect(rect.rect));
+                assert_ne!(rect.fill, egui::Color32::TRANSPARENT);
+            }
+            other => panic!("強調の図形が違います: {:?}", other),
+        }
+        assert!(shapes(LegalMoveMarker::Hidden).is_empty());
+    }
+}
@@ -1,5 +1,45 @@
 use eframe::egui;
 use egui::FontFamily;
+use std::path::Path;
+
+// 実行時に読み込む追加のCJKフォント（名前, パス）。無くても起動はできる
+const OPTIONAL_CJK_FONTS: [(&str, &str); 2] = [
+    ("noto_sans_sc", "assets/NotoSansSC-VariableFont_wght.ttf"), // 中国語（簡体字）
+    ("noto_sans_kr", "assets/NotoSansKR-VariableFont_wght.ttf"), // 韓国語
+];
+
+/// フォントファイルを読み込んで、既存のフォントの後ろに代替フォントとして登録する
+/// 読み込めなければ警告を出して何もしない（egui の既定のフォントのまま）
+pub fn register_font_file(
+    fonts: &mut egui::FontDefinitions,
+    name: &str,
+    path: impl AsRef<Path>,
+) -> bool {
+    let path = path.as_ref();
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!(
+                "警告: フォント {} を読み込めませんでした（{}）。既定のフォントを使います",
+                path.display(),
+                e
+            );
+            return false;
+        }
+    };
+
+    fonts
+        .font_data
+        .insert(name.to_owned(), egui::FontData::from_owned(data));
+    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+        fonts
+            .families
+            .entry(family)
+            .or_default()
+            .push(name.to_owned());
+    }
+    true
+}
 
 // フォント設定用の関数
 pub fn setup_custom_fonts(ctx: &egui::Context) {
@@ -29,6 +69,40 @@ pub fn setup_custom_fonts(ctx: &egui::Context) {
         .or_default()
         .push("noto_sans_jp".to_owned());
 
+    // 他の言語用のフォントは実行時に読み込む（無ければ警告だけ出す）
+    for (name, path) in OPTIONAL_CJK_FONTS {
+        register_font_file(&mut fonts, name, path);
+    }
+
     // フォント設定を適用
     ctx.set_fonts(fonts);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_font_file_keeps_default_fonts() {
+        let mut fonts = egui::FontDefinitions::default();
+        assert!(!register_font_file(
+            &mut fonts,
+            "missing",
+            "assets/no_such_font.ttf"
+        ));
+        assert_eq!(fonts, egui::FontDefinitions::default());
+
+        // 読み込めたフォントは両方のファミリーの最後に追加される
+        assert!(register_font_file(
+            &mut fonts,
+            "noto_sans_jp",
+            "assets/NotoSansJP-VariableFont_wght.ttf"
+        ));
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            assert_eq!(
+                fonts.families[&family].last().map(String::as_str),
+                Some("noto_sans_jp")
+            );
+        }
+    }
+}
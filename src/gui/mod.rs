@@ -1,4 +1,5 @@
 pub mod app;
+pub mod engine_tuner;
 pub mod game_view;
 pub mod plot_viewer;
 
@@ -1,14 +1,16 @@
-use crate::gui::app::Language;
+use crate::language::Language;
 use crate::player::Player;
-use crate::stats::{GameResult, GameStats};
+use crate::stats::{format_duration, GameResult, GameStats, PlotPalette, Termination};
 use eframe::egui;
-use egui_plot::{Line, Plot, PlotPoints};
+use egui_plot::{Line, Plot, PlotPoints, Points};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PlotType {
     DiscCount,
     ThinkingTime,
     Evaluation,
+    Mobility,
     Overview,
 }
 
@@ -16,6 +18,39 @@ pub struct PlotViewer {
     selected_plot: PlotType,
     has_data: bool,
     fixed_bounds: bool,
+    palette: PlotPalette,
+    available_size: egui::Vec2,
+}
+
+// Size limits for the viewer area and the plots inside it
+const VIEWER_MIN_SIZE: egui::Vec2 = egui::Vec2::new(500.0, 400.0);
+const VIEWER_MAX_SIZE: egui::Vec2 = egui::Vec2::new(1600.0, 1200.0);
+const MAIN_PLOT_MIN_SIZE: egui::Vec2 = egui::Vec2::new(400.0, 230.0);
+const MAIN_PLOT_MAX_SIZE: egui::Vec2 = egui::Vec2::new(1600.0, 900.0);
+const MINI_PLOT_MIN_SIZE: egui::Vec2 = egui::Vec2::new(200.0, 100.0);
+const MINI_PLOT_MAX_SIZE: egui::Vec2 = egui::Vec2::new(800.0, 400.0);
+
+/// Fit a plot of the given aspect ratio into the available space, clamped to the size limits
+fn responsive_plot_size(
+    available: egui::Vec2,
+    aspect: f32,
+    min: egui::Vec2,
+    max: egui::Vec2,
+) -> egui::Vec2 {
+    let width = available.x;
+    let height = (width / aspect).min(available.y);
+    egui::Vec2::new(width, height).clamp(min, max)
+}
+
+/// Convert a palette color into an egui color
+fn color32(color: (u8, u8, u8)) -> egui::Color32 {
+    egui::Color32::from_rgb(color.0, color.1, color.2)
+}
+
+impl Default for PlotViewer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PlotViewer {
@@ -24,9 +59,42 @@ impl PlotViewer {
             selected_plot: PlotType::DiscCount,
             has_data: false,
             fixed_bounds: true,
+            palette: PlotPalette::default(),
+            available_size: VIEWER_MIN_SIZE,
         }
     }
 
+    /// Size of the large single plots
+    fn main_plot_size(&self) -> egui::Vec2 {
+        responsive_plot_size(
+            egui::Vec2::new(self.available_size.x - 100.0, self.available_size.y * 0.75),
+            1.75,
+            MAIN_PLOT_MIN_SIZE,
+            MAIN_PLOT_MAX_SIZE,
+        )
+    }
+
+    /// Size of the side-by-side overview plots
+    fn mini_plot_size(&self) -> egui::Vec2 {
+        responsive_plot_size(
+            egui::Vec2::new(
+                self.available_size.x / 2.0 - 50.0,
+                self.available_size.y * 0.4,
+            ),
+            2.0,
+            MINI_PLOT_MIN_SIZE,
+            MINI_PLOT_MAX_SIZE,
+        )
+    }
+
+    /// Line color of a player's series in every plot
+    fn player_color(&self, player: Player) -> egui::Color32 {
+        color32(match player {
+            Player::Black => self.palette.black,
+            Player::White => self.palette.white,
+        })
+    }
+
     pub fn mark_data_available(&mut self) {
         self.has_data = true;
     }
@@ -47,13 +115,15 @@ impl PlotViewer {
             return;
         }
 
-        // Add fixed container to control expansion
+        // Fill the available space (within limits) so the plots follow the window size
+        let viewer_size = ui.available_size().clamp(VIEWER_MIN_SIZE, VIEWER_MAX_SIZE);
+        self.available_size = viewer_size;
         ui.allocate_ui_with_layout(
-            egui::Vec2::new(800.0, 600.0),
+            viewer_size,
             egui::Layout::top_down(egui::Align::Min),
             |ui| {
                 egui::ScrollArea::vertical()
-                    .max_height(580.0)
+                    .max_height(viewer_size.y - 20.0)
                     .show(ui, |ui| {
                         self.show_content(ui, language, stats, result);
                     });
@@ -112,6 +182,17 @@ impl PlotViewer {
                 self.selected_plot = PlotType::Evaluation;
             }
 
+            let mobility_text = match language {
+                Language::Japanese => "合法手数",
+                Language::English => "Mobility",
+            };
+            if ui
+                .selectable_label(self.selected_plot == PlotType::Mobility, mobility_text)
+                .clicked()
+            {
+                self.selected_plot = PlotType::Mobility;
+            }
+
             let overview_text = match language {
                 Language::Japanese => "総合表示",
                 Language::English => "Overview",
@@ -152,6 +233,21 @@ impl PlotViewer {
                 // Force plot to recalculate bounds
                 ui.ctx().request_repaint();
             }
+
+            ui.separator();
+
+            let mut colorblind = self.palette == PlotPalette::COLORBLIND;
+            let colorblind_label = match language {
+                Language::Japanese => "色覚対応配色",
+                Language::English => "Colorblind palette",
+            };
+            if ui.checkbox(&mut colorblind, colorblind_label).changed() {
+                self.palette = if colorblind {
+                    PlotPalette::COLORBLIND
+                } else {
+                    PlotPalette::STANDARD
+                };
+            }
         });
 
         ui.separator();
@@ -161,6 +257,7 @@ impl PlotViewer {
             PlotType::DiscCount => self.show_disc_count_plot(ui, language, stats, result),
             PlotType::ThinkingTime => self.show_thinking_time_plot(ui, language, stats, result),
             PlotType::Evaluation => self.show_evaluation_plot(ui, language, stats, result),
+            PlotType::Mobility => self.show_mobility_plot(ui, language, stats, result),
             PlotType::Overview => self.show_overview_plots(ui, language, stats, result),
         }
     }
@@ -213,8 +310,8 @@ impl PlotViewer {
             .legend(egui_plot::Legend::default())
             .x_axis_label(x_label)
             .y_axis_label(y_label)
-            .height(400.0)
-            .width(700.0)
+            .height(self.main_plot_size().y)
+            .width(self.main_plot_size().x)
             .view_aspect(1.75);
 
         if self.fixed_bounds {
@@ -236,7 +333,7 @@ impl PlotViewer {
             };
             plot_ui.line(
                 Line::new(black_points)
-                    .color(egui::Color32::RED)
+                    .color(self.player_color(Player::Black))
                     .name(black_label),
             );
 
@@ -246,7 +343,7 @@ impl PlotViewer {
             };
             plot_ui.line(
                 Line::new(white_points)
-                    .color(egui::Color32::BLUE)
+                    .color(self.player_color(Player::White))
                     .name(white_label),
             );
         });
@@ -256,6 +353,100 @@ impl PlotViewer {
         self.show_game_result_summary(ui, language, result);
     }
 
+    fn show_mobility_plot(
+        &self,
+        ui: &mut egui::Ui,
+        language: Language,
+        stats: &GameStats,
+        result: &GameResult,
+    ) {
+        let mobility_history = stats.get_mobility_history();
+
+        if mobility_history.is_empty() {
+            let no_data_text = match language {
+                Language::Japanese => "合法手の数のデータがありません。",
+                Language::English => "No mobility data available.",
+            };
+            ui.label(no_data_text);
+            return;
+        }
+
+        let x_label = match language {
+            Language::Japanese => "手数",
+            Language::English => "Move Number",
+        };
+
+        let y_label = match language {
+            Language::Japanese => "合法手の数",
+            Language::English => "Legal Moves",
+        };
+
+        // Prepare data
+        let black_points: PlotPoints = mobility_history
+            .iter()
+            .map(|(move_num, black, _)| [*move_num as f64, *black as f64])
+            .collect();
+
+        let white_points: PlotPoints = mobility_history
+            .iter()
+            .map(|(move_num, _, white)| [*move_num as f64, *white as f64])
+            .collect();
+
+        let mut plot = Plot::new("main_mobility_plot")
+            .legend(egui_plot::Legend::default())
+            .x_axis_label(x_label)
+            .y_axis_label(y_label)
+            .height(self.main_plot_size().y)
+            .width(self.main_plot_size().x)
+            .view_aspect(1.75);
+
+        if self.fixed_bounds {
+            // Set fixed bounds to prevent continuous expansion
+            let max_move = mobility_history
+                .iter()
+                .map(|(m, _, _)| *m)
+                .max()
+                .unwrap_or(0) as f64;
+            let max_mobility = mobility_history
+                .iter()
+                .map(|(_, b, w)| (*b).max(*w))
+                .max()
+                .unwrap_or(0) as f64;
+            plot = plot
+                .include_x(0.0)
+                .include_x(max_move + 1.0)
+                .include_y(0.0)
+                .include_y(max_mobility + 2.0);
+        } else {
+            plot = plot.auto_bounds_x().auto_bounds_y();
+        }
+
+        plot.show(ui, |plot_ui| {
+            let black_label = match language {
+                Language::Japanese => "黒",
+                Language::English => "Black",
+            };
+            plot_ui.line(
+                Line::new(black_points)
+                    .color(self.player_color(Player::Black))
+                    .name(black_label),
+            );
+
+            let white_label = match language {
+                Language::Japanese => "白",
+                Language::English => "White",
+            };
+            plot_ui.line(
+                Line::new(white_points)
+                    .color(self.player_color(Player::White))
+                    .name(white_label),
+            );
+        });
+
+        ui.add_space(10.0);
+        self.show_game_result_summary(ui, language, result);
+    }
+
     fn show_thinking_time_plot(
         &self,
         ui: &mut egui::Ui,
@@ -264,6 +455,7 @@ impl PlotViewer {
         result: &GameResult,
     ) {
         let time_history = stats.get_thinking_time_history();
+        let compute_history = stats.get_compute_time_history();
 
         if time_history.is_empty() {
             let no_data_text = match language {
@@ -303,8 +495,8 @@ impl PlotViewer {
             .legend(egui_plot::Legend::default())
             .x_axis_label(x_label)
             .y_axis_label(y_label)
-            .height(400.0)
-            .width(700.0)
+            .height(self.main_plot_size().y)
+            .width(self.main_plot_size().x)
             .view_aspect(1.75);
 
         if self.fixed_bounds {
@@ -327,10 +519,28 @@ impl PlotViewer {
             };
             plot_ui.line(
                 Line::new(time_points)
-                    .color(egui::Color32::RED)
+                    .color(color32(self.palette.thinking_time))
                     .name(time_label),
             );
 
+            // Engine compute time without the artificial padding (AI moves only)
+            if !compute_history.is_empty() {
+                let compute_points: PlotPoints = compute_history
+                    .iter()
+                    .map(|(move_num, time)| [*move_num as f64, *time])
+                    .collect();
+                let compute_label = match language {
+                    Language::Japanese => "計算時間（待ち時間を除く）",
+                    Language::English => "Compute Time (excl. padding)",
+                };
+                plot_ui.points(
+                    Points::new(compute_points)
+                        .color(color32(self.palette.compute_time))
+                        .radius(3.0)
+                        .name(compute_label),
+                );
+            }
+
             // Add average line
             if !time_history.is_empty() {
                 let first_move = time_history.first().unwrap().0 as f64;
@@ -342,10 +552,11 @@ impl PlotViewer {
                     Language::Japanese => format!("平均: {:.2}秒", avg_time),
                     Language::English => format!("Average: {:.2}s", avg_time),
                 };
+                let average_color = color32(self.palette.average);
                 plot_ui.line(
                     Line::new(avg_line)
-                        .color(egui::Color32::GREEN)
-                        .stroke(egui::Stroke::new(2.0, egui::Color32::GREEN))
+                        .color(average_color)
+                        .stroke(egui::Stroke::new(2.0, average_color))
                         .name(avg_label),
                 );
             }
@@ -362,7 +573,8 @@ impl PlotViewer {
         stats: &GameStats,
         _result: &GameResult,
     ) {
-        let eval_history = stats.get_evaluation_history();
+        // 黒と白で基準を揃えるため、黒から見た値で表示する（正なら黒が優勢）
+        let eval_history = stats.get_normalized_evaluation_history();
 
         if eval_history.is_empty() {
             let no_data_text = match language {
@@ -384,8 +596,8 @@ impl PlotViewer {
         };
 
         let y_label = match language {
-            Language::Japanese => "評価値",
-            Language::English => "Evaluation Score",
+            Language::Japanese => "評価値（黒から見た値）",
+            Language::English => "Evaluation (Black's view)",
         };
 
         // Separate data by player
@@ -405,8 +617,8 @@ impl PlotViewer {
             .legend(egui_plot::Legend::default())
             .x_axis_label(x_label)
             .y_axis_label(y_label)
-            .height(400.0)
-            .width(700.0)
+            .height(self.main_plot_size().y)
+            .width(self.main_plot_size().x)
             .view_aspect(1.75);
 
         if self.fixed_bounds {
@@ -432,7 +644,7 @@ impl PlotViewer {
                 };
                 plot_ui.line(
                     Line::new(black_evals)
-                        .color(egui::Color32::RED)
+                        .color(self.player_color(Player::Black))
                         .name(black_label),
                 );
             }
@@ -444,7 +656,7 @@ impl PlotViewer {
                 };
                 plot_ui.line(
                     Line::new(white_evals)
-                        .color(egui::Color32::BLUE)
+                        .color(self.player_color(Player::White))
                         .name(white_label),
                 );
             }
@@ -453,10 +665,11 @@ impl PlotViewer {
             if let (Some(first), Some(last)) = (eval_history.first(), eval_history.last()) {
                 let zero_line: PlotPoints =
                     vec![[first.0 as f64, 0.0], [last.0 as f64, 0.0]].into();
+                let zero_color = color32(self.palette.zero_line);
                 plot_ui.line(
                     Line::new(zero_line)
-                        .color(egui::Color32::GRAY)
-                        .stroke(egui::Stroke::new(1.0, egui::Color32::GRAY))
+                        .color(zero_color)
+                        .stroke(egui::Stroke::new(1.0, zero_color))
                         .name("Zero"),
                 );
             }
@@ -476,8 +689,8 @@ impl PlotViewer {
         ui.horizontal(|ui| {
             // Left column - Disc count
             ui.vertical(|ui| {
-                ui.set_width(350.0);
-                ui.set_height(200.0);
+                ui.set_width(self.mini_plot_size().x + 50.0);
+                ui.set_height(self.mini_plot_size().y + 50.0);
                 let title = match language {
                     Language::Japanese => "石数推移（簡略）",
                     Language::English => "Disc Count (Brief)",
@@ -490,8 +703,8 @@ impl PlotViewer {
 
             // Right column - Thinking time
             ui.vertical(|ui| {
-                ui.set_width(350.0);
-                ui.set_height(200.0);
+                ui.set_width(self.mini_plot_size().x + 50.0);
+                ui.set_height(self.mini_plot_size().y + 50.0);
                 let title = match language {
                     Language::Japanese => "思考時間（簡略）",
                     Language::English => "Thinking Time (Brief)",
@@ -508,7 +721,7 @@ impl PlotViewer {
         self.show_detailed_game_summary(ui, language, result);
     }
 
-    fn show_mini_disc_plot(&self, ui: &mut egui::Ui, _language: Language, stats: &GameStats) {
+    fn show_mini_disc_plot(&self, ui: &mut egui::Ui, language: Language, stats: &GameStats) {
         let disc_history = stats.get_disc_count_history();
 
         if disc_history.is_empty() {
@@ -526,8 +739,9 @@ impl PlotViewer {
             .collect();
 
         let mut plot = Plot::new("overview_mini_disc_plot")
-            .height(150.0)
-            .width(300.0)
+            .legend(egui_plot::Legend::default())
+            .height(self.mini_plot_size().y)
+            .width(self.mini_plot_size().x)
             .view_aspect(2.0);
 
         if self.fixed_bounds {
@@ -542,8 +756,20 @@ impl PlotViewer {
         }
 
         plot.show(ui, |plot_ui| {
-            plot_ui.line(Line::new(black_points).color(egui::Color32::RED));
-            plot_ui.line(Line::new(white_points).color(egui::Color32::BLUE));
+            let (black_label, white_label) = match language {
+                Language::Japanese => ("黒", "白"),
+                Language::English => ("Black", "White"),
+            };
+            plot_ui.line(
+                Line::new(black_points)
+                    .color(self.player_color(Player::Black))
+                    .name(black_label),
+            );
+            plot_ui.line(
+                Line::new(white_points)
+                    .color(self.player_color(Player::White))
+                    .name(white_label),
+            );
         });
     }
 
@@ -560,8 +786,8 @@ impl PlotViewer {
             .collect();
 
         let mut plot = Plot::new("overview_mini_time_plot")
-            .height(150.0)
-            .width(300.0)
+            .height(self.mini_plot_size().y)
+            .width(self.mini_plot_size().x)
             .view_aspect(2.0);
 
         if self.fixed_bounds {
@@ -577,7 +803,7 @@ impl PlotViewer {
         }
 
         plot.show(ui, |plot_ui| {
-            plot_ui.line(Line::new(time_points).color(egui::Color32::RED));
+            plot_ui.line(Line::new(time_points).color(color32(self.palette.thinking_time)));
         });
     }
 
@@ -600,6 +826,10 @@ impl PlotViewer {
                             Language::Japanese => "勝者: 白",
                             Language::English => "Winner: White",
                         },
+                        None if result.termination == Termination::AgreedDraw => match language {
+                            Language::Japanese => "合意により引き分け",
+                            Language::English => "Draw by agreement",
+                        },
                         None => match language {
                             Language::Japanese => "引き分け",
                             Language::English => "Draw",
@@ -649,37 +879,29 @@ impl PlotViewer {
                     };
                     ui.label(total_moves_text);
 
+                    let game_duration = format_duration(result.game_duration, language);
                     let game_duration_text = match language {
-                        Language::Japanese => {
-                            format!("ゲーム時間: {:.1}秒", result.game_duration.as_secs_f64())
-                        }
-                        Language::English => {
-                            format!("Game Duration: {:.1}s", result.game_duration.as_secs_f64())
-                        }
+                        Language::Japanese => format!("ゲーム時間: {}", game_duration),
+                        Language::English => format!("Game Duration: {}", game_duration),
                     };
                     ui.label(game_duration_text);
 
+                    let total_thinking = format_duration(result.total_thinking_time, language);
                     let total_thinking_text = match language {
-                        Language::Japanese => format!(
-                            "総思考時間: {:.1}秒",
-                            result.total_thinking_time.as_secs_f64()
-                        ),
-                        Language::English => format!(
-                            "Total Thinking Time: {:.1}s",
-                            result.total_thinking_time.as_secs_f64()
-                        ),
+                        Language::Japanese => format!("総思考時間: {}", total_thinking),
+                        Language::English => format!("Total Thinking Time: {}", total_thinking),
                     };
                     ui.label(total_thinking_text);
 
                     if result.total_moves > 0 {
-                        let avg_thinking_time =
-                            result.total_thinking_time.as_secs_f64() / result.total_moves as f64;
+                        let avg_thinking_time = format_duration(
+                            result.total_thinking_time / result.total_moves as u32,
+                            language,
+                        );
                         let avg_text = match language {
-                            Language::Japanese => {
-                                format!("平均思考時間: {:.2}秒", avg_thinking_time)
-                            }
+                            Language::Japanese => format!("平均思考時間: {}", avg_thinking_time),
                             Language::English => {
-                                format!("Average Thinking Time: {:.2}s", avg_thinking_time)
+                                format!("Average Thinking Time: {}", avg_thinking_time)
                             }
                         };
                         ui.label(avg_text);
@@ -692,15 +914,18 @@ impl PlotViewer {
                         let min_time = times.iter().fold(f64::INFINITY, |a, &b| a.min(b));
                         let max_time = times.iter().fold(0.0f64, |a, &b| a.max(b));
 
+                        let min_time = format_duration(Duration::from_secs_f64(min_time), language);
+                        let max_time = format_duration(Duration::from_secs_f64(max_time), language);
+
                         let min_text = match language {
-                            Language::Japanese => format!("最短思考: {:.2}秒", min_time),
-                            Language::English => format!("Min Thinking: {:.2}s", min_time),
+                            Language::Japanese => format!("最短思考: {}", min_time),
+                            Language::English => format!("Min Thinking: {}", min_time),
                         };
                         ui.label(min_text);
 
                         let max_text = match language {
-                            Language::Japanese => format!("最長思考: {:.2}秒", max_time),
-                            Language::English => format!("Max Thinking: {:.2}s", max_time),
+                            Language::Japanese => format!("最長思考: {}", max_time),
+                            Language::English => format!("Max Thinking: {}", max_time),
                         };
                         ui.label(max_text);
                     }
@@ -724,35 +949,31 @@ impl PlotViewer {
                     };
                     ui.strong(stats_title);
 
-                    // Separate by player
-                    let black_evals: Vec<i32> = eval_history
-                        .iter()
-                        .filter(|(_, player, _)| *player == Player::Black)
-                        .map(|(_, _, eval)| *eval)
-                        .collect();
-
-                    let white_evals: Vec<i32> = eval_history
-                        .iter()
-                        .filter(|(_, player, _)| *player == Player::White)
-                        .map(|(_, _, eval)| *eval)
-                        .collect();
-
-                    if !black_evals.is_empty() {
-                        let black_avg =
-                            black_evals.iter().sum::<i32>() as f64 / black_evals.len() as f64;
+                    // Average and peak advantage, each from that player's own view
+                    if let Some(black) = stats.evaluation_advantage(Player::Black) {
                         let black_text = match language {
-                            Language::Japanese => format!("黒AI平均評価: {:.1}", black_avg),
-                            Language::English => format!("Black AI Avg Eval: {:.1}", black_avg),
+                            Language::Japanese => format!(
+                                "黒の平均優勢: {:+.1} (最大 {:+})",
+                                black.average, black.peak
+                            ),
+                            Language::English => format!(
+                                "Black's average advantage: {:+.1} (peak {:+})",
+                                black.average, black.peak
+                            ),
                         };
                         ui.label(black_text);
                     }
 
-                    if !white_evals.is_empty() {
-                        let white_avg =
-                            white_evals.iter().sum::<i32>() as f64 / white_evals.len() as f64;
+                    if let Some(white) = stats.evaluation_advantage(Player::White) {
                         let white_text = match language {
-                            Language::Japanese => format!("白AI平均評価: {:.1}", white_avg),
-                            Language::English => format!("White AI Avg Eval: {:.1}", white_avg),
+                            Language::Japanese => format!(
+                                "白の平均優勢: {:+.1} (最大 {:+})",
+                                white.average, white.peak
+                            ),
+                            Language::English => format!(
+                                "White's average advantage: {:+.1} (peak {:+})",
+                                white.average, white.peak
+                            ),
                         };
                         ui.label(white_text);
                     }
@@ -787,6 +1008,10 @@ impl PlotViewer {
                         Language::Japanese => "🏆 勝者: 白プレイヤー",
                         Language::English => "🏆 Winner: White Player",
                     },
+                    None if result.termination == Termination::AgreedDraw => match language {
+                        Language::Japanese => "🤝 合意により引き分け",
+                        Language::English => "🤝 Draw by Agreement",
+                    },
                     None => match language {
                         Language::Japanese => "🤝 引き分け",
                         Language::English => "🤝 Draw",
@@ -827,35 +1052,74 @@ impl PlotViewer {
                 };
                 ui.label(moves_text);
 
+                let game_duration = format_duration(result.game_duration, language);
                 let duration_text = match language {
-                    Language::Japanese => {
-                        format!("⏱️ ゲーム時間: {:.1}秒", result.game_duration.as_secs_f64())
-                    }
-                    Language::English => {
-                        format!(
-                            "⏱️ Game Duration: {:.1}s",
-                            result.game_duration.as_secs_f64()
-                        )
-                    }
+                    Language::Japanese => format!("⏱️ ゲーム時間: {}", game_duration),
+                    Language::English => format!("⏱️ Game Duration: {}", game_duration),
                 };
                 ui.label(duration_text);
 
+                let total_thinking = format_duration(result.total_thinking_time, language);
                 let thinking_text = match language {
-                    Language::Japanese => {
-                        format!(
-                            "🤔 総思考時間: {:.1}秒",
-                            result.total_thinking_time.as_secs_f64()
-                        )
-                    }
-                    Language::English => {
-                        format!(
-                            "🤔 Total Thinking Time: {:.1}s",
-                            result.total_thinking_time.as_secs_f64()
-                        )
-                    }
+                    Language::Japanese => format!("🤔 総思考時間: {}", total_thinking),
+                    Language::English => format!("🤔 Total Thinking Time: {}", total_thinking),
                 };
                 ui.label(thinking_text);
             });
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::plotter::rgb;
+
+    #[test]
+    fn png_and_egui_plots_use_the_same_palette_colors() {
+        for palette in [PlotPalette::STANDARD, PlotPalette::COLORBLIND] {
+            assert_ne!(palette.black, palette.white);
+            for color in [
+                palette.black,
+                palette.white,
+                palette.thinking_time,
+                palette.compute_time,
+                palette.average,
+                palette.zero_line,
+            ] {
+                let png = rgb(color);
+                let egui = color32(color);
+                assert_eq!((png.0, png.1, png.2), (egui.r(), egui.g(), egui.b()));
+            }
+        }
+    }
+
+    #[test]
+    fn responsive_plot_size_clamps_to_limits() {
+        let size = |available| {
+            responsive_plot_size(available, 2.0, MAIN_PLOT_MIN_SIZE, MAIN_PLOT_MAX_SIZE)
+        };
+        // Keeps the aspect ratio while it fits
+        assert_eq!(size(egui::vec2(1000.0, 800.0)), egui::vec2(1000.0, 500.0));
+        // Shrinks to the available height
+        assert_eq!(size(egui::vec2(1000.0, 300.0)), egui::vec2(1000.0, 300.0));
+        // Stays within the limits on tiny and huge windows
+        assert_eq!(size(egui::vec2(100.0, 50.0)), MAIN_PLOT_MIN_SIZE);
+        assert_eq!(size(egui::vec2(5000.0, 5000.0)), MAIN_PLOT_MAX_SIZE);
+    }
+
+    #[test]
+    fn player_series_use_palette_colors() {
+        let mut viewer = PlotViewer::new();
+        for palette in [PlotPalette::STANDARD, PlotPalette::COLORBLIND] {
+            viewer.palette = palette;
+            let black = viewer.player_color(Player::Black);
+            let white = viewer.player_color(Player::White);
+            assert_eq!(black, color32(palette.black));
+            assert_eq!(white, color32(palette.white));
+            // Black is never drawn in red and White never in blue
+            assert_ne!(black, egui::Color32::RED);
+            assert_ne!(white, egui::Color32::BLUE);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+/// 表示に使う言語（GUI と、統計やコマンドラインの表示で共通）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    Japanese,
+    English,
+}
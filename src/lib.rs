@@ -0,0 +1,37 @@
+//! ビットボードで実装したオセロのエンジン
+//!
+//! 盤面の操作（[`BitBoard`]）、AI の探索（[`BitBoard::find_best_move_with_tt`] など）、
+//! 対局の記録（[`GameStats`]）を提供する。GUI は `gui` フィーチャー（既定で有効）で使える。
+//!
+//! ```
+//! use bitothello::{BitBoard, Player};
+//!
+//! let mut board = BitBoard::new();
+//! let moves = board.get_legal_move_positions(Player::Black);
+//! assert_eq!(moves.len(), 4);
+//!
+//! assert!(board.make_move(moves[0], Player::Black));
+//! assert_eq!(board.count_all_discs(), (4, 1));
+//!
+//! let mut tt = Default::default();
+//! let time_limit = std::time::Duration::from_millis(100);
+//! let (best_move, _) = board.find_best_move_with_tt(Player::White, 3, &mut tt, time_limit);
+//! assert!(best_move.is_some_and(|pos| board.is_legal_move(pos, Player::White)));
+//! ```
+
+pub mod ai;
+pub mod board;
+pub mod drill;
+#[cfg(feature = "gui")]
+pub mod gui;
+pub mod language;
+pub mod match_runner;
+pub mod player;
+pub mod selfplay;
+pub mod selftest;
+pub mod stats;
+pub mod test_graphs;
+
+pub use board::BitBoard;
+pub use player::{Player, PlayerType};
+pub use stats::{GameResult, GameStats};
@@ -1,19 +1,32 @@
-mod ai;
-mod board;
-mod gui;
-mod player;
-mod stats;
-mod test_graphs;
-
-use board::BitBoard;
-use player::{Player, PlayerType};
-use stats::{plot_game_statistics, GameStats};
-use std::cell::RefCell;
-use std::collections::HashMap;
+use bitothello::ai::{
+    win_probability, EngineConfig, Personality, BLITZ_LEVEL, BLITZ_TIME_LIMIT, PERSONALITY_LEVEL,
+    WLD_ANNOUNCE_EMPTIES,
+};
+use bitothello::board::{notation, pos_to_rc, rc_to_pos, BitBoard, GameOverReason};
+use bitothello::drill::{EndgameDrill, DEFAULT_DRILL_EMPTIES};
+use bitothello::player::{Player, PlayerType};
+use bitothello::selfplay::SelfPlayOptions;
+use bitothello::stats::{
+    format_duration, plot_game_statistics, print_review, GameStats, PlotError, REVIEW_DEPTH,
+};
+#[cfg(feature = "serde")]
+use bitothello::stats::{plot_archive_summary, ArchiveSummary};
+use std::cmp::Ordering;
 use std::io::{self, Write};
 use std::time::{Duration, Instant};
 
-use crate::gui::japanese::setup_custom_fonts;
+use bitothello::gui::japanese::setup_custom_fonts;
+use bitothello::language::Language;
+use bitothello::{gui, selfplay, selftest, test_graphs};
+
+// ウィンドウの最小サイズ（メニューはこれより狭くてもスクロールして表示できる）
+const MIN_WINDOW_SIZE: [f32; 2] = [640.0, 480.0];
+
+// analyze サブコマンドで指定できる最大の深さ
+const MAX_ANALYZE_DEPTH: usize = 20;
+
+// プレイヤー選択メニューで個性派AIを並べ始める番号
+const PERSONALITY_MENU_START: usize = 10;
 
 fn main() {
     // コマンドライン引数をチェック
@@ -30,6 +43,24 @@ fn main() {
         run_cli_game();
         return;
     }
+    if args.len() > 1 && args[1] == "analyze" {
+        std::process::exit(run_analyze(&args[2..]));
+    }
+    if args.len() > 1 && args[1] == "selfplay-dataset" {
+        run_selfplay_dataset(&args[2..]);
+        return;
+    }
+    if args.len() > 1 && args[1] == "endgame-drill" {
+        run_endgame_drill(&args[2..]);
+        return;
+    }
+    if args.len() > 1 && args[1] == "--selftest" {
+        std::process::exit(if selftest::run_selftest() { 0 } else { 1 });
+    }
+    if args.len() > 1 && args[1] == "archive-summary" {
+        run_archive_summary(&args[2..]);
+        return;
+    }
 
     // デフォルトでGUIを起動
     run_gui();
@@ -51,6 +82,10 @@ fn run_cli_game() {
 
     // ゲーム統計情報の初期化
     let mut game_stats = GameStats::new();
+    game_stats.set_players(
+        player_type_to_string(&black_player),
+        player_type_to_string(&white_player),
+    );
     let mut _total_moves = 0;
     let mut thinking_time = Duration::new(0, 0);
 
@@ -85,23 +120,16 @@ fn run_cli_game() {
         pass_count = 0;
 
         // 合法手の数を素早くカウント
-        let legal_move_count = legal_moves.count_ones() as usize;
+        let legal_move_count = board.legal_move_count(current_player) as usize;
 
         // 合法手の一覧を表示（最適化版 - 大量にある場合は省略）
         println!("打てる場所: {legal_move_count}箇所");
         if legal_move_count <= 12 {
-            // 数が少ない場合のみ全表示
-            let mut positions = Vec::with_capacity(legal_move_count);
-            for pos in 0..64 {
-                if (legal_moves & (1u64 << pos)) != 0 {
-                    let row = pos / 8;
-                    let col = pos % 8;
-                    positions.push((row, col));
-                }
-            }
+            // 数が少ない場合のみ全表示（座標とひっくり返る石の数）
             print!("具体的な位置: ");
-            for (row, col) in positions {
-                print!("({},{}) ", row, col);
+            for (pos, notation, flips) in board.legal_moves_detailed(current_player) {
+                let (row, col) = pos_to_rc(pos);
+                print!("({},{})={}[{}枚] ", row, col, notation, flips);
             }
             println!();
         }
@@ -113,7 +141,7 @@ fn run_cli_game() {
         };
         // 時間計測
         let start = Instant::now();
-        let (success, move_position, evaluation) =
+        let (success, move_position, evaluation, timing) =
             player_type.play_turn(&mut board, current_player);
         if success {
             // 成功したら盤面表示して手番交代
@@ -131,13 +159,33 @@ fn run_cli_game() {
                 white_count,
                 evaluation,
             );
+            game_stats.record_mobility(&board);
+            if let Some(timing) = timing {
+                game_stats.record_timing(timing);
+            }
 
             // 盤面表示
             println!("{}", board);
+            println!(
+                "盤面の埋まり具合: {:.0}%",
+                board.board_fill_fraction() * 100.0
+            );
+
+            // 終盤は勝敗を読み切って表示
+            let next_player = current_player.opponent();
+            if board.count_empty() <= WLD_ANNOUNCE_EMPTIES && !board.is_game_over() {
+                match board.solve_wld(next_player) {
+                    Ordering::Greater => println!("★ {}の必勝です", next_player.to_string()),
+                    Ordering::Less => {
+                        println!("★ {}の必勝です", current_player.to_string())
+                    }
+                    Ordering::Equal => println!("★ 最善を尽くすと引き分けです"),
+                }
+            }
 
             // 手番交代
             current_player = current_player.opponent();
-            println!("思考時間: {:.2?}", elapsed);
+            println!("思考時間: {}", format_duration(elapsed, Language::Japanese));
         } else {
             // パスの場合も記録
             let elapsed = start.elapsed();
@@ -150,6 +198,7 @@ fn run_cli_game() {
                 white_count,
                 None,
             );
+            game_stats.record_mobility(&board);
         }
     }
 
@@ -158,6 +207,14 @@ fn run_cli_game() {
     println!("      ゲーム終了");
     println!("==========================");
 
+    match board.game_over_reason() {
+        Some(GameOverReason::BoardFull) => println!("終了理由: 盤面が全て埋まりました"),
+        Some(GameOverReason::NoLegalMovesEither) => {
+            println!("終了理由: 両者とも打てる場所がありません")
+        }
+        None => {}
+    }
+
     let (black_count, white_count) = board.count_all_discs();
     println!("黒(X): {} 白(O): {}", black_count, white_count);
 
@@ -175,22 +232,48 @@ fn run_cli_game() {
     println!("      ゲーム統計");
     println!("==========================");
     println!("総手数: {}", game_result.total_moves);
-    println!("総思考時間: {:.2?}", game_result.total_thinking_time);
-    println!("ゲーム所要時間: {:.2?}", game_result.game_duration);
+    println!(
+        "総思考時間: {}",
+        format_duration(game_result.total_thinking_time, Language::Japanese)
+    );
+    println!(
+        "ゲーム所要時間: {}",
+        format_duration(game_result.game_duration, Language::Japanese)
+    );
     if game_result.total_moves > 0 {
         println!(
-            "1手あたりの平均思考時間: {:.2?}",
-            game_result.total_thinking_time / game_result.total_moves as u32
+            "1手あたりの平均思考時間: {}",
+            format_duration(
+                game_result.total_thinking_time / game_result.total_moves as u32,
+                Language::Japanese
+            )
         );
     }
 
     // 詳細統計の表示
     game_stats.print_summary(&game_result);
 
+    // 人間の手をエンジンの最善手と比べる
+    let human_players: Vec<Player> = Player::all()
+        .into_iter()
+        .filter(|player| {
+            let player_type = match player {
+                Player::Black => &black_player,
+                Player::White => &white_player,
+            };
+            matches!(player_type, PlayerType::Human)
+        })
+        .collect();
+    if !human_players.is_empty() {
+        print_review(&game_stats.review_moves(&human_players, REVIEW_DEPTH));
+    }
+
     // グラフの生成
     println!("\nグラフを生成中...");
     match plot_game_statistics(&game_stats, &game_result) {
         Ok(()) => println!("グラフ生成が完了しました！"),
+        Err(PlotError::EmptyData) => println!("記録された手がないためグラフは生成しませんでした"),
+        Err(PlotError::Io(e)) => println!("グラフファイルを書き込めませんでした: {}", e),
         Err(e) => println!("グラフ生成エラー: {}", e),
     }
 }
@@ -213,7 +296,13 @@ fn select_player_types() -> (PlayerType, PlayerType) {
 fn player_type_to_string(player_type: &PlayerType) -> String {
     match player_type {
         PlayerType::Human => String::from("人間"),
-        PlayerType::AI { level, tt: _ } => {
+        PlayerType::AI { config, .. } if config.move_time_limit.is_some() => {
+            format!(
+                "AI (ブリッツ - 1手{}ms)",
+                config.move_time_limit.unwrap_or_default().as_millis()
+            )
+        }
+        PlayerType::AI { level, .. } => {
             let difficulty = match level {
                 1 => "初級",
                 3 => "中級",
@@ -242,7 +331,19 @@ fn select_single_player_type(player_name: &str) -> PlayerType {
         println!("7: AI レベル11（超超超上級）");
         println!("8: AI レベル13（超超超超上級）");
         println!("9: カスタム（任意の深さを指定）");
-        print!("選択 (1-9): ");
+        for (i, personality) in Personality::ALL.iter().enumerate() {
+            println!(
+                "{}: AI {}（レベル{}）",
+                PERSONALITY_MENU_START + i,
+                personality.name(),
+                PERSONALITY_LEVEL
+            );
+        }
+        println!("0: AI ブリッツ（1手{}ms）", BLITZ_TIME_LIMIT.as_millis());
+        print!(
+            "選択 (0-{}): ",
+            PERSONALITY_MENU_START + Personality::ALL.len() - 1
+        );
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
@@ -250,49 +351,15 @@ fn select_single_player_type(player_name: &str) -> PlayerType {
             Ok(_) => {
                 // 入力を処理
                 match input.trim() {
+                    "0" => return PlayerType::new_ai(BLITZ_LEVEL, EngineConfig::blitz()),
                     "1" => return PlayerType::Human,
-                    "2" => {
-                        return PlayerType::AI {
-                            level: 1,
-                            tt: RefCell::new(HashMap::default()),
-                        }
-                    }
-                    "3" => {
-                        return PlayerType::AI {
-                            level: 3,
-                            tt: RefCell::new(HashMap::default()),
-                        }
-                    }
-                    "4" => {
-                        return PlayerType::AI {
-                            level: 5,
-                            tt: RefCell::new(HashMap::default()),
-                        }
-                    }
-                    "5" => {
-                        return PlayerType::AI {
-                            level: 7,
-                            tt: RefCell::new(HashMap::default()),
-                        }
-                    }
-                    "6" => {
-                        return PlayerType::AI {
-                            level: 9,
-                            tt: RefCell::new(HashMap::default()),
-                        }
-                    }
-                    "7" => {
-                        return PlayerType::AI {
-                            level: 11,
-                            tt: RefCell::new(HashMap::default()),
-                        }
-                    }
-                    "8" => {
-                        return PlayerType::AI {
-                            level: 13,
-                            tt: RefCell::new(HashMap::default()),
-                        }
-                    }
+                    "2" => return PlayerType::new_ai(1, EngineConfig::default()),
+                    "3" => return PlayerType::new_ai(3, EngineConfig::default()),
+                    "4" => return PlayerType::new_ai(5, EngineConfig::default()),
+                    "5" => return PlayerType::new_ai(7, EngineConfig::default()),
+                    "6" => return PlayerType::new_ai(9, EngineConfig::default()),
+                    "7" => return PlayerType::new_ai(11, EngineConfig::default()),
+                    "8" => return PlayerType::new_ai(13, EngineConfig::default()),
                     "9" => {
                         // カスタム深さの入力
                         loop {
@@ -304,10 +371,10 @@ fn select_single_player_type(player_name: &str) -> PlayerType {
                                 Ok(_) => match depth_input.trim().parse::<usize>() {
                                     Ok(depth) if depth >= 1 && depth <= 20 => {
                                         println!("カスタム AI (深さ {}) を選択しました", depth);
-                                        return PlayerType::AI {
-                                            level: depth + 1,
-                                            tt: RefCell::new(HashMap::default()),
-                                        };
+                                        return PlayerType::new_ai(
+                                            depth + 1,
+                                            EngineConfig::default(),
+                                        );
                                     }
                                     Ok(_) => println!("深さは 1-20 の範囲で入力してください。"),
                                     Err(_) => println!("無効な入力です。数字を入力してください。"),
@@ -320,7 +387,27 @@ fn select_single_player_type(player_name: &str) -> PlayerType {
                         println!("プログラムを終了します。");
                         std::process::exit(0);
                     }
-                    _ => println!("無効な選択です。1-9の数字を入力してください。"),
+                    other => {
+                        // 個性派AIの選択
+                        let personality = other
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|n| n.checked_sub(PERSONALITY_MENU_START))
+                            .and_then(|i| Personality::ALL.get(i));
+                        match personality {
+                            Some(personality) => {
+                                println!("{} AI を選択しました", personality.name());
+                                return PlayerType::new_ai(
+                                    PERSONALITY_LEVEL,
+                                    personality.engine_config(),
+                                );
+                            }
+                            None => println!(
+                                "無効な選択です。0-{}の数字を入力してください。",
+                                PERSONALITY_MENU_START + Personality::ALL.len() - 1
+                            ),
+                        }
+                    }
                 }
             }
             Err(_) => {
@@ -338,14 +425,8 @@ fn run_quick_ai_game() {
     println!("==========================");
 
     // AI レベル20 vs AI レベル20 の短い試合
-    let black_player = PlayerType::AI {
-        level: 20,
-        tt: RefCell::new(HashMap::default()),
-    };
-    let white_player = PlayerType::AI {
-        level: 20,
-        tt: RefCell::new(HashMap::default()),
-    };
+    let black_player = PlayerType::new_ai(20, EngineConfig::default());
+    let white_player = PlayerType::new_ai(20, EngineConfig::default());
 
     println!("AI (レベル20) vs AI (レベル20) で対戦します...");
 
@@ -374,7 +455,7 @@ fn run_quick_ai_game() {
         };
 
         let start = Instant::now();
-        let (success, move_position, evaluation) =
+        let (success, move_position, evaluation, timing) =
             player_type.play_turn(&mut board, current_player);
 
         if success {
@@ -389,6 +470,10 @@ fn run_quick_ai_game() {
                 white_count,
                 evaluation,
             );
+            game_stats.record_mobility(&board);
+            if let Some(timing) = timing {
+                game_stats.record_timing(timing);
+            }
 
             move_count += 1;
             current_player = current_player.opponent();
@@ -423,16 +508,233 @@ fn run_quick_ai_game() {
     println!("\nグラフを生成中...");
     match plot_game_statistics(&game_stats, &game_result) {
         Ok(()) => println!("✓ グラフ生成が完了しました！"),
+        Err(PlotError::EmptyData) => println!("❌ 記録された手がないためグラフを生成できません"),
+        Err(PlotError::Io(e)) => println!("❌ グラフファイルを書き込めませんでした: {}", e),
         Err(e) => println!("❌ グラフ生成エラー: {}", e),
     }
 }
 
+/// 手番の指定（"X"/"O", "black"/"white", "黒"/"白"）を読む
+fn parse_side(text: &str) -> Option<Player> {
+    match text.to_lowercase().as_str() {
+        "x" | "b" | "black" | "黒" => Some(Player::Black),
+        "o" | "w" | "white" | "白" => Some(Player::White),
+        _ => None,
+    }
+}
+
+/// 局面を1回だけ解析して結果を表示する
+/// 引数: "<盤面の文字列>" <手番> <深さ>。戻り値は終了コード
+fn run_analyze(args: &[String]) -> i32 {
+    let [position, side, depth] = args else {
+        eprintln!("使い方: bitothello analyze \"<盤面の文字列(64文字)>\" <X|O> <深さ>");
+        return 2;
+    };
+
+    let Some(player) = parse_side(side) else {
+        eprintln!("❌ 手番は X(黒) か O(白) で指定してください: '{}'", side);
+        return 1;
+    };
+    let position = format!("{} {}", position, player.to_char());
+    let board = match BitBoard::from_position_string(&position) {
+        Ok((board, _)) => board,
+        Err(e) => {
+            eprintln!("❌ 盤面を読めません: {}", e);
+            return 1;
+        }
+    };
+    let depth = match depth.parse::<usize>() {
+        Ok(depth) if (1..=MAX_ANALYZE_DEPTH).contains(&depth) => depth,
+        _ => {
+            eprintln!(
+                "❌ 深さは1〜{}の数で指定してください: '{}'",
+                MAX_ANALYZE_DEPTH, depth
+            );
+            return 1;
+        }
+    };
+
+    println!("{}", board);
+    let analysis = board.analyze_detailed(player, depth);
+    let (Some(best_move), Some(score)) = (analysis.best_move, analysis.score) else {
+        println!("{}は打てる手がありません（パス）", player.to_string());
+        return 0;
+    };
+
+    let pv: Vec<String> = analysis
+        .pv
+        .iter()
+        .map(|mv| mv.map_or_else(|| "パス".to_string(), notation))
+        .collect();
+    let (row, col) = pos_to_rc(best_move);
+    println!("最善手: {} ({},{})", notation(best_move), row, col);
+    println!("評価値: {} ({}から見た値)", score, player.to_string());
+    println!("勝率(目安): {:.1}%", win_probability(score) * 100.0);
+    println!("読み筋: {}", pv.join(" "));
+    println!(
+        "探索ノード数: {} (読んだ深さ {})",
+        analysis.stats.nodes, analysis.stats.depth_reached
+    );
+    if let Some(nps) = analysis.stats.nodes_per_second() {
+        println!("探索速度: {:.0} ノード/秒", nps);
+    }
+    0
+}
+
+/// 自己対戦で評価関数の学習用データセットを作る
+/// 引数: [対局数] [出力ファイル]
+fn run_selfplay_dataset(args: &[String]) {
+    let defaults = SelfPlayOptions::default();
+    let options = SelfPlayOptions {
+        games: args
+            .first()
+            .and_then(|games| games.parse().ok())
+            .unwrap_or(defaults.games),
+        ..defaults
+    };
+    let path = args
+        .get(1)
+        .map(String::as_str)
+        .unwrap_or("selfplay_dataset.csv");
+
+    println!("自己対戦を{}局行います...", options.games);
+    let start = Instant::now();
+    match selfplay::generate_dataset(&options, path) {
+        Ok(rows) => println!(
+            "✓ {}局面を {} に保存しました（{:.1}秒）",
+            rows,
+            path,
+            start.elapsed().as_secs_f64()
+        ),
+        Err(e) => println!("❌ データセットを書き込めませんでした: {}", e),
+    }
+}
+
+/// フォルダに保存した対局（to_bytes の形式）をまとめて集計する
+/// 引数: <フォルダ> [--no-review]（--no-review で悪手の検討を省く）
+#[cfg(feature = "serde")]
+fn run_archive_summary(args: &[String]) {
+    let Some(dir) = args.first() else {
+        println!("使い方: archive-summary <フォルダ> [--no-review]");
+        return;
+    };
+    let review_depth = if args.iter().any(|arg| arg == "--no-review") {
+        None
+    } else {
+        Some(REVIEW_DEPTH)
+    };
+
+    let (games, failures) = match bitothello::stats::archive_summary::load_games_from_dir(dir) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            println!("❌ フォルダを読み込めませんでした: {}", e);
+            return;
+        }
+    };
+    for (path, e) in &failures {
+        println!("⚠ {} を読み込めませんでした: {}", path.display(), e);
+    }
+
+    let summary = ArchiveSummary::from_games(&games, review_depth);
+    summary.print();
+
+    match plot_archive_summary(&summary, std::path::Path::new(".")) {
+        Ok(()) => {}
+        Err(PlotError::EmptyData) => println!("対局がないためグラフは生成しませんでした"),
+        Err(e) => println!("❌ グラフ生成エラー: {}", e),
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn run_archive_summary(_args: &[String]) {
+    println!("対局の集計には serde 機能が必要です（cargo run --features serde）");
+}
+
+/// 完全読みのAIを相手に、ランダムな終盤局面を最後まで打つ練習
+fn run_endgame_drill(args: &[String]) {
+    let empties = args
+        .first()
+        .and_then(|empties| empties.parse().ok())
+        .unwrap_or(DEFAULT_DRILL_EMPTIES);
+
+    let Some(mut drill) = EndgameDrill::random(&mut rand::thread_rng(), empties) else {
+        println!("練習用の局面を作れませんでした");
+        return;
+    };
+
+    println!("==========================");
+    println!("    終盤の練習");
+    println!("==========================");
+    println!(
+        "あなたは{}({})です。空き{}マスを完全読みのAIと打ち切ってください。",
+        drill.human.to_string(),
+        drill.human.to_char(),
+        drill.start.count_empty()
+    );
+    println!("局面: {}", drill.start.to_position_string_for(drill.human));
+
+    let human = PlayerType::Human;
+    let mut board = drill.start;
+    let mut player = drill.human;
+    println!("{}", board);
+
+    while !board.is_game_over() {
+        if board.get_legal_moves(player) == 0 {
+            println!(
+                "{}は打てる場所がありません。パスします。",
+                player.to_string()
+            );
+            player = player.opponent();
+            continue;
+        }
+
+        if player == drill.human {
+            let before = board;
+            let (success, move_position, _, _) = human.play_turn(&mut board, player);
+            let Some((row, col)) = move_position.filter(|_| success) else {
+                continue;
+            };
+            drill.record_human_move(&before, rc_to_pos(row, col));
+        } else if let Some((pos, _)) = board.solve_endgame_best_move(player) {
+            board.make_move(pos, player);
+            println!("AIは{}に置きました", notation(pos));
+        }
+
+        println!("{}", board);
+        player = player.opponent();
+    }
+
+    let result = drill.result(&board);
+    println!("\n==========================");
+    println!("      練習の結果");
+    println!("==========================");
+    println!("最善の石差: {:+}", result.optimal_diff);
+    println!("あなたの石差: {:+}", result.achieved_diff);
+    if result.is_perfect() {
+        println!("★ 最善の結果を出せました！");
+    } else {
+        println!(
+            "最善より{}石少ない結果でした",
+            result.optimal_diff - result.achieved_diff
+        );
+        for mistake in drill.mistakes() {
+            println!(
+                "・{}ではなく{}が最善でした（{}石の損）",
+                notation(mistake.played),
+                notation(mistake.best_move),
+                mistake.loss
+            );
+            println!("{}", mistake.board);
+        }
+    }
+}
+
 /// GUI版のゲームを実行
 fn run_gui() {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1000.0, 700.0])
-            .with_min_inner_size([800.0, 600.0])
+            .with_min_inner_size(MIN_WINDOW_SIZE)
             .with_title("ビット オセロ"),
         ..Default::default()
     };
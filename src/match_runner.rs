@@ -0,0 +1,45 @@
+use crate::board::BitBoard;
+use crate::player::{Player, PlayerType};
+use crate::selfplay::fast_forward;
+use crate::stats::{GameResult, GameStats};
+
+/// 画面も標準出力も使わずに、初期配置から終局まで AI 同士で対局させる（設定の比較用）
+/// パスも含めて両者が打てなくなるまで打つ。どちらかが人間なら対局できないので None
+pub fn run_match(black: &PlayerType, white: &PlayerType) -> Option<GameResult> {
+    let mut board = BitBoard::new();
+    let mut player = Player::Black;
+    let mut stats = GameStats::new();
+
+    if !fast_forward(&mut board, &mut player, [black, white], &mut stats) {
+        return None;
+    }
+
+    let (black_count, white_count) = board.count_all_discs();
+    Some(stats.finalize_game(board.get_winner(), black_count, white_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::EngineConfig;
+
+    #[test]
+    fn ai_match_finishes_with_consistent_counts() {
+        let black = PlayerType::new_ai(1, EngineConfig::default());
+        let white = PlayerType::new_ai(2, EngineConfig::default());
+        let result = run_match(&black, &white).unwrap();
+
+        let total = result.black_final_count + result.white_final_count;
+        assert!(total <= 64 && total > 4);
+        // 置いた石は手数より4つ多い（初期配置の4石）
+        assert_eq!(total as usize, result.total_moves + 4);
+        let expected_winner = match result.black_final_count.cmp(&result.white_final_count) {
+            std::cmp::Ordering::Greater => Some(Player::Black),
+            std::cmp::Ordering::Less => Some(Player::White),
+            std::cmp::Ordering::Equal => None,
+        };
+        assert_eq!(result.winner, expected_winner);
+
+        assert!(run_match(&black, &PlayerType::Human).is_none());
+    }
+}
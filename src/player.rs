@@ -1,14 +1,25 @@
-use crate::board::BitBoard;
+use crate::ai::{
+    estimate_search_time, tt_memory_estimate, EngineConfig, EvalParams, MoveTiming, SearchStats,
+    BYTES_PER_MB, SEARCH_TIME_WARNING,
+};
+use crate::board::{pos_to_rc, rc_to_pos, BitBoard, Square};
 use fxhash::FxHashMap;
 use std::cell::RefCell;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Player {
     Black,
     White,
 }
 
 impl Player {
+    /// 両方のプレイヤー（黒、白の順）
+    #[inline(always)]
+    pub fn all() -> [Player; 2] {
+        [Player::Black, Player::White]
+    }
+
     /// 相手のプレイヤーを返す
     pub fn opponent(&self) -> Self {
         match self {
@@ -17,6 +28,15 @@ impl Player {
         }
     }
 
+    /// 配列の添字（黒が0、白が1）
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        match self {
+            Player::Black => 0,
+            Player::White => 1,
+        }
+    }
+
     /// 文字列表現を返す
     pub fn to_string(&self) -> &'static str {
         match self {
@@ -34,11 +54,20 @@ impl Player {
     }
 }
 
+/// 1手の結果（成功したかどうか, 手の位置, AI評価値, AIの思考時間の内訳）
+pub type TurnResult = (
+    bool,
+    Option<(usize, usize)>,
+    Option<i32>,
+    Option<MoveTiming>,
+);
+
 pub enum PlayerType {
     Human,
     AI {
         level: usize,
         tt: RefCell<FxHashMap<(u64, u64, u8), Entry>>, //black, white, playerの順
+        config: Box<EngineConfig>, // 大きいので、人間のプレイヤーの分まで場所を取らないよう箱に入れる
     },
 }
 
@@ -46,9 +75,10 @@ impl Clone for PlayerType {
     fn clone(&self) -> Self {
         match self {
             PlayerType::Human => PlayerType::Human,
-            PlayerType::AI { level, tt } => PlayerType::AI {
+            PlayerType::AI { level, tt, config } => PlayerType::AI {
                 level: *level,
                 tt: RefCell::new(tt.borrow().clone()),
+                config: config.clone(),
             },
         }
     }
@@ -77,24 +107,69 @@ UpperBound
 「これ以下の評価しかない」（例：αカットで途中終了）
  */
 
+/// 空きマス数に応じたAIの探索深さ（残りの手数より深くは読まない）
+pub fn adaptive_depth(level: usize, empty_count: usize) -> usize {
+    let depth = match empty_count {
+        // 超終盤：残りの手数まで読み切る
+        0..=8 => level + 6,
+        // 終盤：深く読む
+        9..=16 => std::cmp::min(level + 3, 20),
+        // 中盤：標準的な深度
+        17..=40 => level,
+        // 序盤：効率重視
+        _ => std::cmp::max(level.saturating_sub(1), 1),
+    };
+    depth.min(empty_count).max(1)
+}
+
+/// AIの手の表示に添える「実際に読んだ深さ(読もうとした深さ)」
+fn depth_summary(stats: &SearchStats, max_depth: usize) -> String {
+    format!("深度:{}(最大{})", stats.depth_reached, max_depth)
+}
+
 impl PlayerType {
+    /// 空の置換表を持つAIプレイヤーを作成
+    pub fn new_ai(level: usize, config: EngineConfig) -> Self {
+        PlayerType::AI {
+            level,
+            tt: RefCell::new(FxHashMap::default()),
+            config: Box::new(config),
+        }
+    }
+
+    /// 対局の合間にAIの状態を初期化する（置換表を空にする）
+    /// キラー手と履歴表は探索ごとに作り直しているので、ここで消すものはない
+    pub fn reset(&self) {
+        if let PlayerType::AI { tt, .. } = self {
+            tt.borrow_mut().clear();
+        }
+    }
+
+    /// レベルと設定が同じプレイヤーかどうか（置換表の中身は比べない）
+    pub fn same_settings(&self, other: &PlayerType) -> bool {
+        match (self, other) {
+            (PlayerType::Human, PlayerType::Human) => true,
+            (
+                PlayerType::AI { level, config, .. },
+                PlayerType::AI {
+                    level: other_level,
+                    config: other_config,
+                    ..
+                },
+            ) => level == other_level && config == other_config,
+            _ => false,
+        }
+    }
+
     /// 指定されたプレイヤータイプでゲームを実行する
-    /// 戻り値: (成功したかどうか, 手の位置, AI評価値)
-    pub fn play_turn(
-        &self,
-        board: &mut BitBoard,
-        player: Player,
-    ) -> (bool, Option<(usize, usize)>, Option<i32>) {
+    pub fn play_turn(&self, board: &mut BitBoard, player: Player) -> TurnResult {
         match self {
             PlayerType::Human => {
-                println!("行(0-7) 列(0-7) の形式で入力。例: 3 2");
+                println!("行(0-7) 列(0-7) か座標(a1-h8)の形式で入力。例: 3 2 / c4");
                 println!("ヘルプ: 'h'または'help', ゲーム終了: 'q'または'quit'");
 
                 // 合法手の位置リストを用意（ヘルプ表示用）
-                let legal_pos_list: Vec<(usize, usize)> = (0..64)
-                    .filter(|&pos| (board.get_legal_moves(player) & (1u64 << pos)) != 0)
-                    .map(|pos| (pos / 8, pos % 8))
-                    .collect();
+                let legal_move_list = board.legal_moves_detailed(player);
 
                 loop {
                     let mut input = String::new();
@@ -112,55 +187,78 @@ impl PlayerType {
                                     println!("--ヘルプ--");
                                     println!("・行と列の番号を半角スペースで区切って入力します。");
                                     println!("・例: '2 3' は行2, 列3に石を置きます。");
+                                    println!(
+                                        "・'f5' のように列(a-h)と行(1-8)の座標でも入力できます。"
+                                    );
                                     println!("・現在の合法手リスト:");
-                                    for (i, &(row, col)) in legal_pos_list.iter().enumerate() {
-                                        print!("({},{}) ", row, col);
+                                    for (i, (pos, notation, flips)) in
+                                        legal_move_list.iter().enumerate()
+                                    {
+                                        let (row, col) = pos_to_rc(*pos);
+                                        print!("({},{})={}[{}枚] ", row, col, notation, flips);
                                         if (i + 1) % 8 == 0 {
                                             println!();
                                         }
                                     }
-                                    if legal_pos_list.len() % 8 != 0 {
+                                    if legal_move_list.len() % 8 != 0 {
                                         println!();
                                     }
+                                    // 簡易評価が最も高い手を表示
+                                    if let Some((pos, notation, _)) =
+                                        legal_move_list.iter().max_by_key(|(pos, _, _)| {
+                                            board.evaluate_move(
+                                                *pos,
+                                                player,
+                                                &EvalParams::default(),
+                                            )
+                                        })
+                                    {
+                                        let (row, col) = pos_to_rc(*pos);
+                                        println!(
+                                            "・簡易評価でのおすすめ: ({},{}) {}",
+                                            row, col, notation
+                                        );
+                                    }
                                     continue;
                                 }
                                 _ => {}
                             }
 
-                            // 通常の手の入力を解析
-                            let parts: Vec<&str> = input.split_whitespace().collect();
-                            if parts.len() != 2 {
-                                println!(
-                                    "無効な入力形式です。行(0-7) 列(0-7) の形式で入力してください。"
-                                );
-                                continue;
-                            }
-
-                            let row: Result<usize, _> = parts[0].parse();
-                            let col: Result<usize, _> = parts[1].parse();
-
-                            if let (Ok(row), Ok(col)) = (row, col) {
-                                if row >= 8 || col >= 8 {
+                            // 通常の手の入力を解析（"f5" のような座標表記か、"行 列"）
+                            let (row, col) = if let Some(square) = Square::from_notation(&input) {
+                                square.into()
+                            } else {
+                                let parts: Vec<&str> = input.split_whitespace().collect();
+                                if parts.len() != 2 {
                                     println!(
-                                        "無効な座標です。行と列は0-7の範囲で指定してください。"
+                                        "無効な入力形式です。行(0-7) 列(0-7) か座標(a1-h8)の形式で入力してください。"
                                     );
                                     continue;
                                 }
 
-                                let pos = row * 8 + col;
-                                if board.is_legal_move(pos, player) {
-                                    println!("{}を({},{})に置きます", player.to_string(), row, col);
-                                    board.make_move(pos, player);
-                                    return (true, Some((row, col)), None);
-                                } else {
-                                    println!("そこには置けません。別の場所を選んでください。");
-                                    println!(
-                                        "'h'または'help'と入力すると合法手の一覧を表示します。"
-                                    );
-                                    continue;
+                                match (parts[0].parse::<usize>(), parts[1].parse::<usize>()) {
+                                    (Ok(row), Ok(col)) if row < 8 && col < 8 => (row, col),
+                                    (Ok(_), Ok(_)) => {
+                                        println!(
+                                            "無効な座標です。行と列は0-7の範囲で指定してください。"
+                                        );
+                                        continue;
+                                    }
+                                    _ => {
+                                        println!("無効な入力です。数字を入力してください。");
+                                        continue;
+                                    }
                                 }
+                            };
+
+                            let pos = rc_to_pos(row, col);
+                            if board.is_legal_move(pos, player) {
+                                println!("{}を({},{})に置きます", player.to_string(), row, col);
+                                board.make_move(pos, player);
+                                return (true, Some((row, col)), None, None);
                             } else {
-                                println!("無効な入力です。数字を入力してください。");
+                                println!("そこには置けません。別の場所を選んでください。");
+                                println!("'h'または'help'と入力すると合法手の一覧を表示します。");
                                 continue;
                             }
                         }
@@ -171,53 +269,47 @@ impl PlayerType {
                     }
                 }
             }
-            PlayerType::AI { level, tt } => {
+            PlayerType::AI { level, tt, config } => {
                 let start_thinking = std::time::Instant::now();
 
                 // 適応的深度調整（最適化版）
                 let empty_count = 64 - (board.black | board.white).count_ones() as usize;
-                let total_moves = 64 - empty_count;
 
-                let adaptive_level = match empty_count {
-                    0..=8 => {
-                        // 超終盤：完全読み
-                        std::cmp::min(empty_count + 4, *level + 6)
-                    }
-                    9..=16 => {
-                        // 終盤：深く読む
-                        std::cmp::min(*level + 3, 20)
-                    }
-                    17..=40 => {
-                        // 中盤：標準的な深度
-                        *level
-                    }
-                    _ => {
-                        // 序盤：効率重視
-                        std::cmp::max(*level - 1, 1)
+                let adaptive_level = adaptive_depth(*level, empty_count);
+
+                // 最善手探索（空きマスが閾値以下、または残りの手数まで読み切れるなら完全読み）
+                // 完全読みは時間制限を見ないので、時間制限付きのAIは閾値以下の場合だけにする
+                let exact_solve = config.should_solve_exactly(empty_count as u32)
+                    || (config.move_time_limit.is_none() && adaptive_level >= empty_count);
+                let search_config = if exact_solve {
+                    EngineConfig {
+                        endgame_solve_empties: config.endgame_solve_empties.max(empty_count as u32),
+                        ..**config
                     }
+                } else {
+                    **config
                 };
 
-                // メモリクリーンアップの頻度を調整
+                // 時間がかかりそうな深さなら事前に警告（時間制限付きなら不要）
+                let estimate = estimate_search_time(adaptive_level, empty_count as u32);
+                if config.move_time_limit.is_none()
+                    && !exact_solve
+                    && estimate > SEARCH_TIME_WARNING
                 {
-                    let mut tt_borrowed = tt.borrow_mut();
-                    if tt_borrowed.len() > 5_000_000 && total_moves % 8 == 0 {
-                        // 8手ごとにクリーンアップ
-                        let retain_count = 2_000_000;
-                        let mut entries: Vec<_> =
-                            tt_borrowed.iter().map(|(k, v)| (*k, *v)).collect();
-                        entries.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.depth));
-
-                        tt_borrowed.clear();
-                        for (key, entry) in entries.into_iter().take(retain_count) {
-                            tt_borrowed.insert(key, entry);
-                        }
-                    }
+                    println!(
+                        "⚠ 深度{}の探索は時間がかかる可能性があります（推定{:.1}秒）。レベルを下げるか時間制限を検討してください。",
+                        adaptive_level,
+                        estimate.as_secs_f64()
+                    );
                 }
-
-                // 最善手探索
-                let (pos, evaluation) = {
+                let (pos, evaluation, stats) = {
                     let mut tt_borrowed = tt.borrow_mut();
-                    board.find_best_move_with_tt(player, adaptive_level, &mut *tt_borrowed)
+                    board.find_best_move_with_stats(
+                        player,
+                        adaptive_level,
+                        &mut *tt_borrowed,
+                        &search_config,
+                    )
                 };
 
                 if let Some(pos) = pos {
@@ -230,36 +322,240 @@ impl PlayerType {
                         _ => std::time::Duration::from_millis(1000),
                     };
 
-                    if elapsed < min_thinking_time {
+                    // 時間制限付きのAIと、待ち時間を無効にしたAIは待たせない
+                    let padding_time = if config.pad_thinking_time
+                        && config.move_time_limit.is_none()
+                        && elapsed < min_thinking_time
+                    {
                         std::thread::sleep(min_thinking_time - elapsed);
-                    }
+                        start_thinking.elapsed() - elapsed
+                    } else {
+                        std::time::Duration::ZERO
+                    };
 
-                    let row = pos / 8;
-                    let col = pos % 8;
+                    // 探索以外（深さの調整など）にかかった時間は探索時間に含める
+                    let timing = MoveTiming {
+                        search_time: elapsed.saturating_sub(stats.timing.cleanup_time),
+                        cleanup_time: stats.timing.cleanup_time,
+                        padding_time,
+                    };
+
+                    let (row, col) = pos_to_rc(pos);
 
                     // 詳細情報の表示（デバッグ用）
-                    if *level >= 8 {
+                    // 指定レベルと実際に読んだ深さ（局面に応じて深さを調整するため異なることがある）
+                    if exact_solve {
+                        println!(
+                            "{}(AI Lv.{})は({},{})に置きました [完全読み(空き{}≦{}), 評価:{:?}]",
+                            player.to_string(),
+                            level,
+                            row,
+                            col,
+                            empty_count,
+                            search_config.endgame_solve_empties,
+                            evaluation
+                        );
+                    } else if *level >= 8 {
                         println!(
-                            "{}(AI Lv.{})は({},{})に置きました [深度:{}, 評価:{:?}, 思考時間:{:.2}s]",
+                            "{}(AI Lv.{})は({},{})に置きました [{}, 評価:{:?}, 思考時間:{:.2}s, ノード数:{}({:.0}nps), 置換表:{}件(約{:.1}MB)]",
                             player.to_string(),
-                            adaptive_level,
+                            level,
                             row,
                             col,
-                            adaptive_level,
+                            depth_summary(&stats, adaptive_level),
                             evaluation,
-                            start_thinking.elapsed().as_secs_f64()
+                            start_thinking.elapsed().as_secs_f64(),
+                            stats.nodes,
+                            stats.nodes_per_second().unwrap_or(0.0),
+                            stats.tt_entries,
+                            tt_memory_estimate(&tt.borrow()) as f64 / BYTES_PER_MB
                         );
                     } else {
-                        println!("{}(AI)は({},{})に置きました", player.to_string(), row, col);
+                        println!(
+                            "{}(AI Lv.{})は({},{})に置きました [{}]",
+                            player.to_string(),
+                            level,
+                            row,
+                            col,
+                            depth_summary(&stats, adaptive_level)
+                        );
                     }
 
                     board.make_move(pos, player);
-                    (true, Some((row, col)), evaluation)
+                    (true, Some((row, col)), evaluation, Some(timing))
                 } else {
                     println!("{}(AI)はパスします", player.to_string());
-                    (false, None, None)
+                    (false, None, None, None)
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{BLITZ_LEVEL, BLITZ_TIME_LIMIT};
+    use crate::selftest::random_position_from_seed;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn all_yields_both_players_in_index_order() {
+        let players = Player::all();
+        assert_eq!(players, [Player::Black, Player::White]);
+        for (i, player) in players.into_iter().enumerate() {
+            assert_eq!(player.index(), i);
+            assert_eq!(player.opponent(), players[1 - i]);
+            assert_eq!(player.opponent().opponent(), player);
+        }
+    }
+
+    #[test]
+    fn reset_clears_tt_and_next_search_rebuilds_it() {
+        // ノード数の上限を付けて、時間で打ち切らないようにする
+        let config = EngineConfig {
+            max_nodes: Some(u64::MAX),
+            ..EngineConfig::default()
+        };
+        let ai = PlayerType::new_ai(4, config);
+        let PlayerType::AI { tt, .. } = &ai else {
+            unreachable!();
+        };
+
+        let mut board = BitBoard::new();
+        assert!(ai.play_turn(&mut board, Player::Black).0);
+        let first_len = tt.borrow().len();
+        assert!(first_len > 0);
+
+        ai.reset();
+        assert!(tt.borrow().is_empty());
+        assert!(ai.same_settings(&PlayerType::new_ai(4, config)));
+
+        // 初期化後に同じ局面を読むと、置換表が同じように作り直される
+        let mut board = BitBoard::new();
+        assert!(ai.play_turn(&mut board, Player::Black).0);
+        assert_eq!(tt.borrow().len(), first_len);
+        PlayerType::Human.reset();
+    }
+
+    #[test]
+    fn timing_breakdown_sums_to_elapsed_time() {
+        for pad_thinking_time in [false, true] {
+            let config = EngineConfig {
+                pad_thinking_time,
+                ..EngineConfig::default()
+            };
+            let ai = PlayerType::new_ai(1, config);
+            let mut board = BitBoard::new();
+
+            let start = Instant::now();
+            let (moved, _, _, timing) = ai.play_turn(&mut board, Player::Black);
+            let total = start.elapsed();
+            assert!(moved);
+
+            // 内訳の合計は手全体の時間とほぼ一致する（表示のための出力などの分だけ短い）
+            let timing = timing.unwrap();
+            let sum = timing.compute_time() + timing.padding_time;
+            assert!(sum <= total);
+            assert!(total - sum < Duration::from_millis(50), "{:?}", total - sum);
+
+            if pad_thinking_time {
+                // レベル1は最低200ミリ秒考えたように見せる
+                assert!(timing.padding_time > Duration::ZERO);
+                assert!(sum >= Duration::from_millis(200));
+            } else {
+                assert_eq!(timing.padding_time, Duration::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn adaptive_depth_never_exceeds_remaining_moves() {
+        for level in 1..=20 {
+            for empty_count in 1..=60 {
+                let depth = adaptive_depth(level, empty_count);
+                assert!((1..=empty_count).contains(&depth));
+            }
+        }
+    }
+
+    #[test]
+    fn few_empties_match_exact_solver() {
+        let (board, player) = random_position_from_seed(0, 8);
+        let (_, diff) = board.solve_endgame_best_move(player).unwrap();
+
+        // 完全読みの閾値を無効にしても、残りの手数まで読めるなら完全読みになる
+        let config = EngineConfig {
+            endgame_solve_empties: 0,
+            ..EngineConfig::default()
+        };
+        let ai = PlayerType::new_ai(4, config);
+        let mut played = board;
+        let (moved, _, evaluation, _) = ai.play_turn(&mut played, player);
+        assert!(moved);
+        assert_eq!(evaluation, Some(crate::ai::final_score(diff)));
+    }
+
+    #[test]
+    fn blitz_move_near_endgame_stays_within_time_limit() {
+        // 完全読みの閾値より空きマスが多い局面で、時間制限を無視した完全読みにならないこと
+        for empties in 18..=20 {
+            let (mut board, player) = random_position_from_seed(0, empties);
+            let ai = PlayerType::new_ai(BLITZ_LEVEL, EngineConfig::blitz());
+            let start = Instant::now();
+            let (moved, _, _, _) = ai.play_turn(&mut board, player);
+            let elapsed = start.elapsed();
+            assert!(moved);
+            assert!(
+                elapsed < BLITZ_TIME_LIMIT + Duration::from_millis(100),
+                "空き{}: {:?}",
+                empties,
+                elapsed
+            );
+        }
+    }
+
+    #[test]
+    fn blitz_moves_are_legal_and_fast() {
+        let ai = PlayerType::new_ai(BLITZ_LEVEL, EngineConfig::blitz());
+        for empties in [58, 44, 30] {
+            let (board, player) = random_position_from_seed(0, empties);
+            let mut played = board;
+            let start = Instant::now();
+            let (moved, position, _, _) = ai.play_turn(&mut played, player);
+            let elapsed = start.elapsed();
+
+            assert!(moved);
+            let (row, col) = position.unwrap();
+            assert!(board.is_legal_move(rc_to_pos(row, col), player));
+            assert!(
+                elapsed < BLITZ_TIME_LIMIT + Duration::from_millis(100),
+                "空き{}: {:?}",
+                empties,
+                elapsed
+            );
+        }
+    }
+
+    #[test]
+    fn depth_summary_reports_depth_reached() {
+        // ノード数の上限で途中の深さまでしか読めなかった探索
+        let (mut board, player) = random_position_from_seed(0, 44);
+        let config = EngineConfig {
+            max_nodes: Some(5_000),
+            ..EngineConfig::default()
+        };
+        let mut tt = FxHashMap::default();
+        let (_, _, stats) = board.find_best_move_with_stats(player, 10, &mut tt, &config);
+        assert!(
+            stats.depth_reached >= 1 && stats.depth_reached < 10,
+            "{:?}",
+            stats
+        );
+
+        assert_eq!(
+            depth_summary(&stats, 10),
+            format!("深度:{}(最大10)", stats.depth_reached)
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::ai::EngineConfig;
+use crate::board::{pos_to_rc, BitBoard};
+use crate::player::{Player, PlayerType};
+use crate::stats::GameStats;
+use fxhash::{FxHashMap, FxHashSet};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// 自己対戦の設定
+#[derive(Debug, Clone, Copy)]
+pub struct SelfPlayOptions {
+    pub games: usize,                // 対局数
+    pub seed: u64,                   // 乱数の種（対局ごとに seed + 番号を使う）
+    pub random_opening_moves: usize, // 序盤にランダムに打つ手数（対局ごとに序盤を変えるため）
+    pub level: usize,                // AIの探索深度
+    pub max_moves: usize,            // 1局の最大手数（これを超えたら打ち切る）
+    pub config: EngineConfig,
+}
+
+impl Default for SelfPlayOptions {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            seed: 0,
+            random_opening_moves: 8,
+            level: 3,
+            max_moves: 60,
+            config: EngineConfig::default(),
+        }
+    }
+}
+
+/// 自己対戦1局の記録
+#[derive(Debug, Clone)]
+pub struct SelfPlayGame {
+    pub positions: Vec<(BitBoard, Player)>, // 着手前の盤面と手番（パスの局面は含まない）
+    pub final_disc_diff: i32,               // 終局時の黒から見た石数差
+}
+
+/// 1局を最後まで（または最大手数まで）打つ
+fn play_game(options: &SelfPlayOptions, seed: u64) -> SelfPlayGame {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut tt = FxHashMap::default();
+    let mut board = BitBoard::new();
+    let mut player = Player::Black;
+    let mut positions = Vec::new();
+
+    while !board.is_game_over() && positions.len() < options.max_moves {
+        let moves = board.get_legal_move_positions(player);
+        if moves.is_empty() {
+            player = player.opponent();
+            continue;
+        }
+
+        positions.push((board, player));
+        let pos = if positions.len() <= options.random_opening_moves {
+            moves[rng.gen_range(0..moves.len())]
+        } else {
+            let (best_move, _) =
+                board.find_best_move_with_config(player, options.level, &mut tt, &options.config);
+            best_move.unwrap_or(moves[0])
+        };
+        board.make_move(pos, player);
+        player = player.opponent();
+    }
+
+    let (black_count, white_count) = board.count_all_discs();
+    SelfPlayGame {
+        positions,
+        final_disc_diff: black_count as i32 - white_count as i32,
+    }
+}
+
+/// 今の局面から終局まで、両者のAIに待ち時間なしで打たせる（パスも含めてすべて stats に記録する）
+/// players は黒・白の順。どちらかが人間なら何もせず false を返す
+pub fn fast_forward(
+    board: &mut BitBoard,
+    player: &mut Player,
+    players: [&PlayerType; 2],
+    stats: &mut GameStats,
+) -> bool {
+    if players
+        .iter()
+        .any(|player_type| matches!(player_type, PlayerType::Human))
+    {
+        return false;
+    }
+
+    while !board.is_game_over() {
+        let PlayerType::AI { level, tt, config } = players[player.index()] else {
+            return false;
+        };
+
+        let start = Instant::now();
+        let moves = board.get_legal_move_positions(*player);
+        let (position, evaluation, timing) = if moves.is_empty() {
+            (None, None, None) // パス
+        } else {
+            let (best_move, evaluation, stats) =
+                board.find_best_move_with_stats(*player, *level, &mut tt.borrow_mut(), config);
+            let pos = best_move
+                .filter(|pos| moves.contains(pos))
+                .unwrap_or(moves[0]);
+            board.make_move(pos, *player);
+            (Some(pos), evaluation, Some(stats.timing))
+        };
+
+        let (black_count, white_count) = board.count_all_discs();
+        stats.record_move(
+            *player,
+            position.map(pos_to_rc),
+            start.elapsed(),
+            black_count,
+            white_count,
+            evaluation,
+        );
+        stats.record_mobility(board);
+        if let Some(timing) = timing {
+            stats.record_timing(timing);
+        }
+        stats.record_snapshot(board);
+        *player = player.opponent();
+    }
+
+    true
+}
+
+/// 自己対戦をまとめて並列に実行する（結果は対局の番号順）
+pub fn run_self_play_batch(options: &SelfPlayOptions) -> Vec<SelfPlayGame> {
+    (0..options.games as u64)
+        .into_par_iter()
+        .map(|i| play_game(options, options.seed.wrapping_add(i)))
+        .collect()
+}
+
+/// 評価関数の学習用に、局面ごとの (盤面と手番, 最終結果) を CSV で書き出す
+/// 盤面と手番は BitBoard::to_position_string の形式（from_position_string で読み戻せる）
+/// 回転・反転で同じになる局面は最初の1つだけを残す。書き出した行数を返す
+pub fn write_dataset(games: &[SelfPlayGame], out: &mut impl Write) -> io::Result<usize> {
+    let mut seen = FxHashSet::default();
+    let mut rows = 0;
+
+    writeln!(out, "position,result")?;
+    for game in games {
+        for &(board, player) in &game.positions {
+            let (canonical, player) = board.canonical(player);
+            if !seen.insert((canonical, player)) {
+                continue;
+            }
+            // 結果は黒から見た最終石数差
+            writeln!(
+                out,
+                "{},{}",
+                canonical.to_position_string_for(player),
+                game.final_disc_diff
+            )?;
+            rows += 1;
+        }
+    }
+
+    Ok(rows)
+}
+
+/// 自己対戦を行い、学習用データセットをファイルに保存する
+pub fn generate_dataset(options: &SelfPlayOptions, path: impl AsRef<Path>) -> io::Result<usize> {
+    let games = run_self_play_batch(options);
+    let mut out = io::BufWriter::new(std::fs::File::create(path)?);
+    let rows = write_dataset(&games, &mut out)?;
+    out.flush()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::replay_transcript;
+
+    #[test]
+    fn dataset_rows_skip_symmetric_duplicates() {
+        // 初期局面と1手目の4通りはどれも回転・反転で同じ局面になるので、2局から2行だけ残る
+        let options = SelfPlayOptions {
+            games: 2,
+            random_opening_moves: 2,
+            level: 1,
+            max_moves: 2,
+            ..SelfPlayOptions::default()
+        };
+        let games = run_self_play_batch(&options);
+        assert_eq!(games.len(), 2);
+        assert!(games.iter().all(|game| game.positions.len() == 2));
+
+        let mut out = Vec::new();
+        assert_eq!(write_dataset(&games, &mut out).unwrap(), 2);
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 3); // 見出し行 + 2行
+
+        // 1局を反転しただけの対局を加えても、行は増えない
+        let game = run_self_play_batch(&SelfPlayOptions {
+            games: 1,
+            max_moves: 12,
+            ..options
+        })
+        .remove(0);
+        let mirrored = SelfPlayGame {
+            positions: game
+                .positions
+                .iter()
+                .map(|&(board, player)| (board.transform(6), player))
+                .collect(),
+            final_disc_diff: game.final_disc_diff,
+        };
+        let rows = write_dataset(std::slice::from_ref(&game), &mut io::sink()).unwrap();
+        assert!(rows > 2);
+        assert_eq!(
+            write_dataset(&[game, mirrored], &mut io::sink()).unwrap(),
+            rows
+        );
+    }
+
+    #[test]
+    fn fast_forward_reaches_game_over_with_consistent_stats() {
+        let (mut board, mut stats) = replay_transcript("f5d6c3d3c4f4f6f3e6e7").unwrap();
+        let mut player = Player::Black;
+        let recorded = stats.moves.len();
+
+        // 人間が入っている対局は進めない
+        let ai = PlayerType::new_ai(1, EngineConfig::default());
+        let human = PlayerType::Human;
+        assert!(!fast_forward(
+            &mut board,
+            &mut player,
+            [&ai, &human],
+            &mut stats
+        ));
+        assert_eq!(stats.moves.len(), recorded);
+
+        let other = PlayerType::new_ai(2, EngineConfig::default());
+        assert!(fast_forward(
+            &mut board,
+            &mut player,
+            [&ai, &other],
+            &mut stats
+        ));
+        assert!(board.is_game_over());
+        assert!(stats.moves.len() > recorded);
+
+        // 記録した手を初めから打ち直すと同じ終局図になり、石数も最後の記録と一致する
+        let (replayed, _) = replay_transcript(&stats.to_transcript()).unwrap();
+        assert_eq!(replayed, board);
+        let last = stats.moves.last().unwrap();
+        assert_eq!(
+            (last.black_count, last.white_count),
+            board.count_all_discs()
+        );
+        // パスは手数に数えない
+        let placed = stats.moves.iter().filter(|r| r.position.is_some()).count();
+        assert_eq!(last.move_number, placed);
+    }
+}
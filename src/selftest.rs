@@ -0,0 +1,510 @@
+use crate::ai::EngineConfig;
+use crate::board::{notation, pos_to_rc, rc_to_pos, BitBoard};
+use crate::player::Player;
+use crate::stats::replay_transcript;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Duration;
+
+/// 乱数の種（毎回同じ局面で検証する）
+const SELFTEST_SEED: u64 = 0x5e1f_7e57;
+
+/// 合法手生成などを検証するランダム局面の数
+const RANDOM_BOARDS: usize = 300;
+
+/// 初期配置からの perft の正しい値（1手先から順に）
+const PERFT_COUNTS: [u64; 7] = [4, 12, 56, 244, 1396, 8200, 55092];
+
+/// 探索を検証する局面（黒番）
+const SEARCH_CHECK_TRANSCRIPT: &str = "f5d6c3d3c4f4f6f3e6e7";
+
+/// MTD(f) と Aspiration Window を比べる深さ
+const MTDF_CHECK_DEPTHS: std::ops::RangeInclusive<usize> = 4..=6;
+
+/// ノード数を比べる深さ（浅い順）
+const NODE_CHECK_DEPTHS: [usize; 3] = [4, 5, 6];
+
+/// 温度付きの手選びで試す温度と乱数の種の数
+const SAMPLING_TEMPERATURE: f64 = 1000.0;
+const SAMPLING_SEEDS: u64 = 20;
+
+/// 完全読みを総当たりと比べる局面の空きマス数と局面の数
+const SOLVER_CHECK_EMPTIES: u32 = 9;
+const SOLVER_CHECK_POSITIONS: usize = 2;
+
+/// 1つのチェックの結果（失敗なら理由）
+pub type CheckResult = Result<(), String>;
+
+/// チェックの名前と関数
+pub type Check = (&'static str, fn() -> CheckResult);
+
+/// 自己診断のチェックの一覧（名前と関数）
+/// 各チェックは単独でも呼べる（tests/selftest.rs ですべてをテストとしても実行する）
+pub const CHECKS: [Check; 10] = [
+    ("合法手生成と裏返し", check_move_generation),
+    ("評価の対称性", check_symmetry_invariance),
+    ("フロンティア", check_frontier_discs),
+    ("確定石", check_stable_discs),
+    ("perft", check_perft),
+    ("手の実行と取り消し", check_make_unmake),
+    ("完全読み", check_endgame_solver),
+    ("MTD(f)", check_mtdf_matches_aspiration),
+    ("探索ノード数", check_node_counts),
+    ("温度付きの手選び", check_temperature_sampling),
+];
+
+/// すべてのチェックを実行して結果を表示し、すべて成功したかを返す
+pub fn run_selftest() -> bool {
+    let mut all_passed = true;
+    for (name, check) in CHECKS {
+        match check() {
+            Ok(()) => println!("✓ {}", name),
+            Err(reason) => {
+                println!("❌ {}: {}", name, reason);
+                all_passed = false;
+            }
+        }
+    }
+    all_passed
+}
+
+/// 初期配置から空きマスが empties になるまでランダムに打ち進めた局面（途中で終局したらそこで止める）
+/// 自己診断とユニットテストで共有する
+pub fn random_position(rng: &mut impl Rng, empties: u32) -> (BitBoard, Player) {
+    let mut board = BitBoard::new();
+    let mut player = Player::Black;
+    while board.count_empty() > empties && !board.is_game_over() {
+        let moves = board.get_legal_move_positions(player);
+        if !moves.is_empty() {
+            board.make_move(moves[rng.gen_range(0..moves.len())], player);
+        }
+        player = player.opponent();
+    }
+    (board, player)
+}
+
+/// 乱数の種 seed から打ち進めた、空きマスが empties でまだ終局していない局面
+/// （途中で終局したら同じ乱数で作り直すので、empties は1以上にする）
+pub fn random_position_from_seed(seed: u64, empties: u32) -> (BitBoard, Player) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    loop {
+        let (board, player) = random_position(&mut rng, empties);
+        if !board.is_game_over() {
+            return (board, player);
+        }
+    }
+}
+
+/// 序盤から終盤までの様々なランダム局面（毎回同じ局面になる）
+pub fn random_positions(count: usize) -> Vec<(BitBoard, Player)> {
+    let mut rng = StdRng::seed_from_u64(SELFTEST_SEED);
+    (0..count)
+        .map(|_| {
+            let empties = rng.gen_range(0..60);
+            random_position(&mut rng, empties)
+        })
+        .collect()
+}
+
+/// 8方向に1マスずつたどって裏返る石を求める（ビット演算の実装と比べるための素朴な実装）
+fn naive_flips(board: &BitBoard, pos: usize, player: Player) -> u64 {
+    let (own, opponent) = match player {
+        Player::Black => (board.black, board.white),
+        Player::White => (board.white, board.black),
+    };
+    let (row, col) = pos_to_rc(pos);
+    let mut flips = 0;
+    for (dr, dc) in [
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ] {
+        let mut line = 0u64;
+        let (mut r, mut c) = (row as i32 + dr, col as i32 + dc);
+        while (0..8).contains(&r) && (0..8).contains(&c) {
+            let bit = 1u64 << rc_to_pos(r as usize, c as usize);
+            if opponent & bit != 0 {
+                line |= bit;
+            } else {
+                if own & bit != 0 {
+                    flips |= line;
+                }
+                break;
+            }
+            r += dr;
+            c += dc;
+        }
+    }
+    flips
+}
+
+/// 合法手と裏返る石が素朴な実装と一致し、打った後の石数が合っているか
+pub fn check_move_generation() -> CheckResult {
+    for (board, player) in random_positions(RANDOM_BOARDS) {
+        let legal_moves = board.get_legal_moves(player);
+        // compute_flips は空きマスに打つ前提なので、空きマスだけを調べる
+        let empty = !(board.black | board.white);
+        for pos in (0..64).filter(|&pos| empty & (1u64 << pos) != 0) {
+            let expected = naive_flips(&board, pos, player);
+            if board.compute_flips(pos, player) != expected {
+                return Err(format!(
+                    "{} の {} で裏返る石が違います",
+                    board.to_position_string_for(player),
+                    pos
+                ));
+            }
+            if (legal_moves & (1u64 << pos) != 0) != (expected != 0) {
+                return Err(format!(
+                    "{} の {} の合法手判定が違います",
+                    board.to_position_string_for(player),
+                    pos
+                ));
+            }
+
+            let mut child = board;
+            if child.make_move(pos, player) {
+                let (black, white) = child.count_all_discs();
+                let (before_black, before_white) = board.count_all_discs();
+                if black + white != before_black + before_white + 1 || child.validate().is_err() {
+                    return Err(format!(
+                        "{} に {} を打った後の石数が合いません",
+                        board.to_position_string_for(player),
+                        pos
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 回転・反転した局面で静的評価と合法手の数が変わらないか
+pub fn check_symmetry_invariance() -> CheckResult {
+    for (board, player) in random_positions(RANDOM_BOARDS) {
+        let eval = board.static_eval(player);
+        let mobility = board.get_legal_moves(player).count_ones();
+        for (id, transformed) in board.symmetries().into_iter().enumerate() {
+            if transformed.static_eval(player) != eval {
+                return Err(format!(
+                    "{} を変換{}すると評価値が変わります",
+                    board.to_position_string_for(player),
+                    id
+                ));
+            }
+            if transformed.get_legal_moves(player).count_ones() != mobility {
+                return Err(format!(
+                    "{} を変換{}すると合法手の数が変わります",
+                    board.to_position_string_for(player),
+                    id
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 初期配置と作った局面で、フロンティアの石と潜在的な着手可能数が正しいか
+pub fn check_frontier_discs() -> CheckResult {
+    // 初期配置では中央の4石すべてが空きマスに接している
+    let board = BitBoard::new();
+    for player in Player::all() {
+        let (discs, _) = board.boards(player);
+        if board.get_frontier_discs(player) != discs {
+            return Err(format!("初期配置で{:?}のフロンティアが違います", player));
+        }
+    }
+
+    // 1行目だけが空き、2行目が白、残りが黒の局面では、白の2行目だけがフロンティア
+    let board = BitBoard {
+        black: 0xffff_ffff_ffff_0000,
+        white: 0x0000_0000_0000_ff00,
+    };
+    if board.get_frontier_discs(Player::White) != board.white {
+        return Err("白の2行目がフロンティアになりません".to_string());
+    }
+    if board.get_frontier_discs(Player::Black) != 0 {
+        return Err("空きマスに接していない黒の石がフロンティアになります".to_string());
+    }
+    if board.potential_mobility(Player::Black) != 8 || board.potential_mobility(Player::White) != 0
+    {
+        return Err(format!(
+            "潜在的な着手可能数が 黒{} 白{}（正しくは 黒8 白0）",
+            board.potential_mobility(Player::Black),
+            board.potential_mobility(Player::White)
+        ));
+    }
+    Ok(())
+}
+
+/// 埋まった上辺がすべて確定石になり、ランダム局面の確定石がどの手でも裏返らないか
+pub fn check_stable_discs() -> CheckResult {
+    // 上辺を黒白交互に埋め、中央は初期配置のまま
+    let initial = BitBoard::new();
+    let board = BitBoard {
+        black: initial.black | 0x55,
+        white: initial.white | 0xaa,
+    };
+    for player in Player::all() {
+        let (discs, _) = board.boards(player);
+        let top_edge = discs & 0xff;
+        if board.stable_discs(player) & 0xff != top_edge {
+            return Err(format!(
+                "埋まった上辺の{:?}の石が確定石になりません",
+                player
+            ));
+        }
+    }
+
+    for (board, player) in random_positions(RANDOM_BOARDS) {
+        let stable = board.stable_discs(Player::Black) | board.stable_discs(Player::White);
+        for side in Player::all() {
+            for pos in board.legal_moves_iter(side) {
+                if board.compute_flips(pos, side) & stable != 0 {
+                    return Err(format!(
+                        "{} で{:?}が{}に打つと確定石が裏返ります",
+                        board.to_position_string_for(player),
+                        side,
+                        notation(pos)
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 初期配置からの perft が既知の値と一致するか
+pub fn check_perft() -> CheckResult {
+    let board = BitBoard::new();
+    for (depth, &expected) in (1..).zip(PERFT_COUNTS.iter()) {
+        let nodes = board.perft(Player::Black, depth);
+        if nodes != expected {
+            return Err(format!(
+                "深さ{}で{}局面（正しくは{}）",
+                depth, nodes, expected
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 手を打って取り消すと元の盤面に戻るか（パスも含む）
+pub fn check_make_unmake() -> CheckResult {
+    for (board, player) in random_positions(RANDOM_BOARDS) {
+        let mut passed = board;
+        if let Some(pass) = passed.pass(player) {
+            passed.undo_move(pass);
+            if passed != board {
+                return Err(format!(
+                    "{} でパスを取り消すと盤面が変わります",
+                    board.to_position_string_for(player)
+                ));
+            }
+        }
+
+        for pos in board.legal_moves_iter(player) {
+            let mut child = board;
+            let Some(undo) = child.make_move_undo(pos, player) else {
+                return Err(format!(
+                    "{} に {} を打てません",
+                    board.to_position_string_for(player),
+                    pos
+                ));
+            };
+            if undo.pos() != Some(pos) || undo.flips() != board.compute_flips(pos, player) {
+                return Err(format!(
+                    "{} に {} を打った記録が違います",
+                    board.to_position_string_for(player),
+                    pos
+                ));
+            }
+            let expected = board.with_move(pos, player);
+            if expected != Some(child) {
+                return Err(format!(
+                    "{} に {} を打った盤面が make_move と違います",
+                    board.to_position_string_for(player),
+                    pos
+                ));
+            }
+
+            child.undo_move(undo);
+            if child != board {
+                return Err(format!(
+                    "{} で {} を取り消しても元に戻りません",
+                    board.to_position_string_for(player),
+                    pos
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 総当たりによる最終石差（完全読みの検証用）
+fn brute_force_diff(board: &BitBoard, player: Player, passed: bool) -> i32 {
+    let legal_moves = board.get_legal_moves(player);
+    if legal_moves == 0 {
+        if passed {
+            let (black, white) = board.count_all_discs();
+            let diff = black as i32 - white as i32;
+            return match player {
+                Player::Black => diff,
+                Player::White => -diff,
+            };
+        }
+        return -brute_force_diff(board, player.opponent(), true);
+    }
+
+    board
+        .legal_moves_iter(player)
+        .map(|pos| {
+            let mut child = *board;
+            child.make_move(pos, player);
+            -brute_force_diff(&child, player.opponent(), false)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// 完全読みの結果が総当たりと一致するか
+pub fn check_endgame_solver() -> CheckResult {
+    let mut rng = StdRng::seed_from_u64(SELFTEST_SEED);
+    let positions = std::iter::repeat_with(|| random_position(&mut rng, SOLVER_CHECK_EMPTIES))
+        .take(RANDOM_BOARDS)
+        .filter(|(board, player)| {
+            board.count_empty() == SOLVER_CHECK_EMPTIES && board.get_legal_moves(*player) != 0
+        })
+        .take(SOLVER_CHECK_POSITIONS);
+
+    let mut checked = 0;
+    for (board, player) in positions {
+        let expected = brute_force_diff(&board, player, false);
+        let mut solver_board = board;
+        let solved = solver_board.solve_endgame(player, SOLVER_CHECK_EMPTIES);
+        if solved != Some(expected) {
+            return Err(format!(
+                "{} の最終石差が {:?}（正しくは{}）",
+                board.to_position_string_for(player),
+                solved,
+                expected
+            ));
+        }
+        checked += 1;
+    }
+
+    if checked == 0 {
+        return Err("検証できる局面がありません".to_string());
+    }
+    Ok(())
+}
+
+/// MTD(f) が Aspiration Window の探索と同じ最善手・評価値を返すか
+/// Futility Pruning と LMR は探索の窓によって結果が変わるので、どちらも切って比べる
+pub fn check_mtdf_matches_aspiration() -> CheckResult {
+    let (board, _) = replay_transcript(SEARCH_CHECK_TRANSCRIPT).map_err(|e| e.to_string())?;
+    let aspiration = EngineConfig {
+        forward_pruning: false,
+        move_time_limit: Some(Duration::from_secs(60)),
+        ..EngineConfig::default()
+    };
+    let mtdf = EngineConfig {
+        use_mtdf: true,
+        ..aspiration
+    };
+
+    for depth in MTDF_CHECK_DEPTHS {
+        let (mut aspiration_board, mut mtdf_board) = (board, board);
+        let expected = aspiration_board.find_best_move_with_config(
+            Player::Black,
+            depth,
+            &mut Default::default(),
+            &aspiration,
+        );
+        let actual = mtdf_board.find_best_move_with_config(
+            Player::Black,
+            depth,
+            &mut Default::default(),
+            &mtdf,
+        );
+        if actual != expected {
+            return Err(format!(
+                "深さ{}で MTD(f) が {:?}（Aspiration Window では {:?}）",
+                depth, actual, expected
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 同じ局面を深く読むほど探索ノード数が増えるか
+pub fn check_node_counts() -> CheckResult {
+    let (board, _) = replay_transcript(SEARCH_CHECK_TRANSCRIPT).map_err(|e| e.to_string())?;
+    let config = EngineConfig {
+        move_time_limit: Some(Duration::from_secs(60)),
+        ..EngineConfig::default()
+    };
+
+    let mut previous: Option<(usize, u64)> = None;
+    for depth in NODE_CHECK_DEPTHS {
+        let mut search_board = board;
+        let (_, _, stats) = search_board.find_best_move_with_stats(
+            Player::Black,
+            depth,
+            &mut Default::default(),
+            &config,
+        );
+        if let Some((shallow, shallow_nodes)) = previous {
+            if stats.nodes <= shallow_nodes {
+                return Err(format!(
+                    "深さ{}のノード数{}が深さ{}の{}より多くありません",
+                    depth, stats.nodes, shallow, shallow_nodes
+                ));
+            }
+        }
+        previous = Some((depth, stats.nodes));
+    }
+    Ok(())
+}
+
+/// 温度付きの手選びが、同じ種なら同じ手を選び、高い温度なら最善以外の手も選ぶか
+pub fn check_temperature_sampling() -> CheckResult {
+    let (board, _) = replay_transcript(SEARCH_CHECK_TRANSCRIPT).map_err(|e| e.to_string())?;
+    let choose = |config: &EngineConfig| {
+        let mut search_board = board;
+        let (best_move, _) = search_board.find_best_move_with_config(
+            Player::Black,
+            1,
+            &mut Default::default(),
+            config,
+        );
+        best_move
+    };
+
+    let argmax = choose(&EngineConfig::default());
+    let mut chose_other = false;
+    for seed in 0..SAMPLING_SEEDS {
+        let config = EngineConfig {
+            temperature: SAMPLING_TEMPERATURE,
+            seed: Some(seed),
+            ..EngineConfig::default()
+        };
+        let first = choose(&config);
+        if choose(&config) != first {
+            return Err(format!("種{}で選ぶ手が毎回変わります", seed));
+        }
+        chose_other |= first != argmax;
+    }
+
+    if !chose_other {
+        return Err(format!(
+            "温度{}でも最善手 {:?} 以外を選びません",
+            SAMPLING_TEMPERATURE, argmax
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,327 @@
+use crate::board::{notation, rc_to_pos};
+use crate::language::Language;
+use crate::player::Player;
+use crate::stats::review::MoveQuality;
+use crate::stats::{format_duration, GameStats};
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use crate::stats::game_stats::ArchiveError;
+#[cfg(feature = "serde")]
+use std::path::{Path, PathBuf};
+
+/// 定跡として数える最初の手数
+pub const OPENING_MOVES: usize = 3;
+
+/// プレイヤーの種類ごとの成績
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerRecord {
+    pub label: String, // 保存されたプレイヤーの種類（例: "AI レベル5 (上級)"）
+    pub games: usize,
+    pub wins: usize,
+    pub draws: usize,
+}
+
+impl PlayerRecord {
+    /// 勝率（引き分けは含めない）
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.games as f64
+    }
+}
+
+/// 保存した複数の対局の集計
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArchiveSummary {
+    pub games: usize,
+    pub black_wins: usize,
+    pub white_wins: usize,
+    pub draws: usize,
+    pub total_moves: usize, // パスを除いた手の数の合計
+    pub total_thinking_time: Duration,
+    pub openings: Vec<(String, usize)>, // 最初の OPENING_MOVES 手の棋譜と対局数（多い順）
+    pub players: Vec<PlayerRecord>,     // プレイヤーの種類ごとの成績（種類の名前順）
+    pub reviewed_moves: usize,          // 検討した手の数（検討しなければ0）
+    pub blunders: usize,                // 検討で悪手と判定された手の数
+}
+
+impl ArchiveSummary {
+    /// 対局を集計する。review_depth を指定すると全ての手を検討して悪手の割合も求める
+    pub fn from_games(games: &[GameStats], review_depth: Option<usize>) -> Self {
+        let mut summary = ArchiveSummary {
+            games: games.len(),
+            ..ArchiveSummary::default()
+        };
+
+        for game in games {
+            let winner = final_winner(game);
+            match winner {
+                Some(Player::Black) => summary.black_wins += 1,
+                Some(Player::White) => summary.white_wins += 1,
+                None => summary.draws += 1,
+            }
+
+            let placed = game.moves.iter().filter(|m| m.position.is_some());
+            summary.total_moves += placed.clone().count();
+            summary.total_thinking_time += placed.clone().map(|m| m.thinking_time).sum();
+
+            let opening: Vec<String> = placed
+                .take(OPENING_MOVES)
+                .filter_map(|m| m.position)
+                .map(|(row, col)| notation(rc_to_pos(row, col)))
+                .collect();
+            if opening.len() == OPENING_MOVES {
+                let opening = opening.join(" ");
+                match summary.openings.iter_mut().find(|(o, _)| *o == opening) {
+                    Some((_, count)) => *count += 1,
+                    None => summary.openings.push((opening, 1)),
+                }
+            }
+
+            if let Some((black, white)) = &game.players {
+                summary.add_player_result(black, winner, Player::Black);
+                summary.add_player_result(white, winner, Player::White);
+            }
+
+            if let Some(depth) = review_depth {
+                let reviews = game.review_moves(&[Player::Black, Player::White], depth);
+                summary.reviewed_moves += reviews.len();
+                summary.blunders += reviews
+                    .iter()
+                    .filter(|r| r.quality == MoveQuality::Blunder)
+                    .count();
+            }
+        }
+
+        // 同じ対局数なら棋譜の順にして、結果が読み込み順に依存しないようにする
+        summary
+            .openings
+            .sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        summary.players.sort_by(|a, b| a.label.cmp(&b.label));
+        summary
+    }
+
+    fn add_player_result(&mut self, label: &str, winner: Option<Player>, color: Player) {
+        let index = match self.players.iter().position(|p| p.label == label) {
+            Some(index) => index,
+            None => {
+                self.players.push(PlayerRecord {
+                    label: label.to_string(),
+                    games: 0,
+                    wins: 0,
+                    draws: 0,
+                });
+                self.players.len() - 1
+            }
+        };
+        let record = &mut self.players[index];
+        record.games += 1;
+        match winner {
+            Some(w) if w == color => record.wins += 1,
+            None => record.draws += 1,
+            _ => {}
+        }
+    }
+
+    /// 黒の勝率
+    pub fn black_win_rate(&self) -> Option<f64> {
+        (self.games > 0).then(|| self.black_wins as f64 / self.games as f64)
+    }
+
+    /// 白の勝率
+    pub fn white_win_rate(&self) -> Option<f64> {
+        (self.games > 0).then(|| self.white_wins as f64 / self.games as f64)
+    }
+
+    /// 1局あたりの平均手数（パスを除く）
+    pub fn average_game_length(&self) -> Option<f64> {
+        (self.games > 0).then(|| self.total_moves as f64 / self.games as f64)
+    }
+
+    /// 1手あたりの平均思考時間
+    pub fn average_thinking_time(&self) -> Option<Duration> {
+        (self.total_moves > 0).then(|| self.total_thinking_time / self.total_moves as u32)
+    }
+
+    /// 最も多く打たれた定跡とその対局数
+    pub fn most_played_opening(&self) -> Option<(&str, usize)> {
+        self.openings
+            .first()
+            .map(|(opening, count)| (opening.as_str(), *count))
+    }
+
+    /// 検討した手のうち悪手の割合（検討していなければ None）
+    pub fn blunder_rate(&self) -> Option<f64> {
+        (self.reviewed_moves > 0).then(|| self.blunders as f64 / self.reviewed_moves as f64)
+    }
+
+    /// 集計結果を表示する
+    pub fn print(&self) {
+        println!("\n==========================");
+        println!("      対局の集計");
+        println!("==========================");
+        println!("・対局数: {}", self.games);
+        if let (Some(black), Some(white)) = (self.black_win_rate(), self.white_win_rate()) {
+            println!(
+                "・黒の勝率: {:.1}% ({}勝)  白の勝率: {:.1}% ({}勝)  引き分け: {}",
+                black * 100.0,
+                self.black_wins,
+                white * 100.0,
+                self.white_wins,
+                self.draws
+            );
+        }
+        if let Some(length) = self.average_game_length() {
+            println!("・平均手数: {:.1}", length);
+        }
+        if let Some(time) = self.average_thinking_time() {
+            println!(
+                "・1手平均思考時間: {}",
+                format_duration(time, Language::Japanese)
+            );
+        }
+        if let Some((opening, count)) = self.most_played_opening() {
+            println!("・最も多い定跡: {} ({}局)", opening, count);
+        }
+        if let Some(rate) = self.blunder_rate() {
+            println!(
+                "・悪手率: {:.1}% ({}/{}手)",
+                rate * 100.0,
+                self.blunders,
+                self.reviewed_moves
+            );
+        }
+
+        if !self.players.is_empty() {
+            println!("\nプレイヤー別の成績:");
+            for record in &self.players {
+                println!(
+                    "・{}: {}局 {}勝 {}分 (勝率{:.1}%)",
+                    record.label,
+                    record.games,
+                    record.wins,
+                    record.draws,
+                    record.win_rate() * 100.0
+                );
+            }
+        }
+    }
+}
+
+/// 最後に記録された石数から勝者を求める（引き分けなら None）
+fn final_winner(game: &GameStats) -> Option<Player> {
+    let (black, white) = match game.moves.last() {
+        Some(record) => (record.black_count, record.white_count),
+        None => game.start_position.count_all_discs(),
+    };
+    match black.cmp(&white) {
+        std::cmp::Ordering::Greater => Some(Player::Black),
+        std::cmp::Ordering::Less => Some(Player::White),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// 読み込めた対局と、読み込めなかったファイルとその理由
+#[cfg(feature = "serde")]
+pub type LoadedGames = (Vec<GameStats>, Vec<(PathBuf, ArchiveError)>);
+
+/// フォルダ内の to_bytes で保存した対局をすべて読み込む（ファイル名順）
+/// 読み込めなかったファイルは理由と一緒に別に返す
+#[cfg(feature = "serde")]
+pub fn load_games_from_dir(dir: impl AsRef<Path>) -> std::io::Result<LoadedGames> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    let mut games = Vec::new();
+    let mut failures = Vec::new();
+    for path in paths {
+        match GameStats::from_bytes(&std::fs::read(&path)?) {
+            Ok(game) => games.push(game),
+            Err(e) => failures.push((path, e)),
+        }
+    }
+
+    Ok((games, failures))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::replay_transcript;
+
+    /// 棋譜から対局を作り、プレイヤーの種類を付ける
+    fn game(transcript: &str, players: Option<(&str, &str)>) -> GameStats {
+        let (_, mut stats) = replay_transcript(transcript).unwrap();
+        if let Some((black, white)) = players {
+            stats.set_players(black, white);
+        }
+        stats
+    }
+
+    fn sample_games() -> Vec<GameStats> {
+        vec![
+            game("f5d6c3d3c4", Some(("AI", "人間"))), // 黒 6-3 白
+            game("f5d6c3d3c4f4f6f3e6e7", Some(("AI", "人間"))), // 黒 6-8 白
+            game("f5f6e6f4", None),                   // 黒 3-5 白
+        ]
+    }
+
+    #[test]
+    fn summary_counts_results_openings_and_players() {
+        let summary = ArchiveSummary::from_games(&sample_games(), None);
+
+        assert_eq!(summary.games, 3);
+        assert_eq!(
+            (summary.black_wins, summary.white_wins, summary.draws),
+            (1, 2, 0)
+        );
+        assert_eq!(summary.total_moves, 19);
+        assert_eq!(summary.average_game_length(), Some(19.0 / 3.0));
+        assert_eq!(summary.most_played_opening(), Some(("f5 d6 c3", 2)));
+        assert_eq!(summary.openings.len(), 2);
+        assert_eq!(summary.blunder_rate(), None);
+
+        // どちらも2局とも同じ色を持ち、AI は1局目、人間は2局目に勝つ
+        assert_eq!(summary.players.len(), 2);
+        for record in &summary.players {
+            assert_eq!((record.games, record.wins, record.draws), (2, 1, 0));
+            assert_eq!(record.win_rate(), 0.5);
+        }
+
+        let reviewed = ArchiveSummary::from_games(&sample_games(), Some(1));
+        assert_eq!(reviewed.reviewed_moves, 19);
+        assert!(reviewed.blunder_rate().is_some());
+
+        let empty = ArchiveSummary::from_games(&[], None);
+        assert_eq!(empty.black_win_rate(), None);
+        assert_eq!(empty.average_thinking_time(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn games_are_loaded_from_a_directory() {
+        let dir = std::env::temp_dir().join(format!("bitothello_archive_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (i, game) in sample_games().iter().enumerate() {
+            std::fs::write(dir.join(format!("game{}.bin", i)), game.to_bytes()).unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), b"not a game").unwrap();
+
+        let (games, failures) = load_games_from_dir(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(games.len(), 3);
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].0.ends_with("notes.txt"));
+        assert_eq!(
+            ArchiveSummary::from_games(&games, None),
+            ArchiveSummary::from_games(&sample_games(), None)
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::language::Language;
+use std::time::Duration;
+
+/// 時間を言語に合わせた単位で表す
+/// 1秒未満はミリ秒、1分未満は小数2桁の秒、それ以上は分と秒（秒は切り捨て）
+pub fn format_duration(duration: Duration, language: Language) -> String {
+    if duration < Duration::from_secs(1) {
+        let millis = duration.as_millis();
+        return match language {
+            Language::Japanese => format!("{}ミリ秒", millis),
+            Language::English => format!("{} ms", millis),
+        };
+    }
+
+    if duration < Duration::from_secs(60) {
+        let secs = duration.as_secs_f64();
+        return match language {
+            Language::Japanese => format!("{:.2}秒", secs),
+            Language::English => format!("{:.2} s", secs),
+        };
+    }
+
+    let (minutes, secs) = (duration.as_secs() / 60, duration.as_secs() % 60);
+    match language {
+        Language::Japanese => format!("{}分{}秒", minutes, secs),
+        Language::English => format!("{} min {} s", minutes, secs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_use_localized_units() {
+        let cases = [
+            (Duration::ZERO, "0ミリ秒", "0 ms"),
+            (Duration::from_millis(250), "250ミリ秒", "250 ms"),
+            (Duration::from_millis(999), "999ミリ秒", "999 ms"),
+            (Duration::from_secs(1), "1.00秒", "1.00 s"),
+            (Duration::from_millis(12_345), "12.35秒", "12.35 s"),
+            (Duration::from_secs(60), "1分0秒", "1 min 0 s"),
+            (Duration::from_millis(185_900), "3分5秒", "3 min 5 s"),
+        ];
+        for (duration, japanese, english) in cases {
+            assert_eq!(format_duration(duration, Language::Japanese), japanese);
+            assert_eq!(format_duration(duration, Language::English), english);
+        }
+    }
+}
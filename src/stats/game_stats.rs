@@ -1,35 +1,179 @@
+use crate::ai::MoveTiming;
+use crate::board::{
+    is_pass_notation, notation, parse_notation, pos_to_rc, rc_to_pos, split_transcript, ApplyError,
+    BitBoard, PASS_NOTATION,
+};
+use crate::language::Language;
 use crate::player::Player;
+use crate::stats::format_duration;
+use std::cmp::Ordering;
 use std::time::{Duration, Instant};
 
 /// 一手の記録
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveRecord {
     pub move_number: usize,
     pub player: Player,
     pub position: Option<(usize, usize)>, // None はパス
+    #[cfg_attr(feature = "serde", serde(with = "duration_millis"))]
     pub thinking_time: Duration,
     pub black_count: u32,
     pub white_count: u32,
-    pub evaluation: Option<i32>, // AI の評価値（人間の場合は None）
+    pub evaluation: Option<i32>,    // AI の評価値（人間の場合は None）
+    pub annotation: Option<String>, // 検討用の注釈（例: "良い角取り"）
+    pub snapshot: Option<BitBoard>, // この手の後の盤面（記録が有効な場合のみ）
+    pub timing: Option<MoveTiming>, // AI の思考時間の内訳（人間の場合は None）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mobility: Option<(u32, u32)>, // この手の後の黒・白の合法手の数（記録していなければ None）
+}
+
+impl MoveRecord {
+    /// 黒から見た評価値（記録された評価値は打った側から見た値なので、白の手では符号を反転する）
+    pub fn normalized_evaluation(&self) -> Option<i32> {
+        self.evaluation.map(|eval| match self.player {
+            Player::Black => eval,
+            Player::White => -eval,
+        })
+    }
+}
+
+/// 対局の終わり方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Termination {
+    Normal,     // 両者とも打てなくなるまで打った
+    AgreedDraw, // 合意による引き分け
+}
+
+/// 合意引き分けの判定条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawAgreement {
+    pub consecutive_moves: usize, // 評価値が0付近の手がこの数だけ続いたら引き分け（0で無効）
+    pub eval_threshold: i32,      // 0付近とみなす評価値の絶対値
+}
+
+impl Default for DrawAgreement {
+    fn default() -> Self {
+        Self {
+            consecutive_moves: 0,
+            eval_threshold: 20,
+        }
+    }
+}
+
+/// 1人のプレイヤーの評価値の集計（そのプレイヤーから見た値で、正なら優勢）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalAdvantage {
+    pub average: f64, // 平均
+    pub peak: i32,    // 最も優勢だった時の値
+    pub moves: usize, // 評価値のある手の数
 }
 
 /// ゲーム結果
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameResult {
     pub winner: Option<Player>,
     pub black_final_count: u32,
     pub white_final_count: u32,
     pub total_moves: usize,
+    #[cfg_attr(feature = "serde", serde(with = "duration_millis"))]
     pub game_duration: Duration,
+    #[cfg_attr(feature = "serde", serde(with = "duration_millis"))]
     pub total_thinking_time: Duration,
+    pub termination: Termination,
 }
 
 /// ゲーム統計を記録するクラス
+/// JSON に保存する場合、Instant は保存できないので game_start_time は読み込んだ時刻になる
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameStats {
     pub moves: Vec<MoveRecord>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "Instant::now"))]
     pub game_start_time: Instant,
+    pub start_position: BitBoard,          // 開始局面（通常は初期配置）
+    pub players: Option<(String, String)>, // 黒・白のプレイヤーの種類（表示用）
     current_move_number: usize,
+    record_snapshots: bool,
+}
+
+/// Duration をミリ秒の整数として保存する（serde の with 属性で使う）
+#[cfg(feature = "serde")]
+pub(crate) mod duration_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// バイナリ形式の版（形式を変えたら上げる）
+#[cfg(feature = "serde")]
+const ARCHIVE_FORMAT_VERSION: u8 = 2;
+
+/// バイナリ形式の対局を読み込めなかった理由
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum ArchiveError {
+    Empty,                                  // データが空
+    UnsupportedVersion(u8),                 // 知らない版の形式
+    Corrupt(bincode::Error),                // 途中で切れている・壊れている
+    InvalidBoard(crate::board::BoardError), // 開始局面が盤面として正しくない
+}
+
+#[cfg(feature = "serde")]
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ArchiveError::Empty => write!(f, "対局データが空です"),
+            ArchiveError::UnsupportedVersion(version) => {
+                write!(f, "対応していない形式の版です（{}）", version)
+            }
+            ArchiveError::Corrupt(e) => write!(f, "対局データが壊れています（{}）", e),
+            ArchiveError::InvalidBoard(e) => write!(f, "開始局面が不正です（{}）", e),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for ArchiveError {}
+
+/// バイナリ形式で保存する一手（盤面のスナップショットは手順から復元できるので保存しない）
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ArchivedMove {
+    player: Player,
+    position: Option<u8>, // row * 8 + col（None はパス）
+    thinking_time: Duration,
+    black_count: u8,
+    white_count: u8,
+    evaluation: Option<i32>,
+    annotation: Option<String>,
+    timing: Option<MoveTiming>,
+    mobility: Option<(u8, u8)>,
+}
+
+/// バイナリ形式で保存する対局
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ArchivedGame {
+    start_black: u64,
+    start_white: u64,
+    players: Option<(String, String)>,
+    moves: Vec<ArchivedMove>,
+}
+
+impl Default for GameStats {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameStats {
@@ -37,10 +181,23 @@ impl GameStats {
         Self {
             moves: Vec::new(),
             game_start_time: Instant::now(),
+            start_position: BitBoard::new(),
+            players: None,
             current_move_number: 0,
+            record_snapshots: false,
         }
     }
 
+    /// 黒・白のプレイヤーの種類を記録する（保存した対局の表示用）
+    pub fn set_players(&mut self, black: impl Into<String>, white: impl Into<String>) {
+        self.players = Some((black.into(), white.into()));
+    }
+
+    /// 各手の後の盤面スナップショットを記録するかを設定
+    pub fn set_record_snapshots(&mut self, enabled: bool) {
+        self.record_snapshots = enabled;
+    }
+
     /// 手を記録
     pub fn record_move(
         &mut self,
@@ -63,11 +220,138 @@ impl GameStats {
             black_count,
             white_count,
             evaluation,
+            annotation: None,
+            snapshot: None,
+            timing: None,
+            mobility: None,
         };
 
         self.moves.push(record);
     }
 
+    /// 記録した手を len 件まで切り詰める（待ったで手を戻すときに使う）
+    pub fn truncate_moves(&mut self, len: usize) {
+        self.moves.truncate(len);
+        self.current_move_number = self.moves.last().map_or(0, |m| m.move_number);
+    }
+
+    /// 直前に記録した手に盤面スナップショットを付ける（記録が無効なら何もしない）
+    pub fn record_snapshot(&mut self, board: &BitBoard) {
+        if !self.record_snapshots {
+            return;
+        }
+        if let Some(record) = self.moves.last_mut() {
+            record.snapshot = Some(*board);
+        }
+    }
+
+    /// 直前に記録した手に、その手の後の黒・白の合法手の数を付ける
+    pub fn record_mobility(&mut self, board: &BitBoard) {
+        if let Some(record) = self.moves.last_mut() {
+            record.mobility = Some((
+                board.legal_move_count(Player::Black),
+                board.legal_move_count(Player::White),
+            ));
+        }
+    }
+
+    /// 直前に記録した手に AI の思考時間の内訳を付ける
+    pub fn record_timing(&mut self, timing: MoveTiming) {
+        if let Some(record) = self.moves.last_mut() {
+            record.timing = Some(timing);
+        }
+    }
+
+    /// 指定した手数の後の盤面を取得（0 は初期盤面）
+    /// スナップショットがあればそれを使い、無ければ（または不正なら）初期盤面から手順を再生する
+    pub fn board_at_move(&self, move_number: usize) -> Option<BitBoard> {
+        if move_number == 0 {
+            return Some(self.start_position);
+        }
+
+        if let Some(snapshot) = self
+            .moves
+            .iter()
+            .rev()
+            .find(|m| m.move_number == move_number)
+            .and_then(|m| m.snapshot)
+            .filter(|board| board.validate().is_ok())
+        {
+            return Some(snapshot);
+        }
+
+        let mut board = self.start_position;
+        for record in self
+            .moves
+            .iter()
+            .take_while(|m| m.move_number <= move_number)
+        {
+            if let Some((row, col)) = record.position {
+                if !board.make_move(rc_to_pos(row, col), record.player) {
+                    return None;
+                }
+            }
+        }
+
+        (move_number <= self.current_move_number).then_some(board)
+    }
+
+    /// 棋譜を "f5 d6 -- c3" の形式で返す（moves_as_notation の手を空白で区切り、パスの位置に PASS_NOTATION を入れる）
+    /// split_transcript と apply_moves でそのまま読み込める
+    pub fn transcript(&self) -> String {
+        let mut placed = self.moves_as_notation().into_iter();
+        self.moves
+            .iter()
+            .map(|record| match record.position {
+                Some(_) => placed.next().unwrap_or_default(),
+                None => PASS_NOTATION.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 打った手の座標表記（"f5" など）を打った順に返す
+    /// パスは含めない（打てる手が無い側は必ずパスするので、split_transcript と apply_moves で
+    /// 読み込むと自動でパスが補われる）
+    pub fn moves_as_notation(&self) -> Vec<String> {
+        self.moves
+            .iter()
+            .filter_map(|record| record.position)
+            .map(|(row, col)| notation(rc_to_pos(row, col)))
+            .collect()
+    }
+
+    /// 棋譜を WThor などで使われる区切りなしの形式（"f5d6c3..."）で返す（moves_as_notation をつなげたもの）
+    pub fn to_transcript(&self) -> String {
+        self.moves_as_notation().concat()
+    }
+
+    /// 指定した手数の手に注釈を付ける（None で削除）
+    /// 該当する手がなければ false を返す
+    pub fn annotate_move(&mut self, move_number: usize, annotation: Option<String>) -> bool {
+        let annotation = annotation.filter(|text| !text.trim().is_empty());
+
+        match self
+            .moves
+            .iter_mut()
+            .find(|m| m.position.is_some() && m.move_number == move_number)
+        {
+            Some(record) => {
+                record.annotation = annotation;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 注釈付きの手を取得
+    pub fn get_annotated_moves(&self) -> Vec<&MoveRecord> {
+        self.moves
+            .iter()
+            .filter(|m| m.annotation.is_some())
+            .collect()
+    }
+
     /// ゲーム結果を生成
     pub fn finalize_game(
         &self,
@@ -91,9 +375,40 @@ impl GameStats {
             total_moves,
             game_duration,
             total_thinking_time,
+            termination: Termination::Normal,
+        }
+    }
+
+    /// 合意による引き分けとしてゲームを終了
+    pub fn finalize_agreed_draw(&self, black_count: u32, white_count: u32) -> GameResult {
+        GameResult {
+            termination: Termination::AgreedDraw,
+            ..self.finalize_game(None, black_count, white_count)
         }
     }
 
+    /// 直近の手がすべて評価値0付近のAIの手なら true（合意引き分けの条件）
+    /// 人間の手やパスを挟む場合は連続とみなさない
+    pub fn agreed_draw_reached(&self, rule: &DrawAgreement) -> bool {
+        if rule.consecutive_moves == 0 {
+            return false;
+        }
+
+        let recent: Vec<&MoveRecord> = self
+            .moves
+            .iter()
+            .rev()
+            .take(rule.consecutive_moves)
+            .collect();
+
+        recent.len() == rule.consecutive_moves
+            && recent.iter().all(|m| {
+                m.position.is_some()
+                    && m.evaluation
+                        .is_some_and(|eval| eval.abs() <= rule.eval_threshold)
+            })
+    }
+
     /// 石数の推移を取得
     pub fn get_disc_count_history(&self) -> Vec<(usize, u32, u32)> {
         self.moves
@@ -103,6 +418,42 @@ impl GameStats {
             .collect()
     }
 
+    /// 石数のリードが入れ替わった手番号を取得（同数の局面は入れ替わりとみなさない）
+    pub fn lead_change_moves(&self) -> Vec<usize> {
+        let mut changes = Vec::new();
+        let mut leader = Ordering::Equal;
+
+        for (move_number, black, white) in self.get_disc_count_history() {
+            let current = black.cmp(&white);
+            if current == Ordering::Equal {
+                continue;
+            }
+            if leader != Ordering::Equal && current != leader {
+                changes.push(move_number);
+            }
+            leader = current;
+        }
+
+        changes
+    }
+
+    /// 石数のリードが入れ替わった回数
+    pub fn lead_changes(&self) -> usize {
+        self.lead_change_moves().len()
+    }
+
+    /// 合法手の数の推移を取得（パス以外の手の後の黒・白の合法手の数）
+    pub fn get_mobility_history(&self) -> Vec<(usize, u32, u32)> {
+        self.moves
+            .iter()
+            .filter(|m| m.position.is_some())
+            .filter_map(|m| {
+                let (black, white) = m.mobility?;
+                Some((m.move_number, black, white))
+            })
+            .collect()
+    }
+
     /// 思考時間の推移を取得
     pub fn get_thinking_time_history(&self) -> Vec<(usize, f64)> {
         self.moves
@@ -112,6 +463,15 @@ impl GameStats {
             .collect()
     }
 
+    /// 待ち時間を除いた、AI が実際に計算していた時間の推移を取得（内訳のある手のみ）
+    pub fn get_compute_time_history(&self) -> Vec<(usize, f64)> {
+        self.moves
+            .iter()
+            .filter(|m| m.position.is_some())
+            .filter_map(|m| Some((m.move_number, m.timing?.compute_time().as_secs_f64())))
+            .collect()
+    }
+
     /// 評価値の推移を取得（AI のみ）
     pub fn get_evaluation_history(&self) -> Vec<(usize, Player, i32)> {
         self.moves
@@ -126,6 +486,34 @@ impl GameStats {
             .collect()
     }
 
+    /// 黒から見た評価値の推移を取得（AI のみ、グラフで黒と白の評価を1本の曲線として比べられる）
+    pub fn get_normalized_evaluation_history(&self) -> Vec<(usize, Player, i32)> {
+        self.moves
+            .iter()
+            .filter(|m| m.position.is_some())
+            .filter_map(|m| Some((m.move_number, m.player, m.normalized_evaluation()?)))
+            .collect()
+    }
+
+    /// 指定プレイヤーの評価値の平均と最大（評価値が無ければ None）
+    /// 記録された評価値は打ったAI自身から見た値なので、そのプレイヤーの手だけを集計すれば
+    /// 黒と白を同じ基準（正なら自分が優勢）で比べられる
+    pub fn evaluation_advantage(&self, player: Player) -> Option<EvalAdvantage> {
+        let evals: Vec<i32> = self
+            .get_evaluation_history()
+            .into_iter()
+            .filter(|(_, mover, _)| *mover == player)
+            .map(|(_, _, eval)| eval)
+            .collect();
+
+        let peak = *evals.iter().max()?;
+        Some(EvalAdvantage {
+            average: evals.iter().map(|&e| e as f64).sum::<f64>() / evals.len() as f64,
+            peak,
+            moves: evals.len(),
+        })
+    }
+
     /// 手数を取得
     pub fn get_move_count(&self) -> usize {
         self.current_move_number
@@ -136,8 +524,109 @@ impl GameStats {
         GameStats {
             moves: self.moves.clone(),
             game_start_time: Instant::now(),
+            start_position: self.start_position,
+            players: self.players.clone(),
             current_move_number: self.current_move_number,
+            record_snapshots: self.record_snapshots,
+        }
+    }
+
+    /// 対局をコンパクトなバイナリ形式にする（先頭1バイトは形式の版）
+    /// 大量の自己対戦の保存向け。スナップショットは手順から復元できるので保存しない
+    #[cfg(feature = "serde")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let archived = ArchivedGame {
+            start_black: self.start_position.black,
+            start_white: self.start_position.white,
+            players: self.players.clone(),
+            moves: self
+                .moves
+                .iter()
+                .map(|record| ArchivedMove {
+                    player: record.player,
+                    position: record.position.map(|(row, col)| rc_to_pos(row, col) as u8),
+                    thinking_time: record.thinking_time,
+                    black_count: record.black_count as u8,
+                    white_count: record.white_count as u8,
+                    evaluation: record.evaluation,
+                    annotation: record.annotation.clone(),
+                    timing: record.timing,
+                    mobility: record
+                        .mobility
+                        .map(|(black, white)| (black as u8, white as u8)),
+                })
+                .collect(),
+        };
+
+        let mut bytes = vec![ARCHIVE_FORMAT_VERSION];
+        bytes.extend(bincode::serialize(&archived).expect("対局データを変換できません"));
+        bytes
+    }
+
+    /// to_bytes で保存した対局を読み込む
+    /// 途中で切れているなど壊れたデータはパニックせずにエラーを返す
+    #[cfg(feature = "serde")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<GameStats, ArchiveError> {
+        let (&version, body) = bytes.split_first().ok_or(ArchiveError::Empty)?;
+        if version != ARCHIVE_FORMAT_VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
         }
+        let archived: ArchivedGame = bincode::deserialize(body).map_err(ArchiveError::Corrupt)?;
+
+        let start_position = BitBoard {
+            black: archived.start_black,
+            white: archived.start_white,
+        };
+        start_position
+            .validate()
+            .map_err(ArchiveError::InvalidBoard)?;
+
+        let mut stats = GameStats::new();
+        stats.start_position = start_position;
+        stats.players = archived.players;
+        for archived_move in archived.moves {
+            let position = archived_move.position.map(|pos| pos_to_rc(pos as usize));
+            stats.record_move(
+                archived_move.player,
+                position,
+                archived_move.thinking_time,
+                archived_move.black_count as u32,
+                archived_move.white_count as u32,
+                archived_move.evaluation,
+            );
+            if let Some(record) = stats.moves.last_mut() {
+                record.annotation = archived_move.annotation;
+                record.timing = archived_move.timing;
+                record.mobility = archived_move
+                    .mobility
+                    .map(|(black, white)| (black as u32, white as u32));
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// 対局を JSON で保存する（思考時間などはミリ秒単位）
+    #[cfg(feature = "serde")]
+    pub fn save_json(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer_pretty(file, self).map_err(std::io::Error::from)
+    }
+
+    /// save_json で保存した対局を読み込む
+    /// 開始局面やスナップショットが盤面として正しくなければ InvalidData のエラーを返す
+    #[cfg(feature = "serde")]
+    pub fn load_json(path: impl AsRef<std::path::Path>) -> std::io::Result<GameStats> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let stats: GameStats = serde_json::from_reader(file).map_err(std::io::Error::from)?;
+
+        let snapshots = stats.moves.iter().filter_map(|record| record.snapshot);
+        for board in std::iter::once(stats.start_position).chain(snapshots) {
+            board
+                .validate()
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        }
+        Ok(stats)
     }
 
     /// 統計サマリーを表示
@@ -149,31 +638,46 @@ impl GameStats {
         println!("手数分析:");
         println!("・総手数: {}", game_result.total_moves);
         println!("・総記録数: {} (パス含む)", self.moves.len());
+        println!("・棋譜: {}", self.transcript());
 
         println!("\n時間分析:");
-        println!("・ゲーム時間: {:.2?}", game_result.game_duration);
-        println!("・総思考時間: {:.2?}", game_result.total_thinking_time);
+        println!(
+            "・ゲーム時間: {}",
+            format_duration(game_result.game_duration, Language::Japanese)
+        );
+        println!(
+            "・総思考時間: {}",
+            format_duration(game_result.total_thinking_time, Language::Japanese)
+        );
 
         if game_result.total_moves > 0 {
             println!(
-                "・1手平均思考時間: {:.2?}",
-                game_result.total_thinking_time / game_result.total_moves as u32
+                "・1手平均思考時間: {}",
+                format_duration(
+                    game_result.total_thinking_time / game_result.total_moves as u32,
+                    Language::Japanese
+                )
             );
         }
 
         // 思考時間の統計
-        let thinking_times: Vec<f64> = self
+        let thinking_times = self
             .moves
             .iter()
             .filter(|m| m.position.is_some())
-            .map(|m| m.thinking_time.as_secs_f64())
-            .collect();
+            .map(|m| m.thinking_time);
 
-        if !thinking_times.is_empty() {
-            let max_time = thinking_times.iter().fold(0.0f64, |a, &b| a.max(b));
-            let min_time = thinking_times.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-            println!("・最長思考時間: {:.2}秒", max_time);
-            println!("・最短思考時間: {:.2}秒", min_time);
+        if let (Some(max_time), Some(min_time)) =
+            (thinking_times.clone().max(), thinking_times.min())
+        {
+            println!(
+                "・最長思考時間: {}",
+                format_duration(max_time, Language::Japanese)
+            );
+            println!(
+                "・最短思考時間: {}",
+                format_duration(min_time, Language::Japanese)
+            );
         }
 
         // 石数の推移
@@ -193,6 +697,542 @@ impl GameStats {
                 "・白の増減: {:+}個",
                 final_white as i32 - initial_white as i32
             );
+
+            let lead_changes = self.lead_change_moves();
+            if lead_changes.is_empty() {
+                println!("・リードの入れ替わり: なし");
+            } else {
+                let move_list: Vec<String> =
+                    lead_changes.iter().map(|n| format!("{}手目", n)).collect();
+                println!(
+                    "・リードの入れ替わり: {}回 ({})",
+                    lead_changes.len(),
+                    move_list.join(", ")
+                );
+            }
+        }
+
+        // 評価値（AI のみ）
+        let advantages: Vec<(Player, EvalAdvantage)> = Player::all()
+            .into_iter()
+            .filter_map(|player| Some((player, self.evaluation_advantage(player)?)))
+            .collect();
+        if !advantages.is_empty() {
+            println!("\n評価値:");
+            for (player, advantage) in advantages {
+                println!(
+                    "・{}の平均優勢: {:+.1} (最大 {:+})",
+                    player.to_string(),
+                    advantage.average,
+                    advantage.peak
+                );
+            }
+        }
+
+        // 注釈
+        let annotated = self.get_annotated_moves();
+        if !annotated.is_empty() {
+            println!("\n注釈:");
+            for record in annotated {
+                if let (Some((row, col)), Some(note)) = (record.position, &record.annotation) {
+                    println!(
+                        "・{}手目 {}({},{}): {}",
+                        record.move_number,
+                        record.player.to_string(),
+                        row,
+                        col,
+                        note
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// 棋譜の打ち直しに失敗した理由（何手目かは ApplyError::index で分かる）
+pub type ReplayError = ApplyError;
+
+/// 棋譜（"f5d6c3" や "f5 d6 -- c3"）を初期配置から打ち直し、最後の盤面と記録を返す
+/// 打てる手が無い側のパスは、棋譜に書かれていなくても補って記録する（思考時間は0）
+/// 読めない手・打てない手があればその手の位置をエラーで返す
+pub fn replay_transcript(moves: &str) -> Result<(BitBoard, GameStats), ReplayError> {
+    let mut board = BitBoard::new();
+    let mut player = Player::Black;
+    let mut stats = GameStats::new();
+
+    let record = |stats: &mut GameStats, board: &BitBoard, player, position| {
+        let (black_count, white_count) = board.count_all_discs();
+        stats.record_move(
+            player,
+            position,
+            Duration::ZERO,
+            black_count,
+            white_count,
+            None,
+        );
+        stats.record_mobility(board);
+        stats.record_snapshot(board);
+    };
+
+    for (index, notation) in split_transcript(moves).into_iter().enumerate() {
+        if board.is_game_over() {
+            return Err(ApplyError::GameOver { index });
+        }
+
+        if is_pass_notation(notation) {
+            if board.pass(player).is_none() {
+                return Err(ApplyError::IllegalMove {
+                    index,
+                    notation: notation.to_string(),
+                });
+            }
+            record(&mut stats, &board, player, None);
+            player = player.opponent();
+            continue;
+        }
+
+        let pos = parse_notation(notation).ok_or_else(|| ApplyError::InvalidNotation {
+            index,
+            notation: notation.to_string(),
+        })?;
+
+        if board.is_pass_required(player) {
+            record(&mut stats, &board, player, None);
+            player = player.opponent();
+        }
+        if !board.make_move(pos, player) {
+            return Err(ApplyError::IllegalMove {
+                index,
+                notation: notation.to_string(),
+            });
+        }
+        record(&mut stats, &board, player, Some(pos_to_rc(pos)));
+        player = player.opponent();
+    }
+
+    Ok((board, stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 評価値・注釈・思考時間の内訳を付けた短い対局
+    fn sample_stats() -> GameStats {
+        let (_, mut stats) = replay_transcript("f5d6c3d3c4f4f6f3e6e7").unwrap();
+        stats.players = Some(("人間".to_string(), "AI Lv.3".to_string()));
+        stats.moves[1].evaluation = Some(-42);
+        stats.moves[1].thinking_time = Duration::from_millis(250);
+        stats.moves[2].annotation = Some("良い手".to_string());
+        stats.record_timing(MoveTiming {
+            search_time: Duration::from_millis(120),
+            cleanup_time: Duration::from_millis(3),
+            padding_time: Duration::ZERO,
+        });
+        stats
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn binary_round_trip_keeps_moves_and_histories() {
+        let stats = sample_stats();
+        let loaded = GameStats::from_bytes(&stats.to_bytes()).unwrap();
+
+        assert_eq!(loaded.start_position, stats.start_position);
+        assert_eq!(loaded.players, stats.players);
+        assert_eq!(loaded.transcript(), stats.transcript());
+        assert_eq!(loaded.get_mobility_history(), stats.get_mobility_history());
+        assert_eq!(loaded.moves.len(), stats.moves.len());
+        for (loaded, original) in loaded.moves.iter().zip(&stats.moves) {
+            assert_eq!(loaded.thinking_time, original.thinking_time);
+            assert_eq!(
+                (loaded.black_count, loaded.white_count),
+                (original.black_count, original.white_count)
+            );
+            assert_eq!(loaded.evaluation, original.evaluation);
+            assert_eq!(loaded.annotation, original.annotation);
+            assert_eq!(loaded.timing, original.timing);
+            assert_eq!(loaded.mobility, original.mobility);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip_keeps_moves_evaluations_and_counts() {
+        let stats = sample_stats();
+        let path =
+            std::env::temp_dir().join(format!("bitothello_game_{}.json", std::process::id()));
+        stats.save_json(&path).unwrap();
+        let loaded = GameStats::load_json(&path);
+        std::fs::remove_file(&path).ok();
+        let loaded = loaded.unwrap();
+
+        assert_eq!(loaded.moves.len(), stats.moves.len());
+        for (loaded, original) in loaded.moves.iter().zip(&stats.moves) {
+            assert_eq!(loaded.move_number, original.move_number);
+            assert_eq!(loaded.player, original.player);
+            assert_eq!(loaded.position, original.position);
+            assert_eq!(loaded.evaluation, original.evaluation);
+            assert_eq!(loaded.thinking_time, original.thinking_time);
+            assert_eq!(
+                (loaded.black_count, loaded.white_count),
+                (original.black_count, original.white_count)
+            );
+        }
+        assert_eq!(loaded.moves[1].evaluation, Some(-42));
+
+        // 結果も時間をミリ秒にして保存できる
+        let result = stats.finalize_game(Some(Player::White), 6, 8);
+        let json = serde_json::to_string(&result).unwrap();
+        let loaded: GameResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.winner, result.winner);
+        assert_eq!((loaded.black_final_count, loaded.white_final_count), (6, 8));
+        assert_eq!(loaded.total_moves, result.total_moves);
+        assert_eq!(
+            loaded.total_thinking_time.as_millis(),
+            result.total_thinking_time.as_millis()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn truncated_binary_is_an_error() {
+        let bytes = sample_stats().to_bytes();
+        assert!(matches!(
+            GameStats::from_bytes(&[]),
+            Err(ArchiveError::Empty)
+        ));
+        for len in 1..bytes.len() {
+            assert!(
+                GameStats::from_bytes(&bytes[..len]).is_err(),
+                "{}バイト",
+                len
+            );
+        }
+
+        let mut other_version = bytes.clone();
+        other_version[0] = ARCHIVE_FORMAT_VERSION + 1;
+        assert!(matches!(
+            GameStats::from_bytes(&other_version),
+            Err(ArchiveError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn overlapping_start_position_is_rejected() {
+        let mut stats = sample_stats();
+        stats.start_position.white |= stats.start_position.black;
+
+        assert!(matches!(
+            GameStats::from_bytes(&stats.to_bytes()),
+            Err(ArchiveError::InvalidBoard(_))
+        ));
+
+        let path =
+            std::env::temp_dir().join(format!("bitothello_invalid_{}.json", std::process::id()));
+        stats.save_json(&path).unwrap();
+        let loaded = GameStats::load_json(&path);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn annotations_survive_json_round_trip() {
+        let mut stats = sample_stats();
+        assert!(stats.annotate_move(4, Some("悪手".to_string())));
+        // 空白だけの注釈は削除として扱い、存在しない手には付けられない
+        assert!(stats.annotate_move(3, Some("  ".to_string())));
+        assert!(!stats.annotate_move(99, Some("無効".to_string())));
+
+        let path =
+            std::env::temp_dir().join(format!("bitothello_notes_{}.json", std::process::id()));
+        stats.save_json(&path).unwrap();
+        let loaded = GameStats::load_json(&path);
+        std::fs::remove_file(&path).ok();
+        let loaded = loaded.unwrap();
+
+        let notes = |stats: &GameStats| -> Vec<(usize, Option<String>)> {
+            stats
+                .get_annotated_moves()
+                .into_iter()
+                .map(|record| (record.move_number, record.annotation.clone()))
+                .collect()
+        };
+        assert_eq!(notes(&loaded), notes(&stats));
+        assert_eq!(notes(&loaded).len(), 1);
+        assert_eq!(notes(&loaded)[0].1.as_deref(), Some("悪手"));
+    }
+
+    #[test]
+    fn snapshots_match_replayed_boards() {
+        let replayed = sample_stats();
+
+        let mut stats = GameStats::new();
+        stats.set_record_snapshots(true);
+        let mut board = BitBoard::new();
+        for record in &replayed.moves {
+            if let Some((row, col)) = record.position {
+                assert!(board.make_move(rc_to_pos(row, col), record.player));
+            }
+            let (black_count, white_count) = board.count_all_discs();
+            stats.record_move(
+                record.player,
+                record.position,
+                Duration::ZERO,
+                black_count,
+                white_count,
+                None,
+            );
+            stats.record_snapshot(&board);
+        }
+
+        // スナップショットの無い記録は手順を再生して盤面を求める
+        assert!(replayed
+            .moves
+            .iter()
+            .all(|record| record.snapshot.is_none()));
+        for record in &stats.moves {
+            assert!(record.snapshot.is_some());
+            assert_eq!(record.snapshot, replayed.board_at_move(record.move_number));
+        }
+    }
+
+    #[test]
+    fn lead_changes_skip_ties_and_passes() {
+        let mut stats = GameStats::new();
+        let counts = [(4, 1), (3, 3), (2, 5), (5, 3), (5, 5), (4, 7), (8, 4)];
+        for (i, &(black, white)) in counts.iter().enumerate() {
+            let player = if i % 2 == 0 {
+                Player::Black
+            } else {
+                Player::White
+            };
+            stats.record_move(player, Some((i, 0)), Duration::ZERO, black, white, None);
+            if i == 3 {
+                // パスは石数が変わらないので数えない
+                stats.record_move(Player::Black, None, Duration::ZERO, black, white, None);
+            }
+        }
+
+        // 黒→(同数)→白→黒→(同数)→白→黒
+        assert_eq!(stats.lead_change_moves(), [3, 4, 6, 7]);
+        assert_eq!(stats.lead_changes(), 4);
+        assert_eq!(GameStats::new().lead_changes(), 0);
+    }
+
+    #[test]
+    fn agreed_draw_needs_consecutive_near_zero_evals() {
+        let rule = DrawAgreement {
+            consecutive_moves: 3,
+            eval_threshold: 20,
+        };
+        let mut stats = GameStats::new();
+        let mut reached = Vec::new();
+        for (i, eval) in [
+            Some(150),
+            Some(10),
+            Some(-15),
+            None,
+            Some(5),
+            Some(-20),
+            Some(0),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let player = if i % 2 == 0 {
+                Player::Black
+            } else {
+                Player::White
+            };
+            stats.record_move(player, Some((i, 0)), Duration::ZERO, 2, 2, eval);
+            reached.push(stats.agreed_draw_reached(&rule));
+        }
+
+        // 評価値の無い手（人間の手）で途切れ、その後に3手続いたところで引き分けになる
+        assert_eq!(reached, [false, false, false, false, false, false, true]);
+        assert!(!stats.agreed_draw_reached(&DrawAgreement::default()));
+    }
+
+    #[test]
+    fn evaluation_advantage_uses_each_movers_view() {
+        let mut stats = GameStats::new();
+        let moves = [
+            (Player::Black, Some(30)),
+            (Player::White, Some(10)),
+            (Player::Black, Some(90)),
+            (Player::White, None),
+            (Player::Black, Some(-60)),
+            (Player::White, Some(-20)),
+        ];
+        for (i, &(player, eval)) in moves.iter().enumerate() {
+            stats.record_move(player, Some((i, 0)), Duration::ZERO, 2, 2, eval);
+        }
+
+        assert_eq!(
+            stats.evaluation_advantage(Player::Black),
+            Some(EvalAdvantage {
+                average: 20.0,
+                peak: 90,
+                moves: 3
+            })
+        );
+        assert_eq!(
+            stats.evaluation_advantage(Player::White),
+            Some(EvalAdvantage {
+                average: -5.0,
+                peak: 10,
+                moves: 2
+            })
+        );
+        // 黒から見た値に揃えると白の評価値は符号が反転する
+        assert_eq!(stats.moves[1].normalized_evaluation(), Some(-10));
+        assert_eq!(GameStats::new().evaluation_advantage(Player::Black), None);
+    }
+
+    #[test]
+    fn replay_records_legal_moves_and_rejects_illegal_ones() {
+        let (board, stats) = replay_transcript("f5 d6 c3").unwrap();
+        assert_eq!(board.count_all_discs(), (5, 2));
+        assert_eq!(stats.moves_as_notation(), ["f5", "d6", "c3"]);
+        let counts: Vec<(u32, u32)> = stats
+            .moves
+            .iter()
+            .map(|m| (m.black_count, m.white_count))
+            .collect();
+        assert_eq!(counts, [(4, 1), (3, 3), (5, 2)]);
+
+        // 4手目の d5 は既に石があるので打てない
+        assert_eq!(
+            replay_transcript("f5d6c3d5").unwrap_err(),
+            ApplyError::IllegalMove {
+                index: 3,
+                notation: "d5".to_string()
+            }
+        );
+        assert_eq!(replay_transcript("f5 z9").unwrap_err().index(), 1);
+        assert!(matches!(
+            replay_transcript("f5 z9"),
+            Err(ApplyError::InvalidNotation { .. })
+        ));
+    }
+
+    #[test]
+    fn mobility_history_has_one_entry_per_placed_move() {
+        // パスを含む対局（9手目が黒のパス）
+        let (board, stats) = replay_transcript("e6f6g6g7c4h6h8f8c3").unwrap();
+        let placed = stats.moves.iter().filter(|m| m.position.is_some()).count();
+        assert_eq!(placed, 9);
+        assert!(stats.moves.len() > placed);
+
+        let history = stats.get_mobility_history();
+        assert_eq!(history.len(), placed);
+        let move_numbers: Vec<usize> = history.iter().map(|&(number, _, _)| number).collect();
+        assert_eq!(move_numbers, (1..=9).collect::<Vec<_>>());
+        // 最後の値は終わりの盤面の合法手の数
+        assert_eq!(
+            history.last().map(|&(_, black, white)| (black, white)),
+            Some((
+                board.legal_move_count(Player::Black),
+                board.legal_move_count(Player::White)
+            ))
+        );
+    }
+
+    #[test]
+    fn normalized_evaluations_follow_black() {
+        let mut stats = GameStats::new();
+        // 黒が +30 と見た後、白が -30（白から見て不利）と見るのは同じ形勢
+        stats.record_move(Player::Black, Some((2, 3)), Duration::ZERO, 4, 1, Some(30));
+        stats.record_move(Player::White, Some((2, 2)), Duration::ZERO, 3, 3, Some(-30));
+        stats.record_move(Player::Black, None, Duration::ZERO, 3, 3, None);
+        stats.record_move(Player::White, Some((4, 2)), Duration::ZERO, 2, 5, Some(80));
+
+        let raw: Vec<Option<i32>> = stats.moves.iter().map(|m| m.evaluation).collect();
+        let normalized: Vec<Option<i32>> = stats
+            .moves
+            .iter()
+            .map(MoveRecord::normalized_evaluation)
+            .collect();
+        assert_eq!(raw, [Some(30), Some(-30), None, Some(80)]);
+        assert_eq!(normalized, [Some(30), Some(30), None, Some(-80)]);
+
+        // グラフ用の推移はパスと評価値の無い手を除き、黒から見た1本の曲線になる
+        assert_eq!(
+            stats.get_normalized_evaluation_history(),
+            [
+                (1, Player::Black, 30),
+                (2, Player::White, 30),
+                (3, Player::White, -80)
+            ]
+        );
+    }
+
+    /// 記録を比べやすい形にする（手番・座標・石数）
+    fn record_summary(stats: &GameStats) -> Vec<(Player, Option<(usize, usize)>, u32, u32)> {
+        stats
+            .moves
+            .iter()
+            .map(|m| (m.player, m.position, m.black_count, m.white_count))
+            .collect()
+    }
+
+    #[test]
+    fn transcripts_are_built_from_the_move_notation() {
+        let (_, stats) = replay_transcript("f5 d6 c3").unwrap();
+        assert_eq!(stats.moves_as_notation(), ["f5", "d6", "c3"]);
+        assert_eq!(stats.to_transcript(), "f5d6c3");
+        assert_eq!(stats.transcript(), "f5 d6 c3");
+
+        // パスは区切りなしの棋譜には含めず、区切りありの棋譜には PASS_NOTATION で残す
+        let (_, stats) = replay_transcript("e6f6g6g7c4h6h8f8c3").unwrap();
+        assert_eq!(stats.moves_as_notation().len(), 9);
+        assert_eq!(stats.to_transcript(), "e6f6g6g7c4h6h8f8c3");
+        assert_eq!(
+            stats.transcript(),
+            format!("e6 f6 g6 g7 c4 h6 h8 f8 {} c3", PASS_NOTATION)
+        );
+    }
+
+    #[test]
+    fn forced_pass_round_trips_through_transcripts() {
+        // 8手目の後に黒は打てないので、棋譜に書かなくてもパスが記録される
+        let (board, stats) = replay_transcript("e6f6g6g7c4h6h8f8c3").unwrap();
+        assert_eq!(stats.moves.len(), 10);
+        assert_eq!(stats.moves[8].position, None);
+        assert_eq!(stats.moves[8].player, Player::Black);
+        assert!(stats.transcript().contains(PASS_NOTATION));
+
+        // パス付きの棋譜も、パスを省いた棋譜も、同じ盤面と記録に戻る
+        for transcript in [stats.transcript(), stats.to_transcript()] {
+            let (replayed_board, replayed) = replay_transcript(&transcript).unwrap();
+            assert_eq!(replayed_board, board);
+            assert_eq!(record_summary(&replayed), record_summary(&stats));
+            assert_eq!(replayed.transcript(), stats.transcript());
+        }
+
+        // 盤面の上でもパスは手として打って戻せる
+        let mut before_pass = BitBoard::new();
+        let moves = split_transcript("e6f6g6g7c4h6h8f8");
+        // apply_moves は黒のパスを補って、次に打つ白を返す
+        assert_eq!(
+            before_pass.apply_moves(&moves, Player::Black),
+            Ok(Player::White)
+        );
+        let mut passed = before_pass;
+        let undo = passed.pass(Player::Black).unwrap();
+        assert_eq!(passed, before_pass);
+        passed.undo_move(undo);
+        assert_eq!(passed, before_pass);
+        assert!(passed.pass(Player::White).is_none());
+
+        #[cfg(feature = "serde")]
+        {
+            let loaded = GameStats::from_bytes(&stats.to_bytes()).unwrap();
+            assert_eq!(record_summary(&loaded), record_summary(&stats));
+            assert_eq!(loaded.transcript(), stats.transcript());
         }
     }
 }
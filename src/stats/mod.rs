@@ -1,5 +1,20 @@
+pub mod archive_summary;
+pub mod format;
 pub mod game_stats;
+pub mod palette;
 pub mod plotter;
+pub mod review;
 
-pub use game_stats::{GameResult, GameStats};
-pub use plotter::plot_game_statistics;
+pub use archive_summary::ArchiveSummary;
+pub use format::format_duration;
+pub use game_stats::{
+    replay_transcript, DrawAgreement, GameResult, GameStats, ReplayError, Termination,
+};
+pub use palette::PlotPalette;
+#[cfg(feature = "serde")]
+pub use plotter::plot_archive_summary;
+pub use plotter::{
+    plot_game_statistics, plot_game_statistics_to, render_disc_count_png, render_evaluation_png,
+    render_mobility_png, render_overview_png, render_thinking_time_png, PlotError,
+};
+pub use review::{print_review, MoveReview, REVIEW_DEPTH};
@@ -0,0 +1,39 @@
+/// グラフの配色（PNG出力とGUI表示で共通）
+/// 色は (R, G, B) で保持し、各描画バックエンド側で変換する
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlotPalette {
+    pub black: (u8, u8, u8),         // 黒プレイヤーの系列
+    pub white: (u8, u8, u8),         // 白プレイヤーの系列
+    pub thinking_time: (u8, u8, u8), // 思考時間の系列
+    pub compute_time: (u8, u8, u8),  // 待ち時間を除いた計算時間の系列
+    pub average: (u8, u8, u8),       // 平均線
+    pub zero_line: (u8, u8, u8),     // ゼロライン
+}
+
+impl PlotPalette {
+    /// 標準の配色（黒は濃い灰色、白は明るい灰色）
+    pub const STANDARD: PlotPalette = PlotPalette {
+        black: (40, 40, 40),
+        white: (170, 170, 170),
+        thinking_time: (220, 50, 50),
+        compute_time: (50, 90, 200),
+        average: (40, 160, 60),
+        zero_line: (128, 128, 128),
+    };
+
+    /// 色覚多様性に配慮した配色（Okabe-Ito）
+    pub const COLORBLIND: PlotPalette = PlotPalette {
+        black: (0, 114, 178),
+        white: (230, 159, 0),
+        thinking_time: (204, 121, 167),
+        compute_time: (86, 180, 233),
+        average: (0, 158, 115),
+        zero_line: (128, 128, 128),
+    };
+}
+
+impl Default for PlotPalette {
+    fn default() -> Self {
+        PlotPalette::STANDARD
+    }
+}
@@ -1,44 +1,412 @@
-use crate::stats::{GameResult, GameStats};
+use crate::language::Language;
+use crate::stats::{
+    format_duration, ArchiveSummary, GameResult, GameStats, PlotPalette, Termination,
+};
 use chrono::Local;
+use image::codecs::png::PngEncoder;
+use image::{ColorType, ImageEncoder};
+use plotters::coord::Shift;
 use plotters::prelude::*;
 use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+/// グラフ生成時のエラー
+#[derive(Debug)]
+pub enum PlotError {
+    /// 出力ファイルの作成・書き込みに失敗
+    Io(std::io::Error),
+    /// 描画するデータ（手の記録）が無い
+    EmptyData,
+    /// 描画バックエンド（フォント・画像エンコード等）のエラー
+    Backend(String),
+    /// 複数のグラフを生成したうち、失敗したグラフの名前とその理由
+    Charts(Vec<(&'static str, PlotError)>),
+}
+
+impl fmt::Display for PlotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlotError::Io(e) => write!(f, "ファイル入出力エラー: {}", e),
+            PlotError::EmptyData => write!(f, "グラフにするデータがありません"),
+            PlotError::Backend(msg) => write!(f, "描画エラー: {}", msg),
+            PlotError::Charts(failures) => {
+                write!(f, "グラフ生成に失敗:")?;
+                for (name, e) in failures {
+                    write!(f, " {}（{}）", name, e)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for PlotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlotError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PlotError {
+    fn from(e: std::io::Error) -> Self {
+        PlotError::Io(e)
+    }
+}
+
+impl<E: Error + Send + Sync> From<DrawingAreaErrorKind<E>> for PlotError {
+    fn from(e: DrawingAreaErrorKind<E>) -> Self {
+        PlotError::Backend(e.to_string())
+    }
+}
+
+/// ゲーム統計のグラフをカレントディレクトリに `game_stats_{日時}_*.png` として生成する
+pub fn plot_game_statistics(stats: &GameStats, game_result: &GameResult) -> Result<(), PlotError> {
+    plot_game_statistics_to(stats, game_result, Path::new("."), None)
+}
 
-/// ゲーム統計のグラフを生成する
-pub fn plot_game_statistics(
+/// ゲーム統計のグラフを output_dir に生成する（無ければ作る）
+///
+/// ファイル名は `{prefix}_disc_count.png` などになる。prefix が None なら
+/// `game_stats_{日時}` を使う。
+/// グラフは並列に生成し、失敗したものがあれば PlotError::Charts にまとめて返す。
+pub fn plot_game_statistics_to(
     stats: &GameStats,
     game_result: &GameResult,
-) -> Result<(), Box<dyn Error>> {
-    // タイムスタンプ付きのファイル名を生成
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let base_filename = format!("game_stats_{}", timestamp);
-
-    // 各種グラフを生成
-    plot_disc_count_history(stats, &format!("{}_disc_count.png", base_filename))?;
-    plot_thinking_time_history(stats, &format!("{}_thinking_time.png", base_filename))?;
-    plot_evaluation_history(stats, &format!("{}_evaluation.png", base_filename))?;
-    plot_combined_overview(
-        stats,
-        game_result,
-        &format!("{}_overview.png", base_filename),
-    )?;
+    output_dir: &Path,
+    prefix: Option<&str>,
+) -> Result<(), PlotError> {
+    if stats.moves.is_empty() {
+        return Err(PlotError::EmptyData);
+    }
+
+    let palette = PlotPalette::default();
+
+    // 接頭辞が無ければタイムスタンプ付きのファイル名を生成
+    let base_filename = match prefix {
+        Some(prefix) => prefix.to_string(),
+        None => format!("game_stats_{}", Local::now().format("%Y%m%d_%H%M%S")),
+    };
+
+    std::fs::create_dir_all(output_dir)?;
+    let disc_filename = output_dir.join(format!("{}_disc_count.png", base_filename));
+    let time_filename = output_dir.join(format!("{}_thinking_time.png", base_filename));
+    let eval_filename = output_dir.join(format!("{}_evaluation.png", base_filename));
+    let mobility_filename = output_dir.join(format!("{}_mobility.png", base_filename));
+    let overview_filename = output_dir.join(format!("{}_overview.png", base_filename));
+
+    // 各グラフは独立したファイルに書き出すので並列に生成する
+    let (((disc_result, time_result), (eval_result, overview_result)), mobility_result) =
+        rayon::join(
+            || {
+                rayon::join(
+                    || {
+                        rayon::join(
+                            || plot_disc_count_history(stats, &palette, &disc_filename),
+                            || plot_thinking_time_history(stats, &palette, &time_filename),
+                        )
+                    },
+                    || {
+                        rayon::join(
+                            || plot_evaluation_history(stats, &palette, &eval_filename),
+                            || {
+                                plot_combined_overview(
+                                    stats,
+                                    game_result,
+                                    &palette,
+                                    &overview_filename,
+                                )
+                            },
+                        )
+                    },
+                )
+            },
+            || plot_mobility_history(stats, &palette, &mobility_filename),
+        );
+
+    // 失敗したグラフがあれば、すべてまとめて返す
+    let failures: Vec<(&'static str, PlotError)> = [
+        ("石数推移", disc_result),
+        ("思考時間", time_result),
+        ("評価値推移", eval_result),
+        ("合法手数推移", mobility_result),
+        ("総合グラフ", overview_result),
+    ]
+    .into_iter()
+    .filter_map(|(name, result)| Some((name, result.err()?)))
+    .collect();
+    if !failures.is_empty() {
+        return Err(PlotError::Charts(failures));
+    }
+
+    println!("\nグラフファイルを生成しました:");
+    println!("・石数推移: {}", disc_filename.display());
+    println!("・思考時間: {}", time_filename.display());
+    println!("・評価値推移: {}", eval_filename.display());
+    if !stats.get_mobility_history().is_empty() {
+        println!("・合法手数推移: {}", mobility_filename.display());
+    }
+    println!("・総合グラフ: {}", overview_filename.display());
+
+    Ok(())
+}
+
+/// 石数の推移グラフを PNG のバイト列として返す（ファイルには書き出さない）
+pub fn render_disc_count_png(stats: &GameStats) -> Result<Vec<u8>, PlotError> {
+    let palette = PlotPalette::default();
+    render_png(CHART_SIZE, |root| {
+        draw_disc_count_history(root, stats, &palette)
+    })
+}
+
+/// 思考時間の推移グラフを PNG のバイト列として返す
+pub fn render_thinking_time_png(stats: &GameStats) -> Result<Vec<u8>, PlotError> {
+    let palette = PlotPalette::default();
+    render_png(CHART_SIZE, |root| {
+        draw_thinking_time_history(root, stats, &palette)
+    })
+}
+
+/// 評価値の推移グラフを PNG のバイト列として返す
+pub fn render_evaluation_png(stats: &GameStats) -> Result<Vec<u8>, PlotError> {
+    let palette = PlotPalette::default();
+    render_png(CHART_SIZE, |root| {
+        draw_evaluation_history(root, stats, &palette)
+    })
+}
+
+/// 合法手の数の推移グラフを PNG のバイト列として返す
+pub fn render_mobility_png(stats: &GameStats) -> Result<Vec<u8>, PlotError> {
+    let palette = PlotPalette::default();
+    render_png(CHART_SIZE, |root| {
+        draw_mobility_history(root, stats, &palette)
+    })
+}
+
+/// 総合グラフを PNG のバイト列として返す
+pub fn render_overview_png(
+    stats: &GameStats,
+    game_result: &GameResult,
+) -> Result<Vec<u8>, PlotError> {
+    if stats.moves.is_empty() {
+        return Err(PlotError::EmptyData);
+    }
+
+    let palette = PlotPalette::default();
+    render_png(OVERVIEW_SIZE, |root| {
+        draw_combined_overview(root, stats, game_result, &palette)
+    })
+}
+
+/// 保存した対局の集計のグラフを output_dir に生成する（無ければ作る）
+///
+/// 色ごとの結果とプレイヤー別の勝率を `archive_summary_{日時}_*.png` として書き出す。
+pub fn plot_archive_summary(summary: &ArchiveSummary, output_dir: &Path) -> Result<(), PlotError> {
+    if summary.games == 0 {
+        return Err(PlotError::EmptyData);
+    }
 
+    let palette = PlotPalette::default();
+    let base_filename = format!("archive_summary_{}", Local::now().format("%Y%m%d_%H%M%S"));
+
+    std::fs::create_dir_all(output_dir)?;
+    let color_filename = output_dir.join(format!("{}_results_by_color.png", base_filename));
+    let player_filename = output_dir.join(format!("{}_win_rate_by_player.png", base_filename));
+
+    let results = [
+        ("黒勝ち".to_string(), summary.black_wins as f64),
+        ("白勝ち".to_string(), summary.white_wins as f64),
+        ("引き分け".to_string(), summary.draws as f64),
+    ];
+    plot_bar_chart(
+        &results,
+        "色ごとの結果",
+        "対局数",
+        summary.games as f64,
+        rgb(palette.black),
+        &color_filename,
+    )?;
     println!("\nグラフファイルを生成しました:");
-    println!("・石数推移: {}_disc_count.png", base_filename);
-    println!("・思考時間: {}_thinking_time.png", base_filename);
-    println!("・評価値推移: {}_evaluation.png", base_filename);
-    println!("・総合グラフ: {}_overview.png", base_filename);
+    println!("・色ごとの結果: {}", color_filename.display());
+
+    if !summary.players.is_empty() {
+        let win_rates: Vec<(String, f64)> = summary
+            .players
+            .iter()
+            .map(|record| (record.label.clone(), record.win_rate() * 100.0))
+            .collect();
+        plot_bar_chart(
+            &win_rates,
+            "プレイヤー別の勝率",
+            "勝率 (%)",
+            100.0,
+            rgb(palette.thinking_time),
+            &player_filename,
+        )?;
+        println!("・プレイヤー別の勝率: {}", player_filename.display());
+    }
+
+    Ok(())
+}
+
+/// (ラベル, 値) を棒グラフにする
+fn plot_bar_chart(
+    bars: &[(String, f64)],
+    caption: &str,
+    y_desc: &str,
+    y_max: f64,
+    color: RGBColor,
+    filename: &Path,
+) -> Result<(), PlotError> {
+    let root = BitMapBackend::new(filename, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(caption, ("sans-serif", 40))
+        .margin(10)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d((0..bars.len()).into_segmented(), 0.0..y_max.max(1.0))?;
 
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(bars.len())
+        .x_label_formatter(&|value| match value {
+            SegmentValue::CenterOf(i) => bars.get(*i).map_or(String::new(), |(l, _)| l.clone()),
+            _ => String::new(),
+        })
+        .y_desc(y_desc)
+        .draw()?;
+
+    chart.draw_series(
+        Histogram::vertical(&chart)
+            .style(color.filled())
+            .margin(20)
+            .data(bars.iter().enumerate().map(|(i, (_, value))| (i, *value))),
+    )?;
+
+    root.present()?;
     Ok(())
 }
 
+/// 配色の (R, G, B) を plotters の色に変換
+pub(crate) fn rgb(color: (u8, u8, u8)) -> RGBColor {
+    RGBColor(color.0, color.1, color.2)
+}
+
+/// 個別のグラフの画像サイズ
+const CHART_SIZE: (u32, u32) = (800, 600);
+/// 総合グラフの画像サイズ
+const OVERVIEW_SIZE: (u32, u32) = (1200, 800);
+
+/// メモリ上の RGB バッファに描画し、PNG にエンコードしたバイト列を返す
+fn render_png(
+    size: (u32, u32),
+    draw: impl FnOnce(&DrawingArea<BitMapBackend, Shift>) -> Result<(), PlotError>,
+) -> Result<Vec<u8>, PlotError> {
+    let mut buffer = vec![0u8; size.0 as usize * size.1 as usize * 3];
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, size).into_drawing_area();
+        draw(&root)?;
+        root.present()?;
+    }
+
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(&buffer, size.0, size.1, ColorType::Rgb8)
+        .map_err(|e| PlotError::Backend(e.to_string()))?;
+    Ok(png)
+}
+
+/// PNG をファイルに書き出す（描くデータが無いグラフはファイルを作らない）
+fn write_png(filename: &Path, png: Result<Vec<u8>, PlotError>) -> Result<(), PlotError> {
+    match png {
+        Ok(bytes) => Ok(std::fs::write(filename, bytes)?),
+        Err(PlotError::EmptyData) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn plot_disc_count_history(
+    stats: &GameStats,
+    palette: &PlotPalette,
+    filename: &Path,
+) -> Result<(), PlotError> {
+    write_png(
+        filename,
+        render_png(CHART_SIZE, |root| {
+            draw_disc_count_history(root, stats, palette)
+        }),
+    )
+}
+
+fn plot_mobility_history(
+    stats: &GameStats,
+    palette: &PlotPalette,
+    filename: &Path,
+) -> Result<(), PlotError> {
+    write_png(
+        filename,
+        render_png(CHART_SIZE, |root| {
+            draw_mobility_history(root, stats, palette)
+        }),
+    )
+}
+
+fn plot_thinking_time_history(
+    stats: &GameStats,
+    palette: &PlotPalette,
+    filename: &Path,
+) -> Result<(), PlotError> {
+    write_png(
+        filename,
+        render_png(CHART_SIZE, |root| {
+            draw_thinking_time_history(root, stats, palette)
+        }),
+    )
+}
+
+fn plot_evaluation_history(
+    stats: &GameStats,
+    palette: &PlotPalette,
+    filename: &Path,
+) -> Result<(), PlotError> {
+    write_png(
+        filename,
+        render_png(CHART_SIZE, |root| {
+            draw_evaluation_history(root, stats, palette)
+        }),
+    )
+}
+
+fn plot_combined_overview(
+    stats: &GameStats,
+    game_result: &GameResult,
+    palette: &PlotPalette,
+    filename: &Path,
+) -> Result<(), PlotError> {
+    write_png(
+        filename,
+        render_png(OVERVIEW_SIZE, |root| {
+            draw_combined_overview(root, stats, game_result, palette)
+        }),
+    )
+}
+
 /// 石数の推移グラフを作成
-fn plot_disc_count_history(stats: &GameStats, filename: &str) -> Result<(), Box<dyn Error>> {
+fn draw_disc_count_history(
+    root: &DrawingArea<BitMapBackend, Shift>,
+    stats: &GameStats,
+    palette: &PlotPalette,
+) -> Result<(), PlotError> {
     let disc_history = stats.get_disc_count_history();
     if disc_history.is_empty() {
-        return Ok(());
+        return Err(PlotError::EmptyData);
     }
 
-    let root = BitMapBackend::new(filename, (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
     let max_move = disc_history.iter().map(|(m, _, _)| *m).max().unwrap_or(1);
@@ -53,7 +421,7 @@ fn plot_disc_count_history(stats: &GameStats, filename: &str) -> Result<(), Box<
         .min()
         .unwrap_or(0);
 
-    let mut chart = ChartBuilder::on(&root)
+    let mut chart = ChartBuilder::on(root)
         .caption("石数の推移", ("sans-serif", 40))
         .margin(10)
         .x_label_area_size(50)
@@ -66,38 +434,104 @@ fn plot_disc_count_history(stats: &GameStats, filename: &str) -> Result<(), Box<
         .y_desc("石数")
         .draw()?;
 
+    let black_color = rgb(palette.black);
+    let white_color = rgb(palette.white);
+
     // 黒の石数
     chart
         .draw_series(LineSeries::new(
             disc_history.iter().map(|(m, b, _)| (*m, *b)),
-            &BLACK,
+            &black_color,
         ))?
         .label("黒")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLACK));
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], black_color));
 
     // 白の石数
     chart
         .draw_series(LineSeries::new(
             disc_history.iter().map(|(m, _, w)| (*m, *w)),
-            &BLUE,
+            &white_color,
         ))?
         .label("白")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], white_color));
 
     chart.configure_series_labels().draw()?;
-    root.present()?;
+    Ok(())
+}
+
+/// 合法手の数の推移グラフを作成
+fn draw_mobility_history(
+    root: &DrawingArea<BitMapBackend, Shift>,
+    stats: &GameStats,
+    palette: &PlotPalette,
+) -> Result<(), PlotError> {
+    let mobility_history = stats.get_mobility_history();
+    if mobility_history.is_empty() {
+        return Err(PlotError::EmptyData);
+    }
+
+    root.fill(&WHITE)?;
+
+    let max_move = mobility_history
+        .iter()
+        .map(|(m, _, _)| *m)
+        .max()
+        .unwrap_or(1);
+    let max_mobility = mobility_history
+        .iter()
+        .map(|(_, b, w)| (*b).max(*w))
+        .max()
+        .unwrap_or(10);
+
+    let mut chart = ChartBuilder::on(root)
+        .caption("合法手の数の推移", ("sans-serif", 40))
+        .margin(10)
+        .x_label_area_size(50)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0..max_move, 0..(max_mobility + 2))?;
+
+    chart
+        .configure_mesh()
+        .x_desc("手数")
+        .y_desc("合法手の数")
+        .draw()?;
+
+    let black_color = rgb(palette.black);
+    let white_color = rgb(palette.white);
+
+    // 黒の合法手の数
+    chart
+        .draw_series(LineSeries::new(
+            mobility_history.iter().map(|(m, b, _)| (*m, *b)),
+            &black_color,
+        ))?
+        .label("黒")
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], black_color));
+
+    // 白の合法手の数
+    chart
+        .draw_series(LineSeries::new(
+            mobility_history.iter().map(|(m, _, w)| (*m, *w)),
+            &white_color,
+        ))?
+        .label("白")
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], white_color));
 
+    chart.configure_series_labels().draw()?;
     Ok(())
 }
 
 /// 思考時間の推移グラフを作成
-fn plot_thinking_time_history(stats: &GameStats, filename: &str) -> Result<(), Box<dyn Error>> {
+fn draw_thinking_time_history(
+    root: &DrawingArea<BitMapBackend, Shift>,
+    stats: &GameStats,
+    palette: &PlotPalette,
+) -> Result<(), PlotError> {
     let time_history = stats.get_thinking_time_history();
     if time_history.is_empty() {
-        return Ok(());
+        return Err(PlotError::EmptyData);
     }
 
-    let root = BitMapBackend::new(filename, (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
     let max_move = time_history.iter().map(|(m, _)| *m).max().unwrap_or(1);
@@ -111,7 +545,7 @@ fn plot_thinking_time_history(stats: &GameStats, filename: &str) -> Result<(), B
         .fold(f64::INFINITY, |a, b| a.min(b))
         .max(0.0);
 
-    let mut chart = ChartBuilder::on(&root)
+    let mut chart = ChartBuilder::on(root)
         .caption("思考時間の推移", ("sans-serif", 40))
         .margin(10)
         .x_label_area_size(50)
@@ -127,37 +561,40 @@ fn plot_thinking_time_history(stats: &GameStats, filename: &str) -> Result<(), B
     // 思考時間の折れ線グラフ
     chart.draw_series(LineSeries::new(
         time_history.iter().map(|(m, t)| (*m, *t)),
-        &RED,
+        &rgb(palette.thinking_time),
     ))?;
 
     // 平均線を追加
     if !time_history.is_empty() {
+        let average_color = rgb(palette.average);
         let avg_time: f64 =
             time_history.iter().map(|(_, t)| t).sum::<f64>() / time_history.len() as f64;
         chart
             .draw_series(LineSeries::new(
                 vec![(0, avg_time), (max_move, avg_time)],
-                GREEN.stroke_width(2),
+                average_color.stroke_width(2),
             ))?
             .label(format!("平均: {:.2}秒", avg_time))
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &GREEN));
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], average_color));
 
         chart.configure_series_labels().draw()?;
     }
 
-    root.present()?;
-
     Ok(())
 }
 
 /// 評価値の推移グラフを作成
-fn plot_evaluation_history(stats: &GameStats, filename: &str) -> Result<(), Box<dyn Error>> {
-    let eval_history = stats.get_evaluation_history();
+fn draw_evaluation_history(
+    root: &DrawingArea<BitMapBackend, Shift>,
+    stats: &GameStats,
+    palette: &PlotPalette,
+) -> Result<(), PlotError> {
+    // 黒と白で基準を揃えるため、黒から見た値で描く（正なら黒が優勢）
+    let eval_history = stats.get_normalized_evaluation_history();
     if eval_history.is_empty() {
-        return Ok(());
+        return Err(PlotError::EmptyData);
     }
 
-    let root = BitMapBackend::new(filename, (800, 600)).into_drawing_area();
     root.fill(&WHITE)?;
 
     let max_move = eval_history.iter().map(|(m, _, _)| *m).max().unwrap_or(1);
@@ -170,7 +607,7 @@ fn plot_evaluation_history(stats: &GameStats, filename: &str) -> Result<(), Box<
 
     let margin = (max_eval - min_eval).max(100) / 10;
 
-    let mut chart = ChartBuilder::on(&root)
+    let mut chart = ChartBuilder::on(root)
         .caption("AI評価値の推移", ("sans-serif", 40))
         .margin(10)
         .x_label_area_size(50)
@@ -180,12 +617,15 @@ fn plot_evaluation_history(stats: &GameStats, filename: &str) -> Result<(), Box<
     chart
         .configure_mesh()
         .x_desc("手数")
-        .y_desc("評価値")
+        .y_desc("評価値（黒から見た値）")
         .draw()?;
 
     // プレイヤー別に色分け
     use crate::player::Player;
 
+    let black_color = rgb(palette.black);
+    let white_color = rgb(palette.white);
+
     let black_moves: Vec<_> = eval_history
         .iter()
         .filter(|(_, player, _)| *player == Player::Black)
@@ -200,37 +640,35 @@ fn plot_evaluation_history(stats: &GameStats, filename: &str) -> Result<(), Box<
 
     if !black_moves.is_empty() {
         chart
-            .draw_series(LineSeries::new(black_moves, &BLACK))?
+            .draw_series(LineSeries::new(black_moves, &black_color))?
             .label("黒AI評価値")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLACK));
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], black_color));
     }
 
     if !white_moves.is_empty() {
         chart
-            .draw_series(LineSeries::new(white_moves, &BLUE))?
+            .draw_series(LineSeries::new(white_moves, &white_color))?
             .label("白AI評価値")
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], &BLUE));
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], white_color));
     }
 
     // ゼロラインを追加
     chart.draw_series(LineSeries::new(
         vec![(0, 0), (max_move, 0)],
-        RGBColor(128, 128, 128).stroke_width(1),
+        rgb(palette.zero_line).stroke_width(1),
     ))?;
 
     chart.configure_series_labels().draw()?;
-    root.present()?;
-
     Ok(())
 }
 
 /// 総合概要グラフを作成（複数のサブプロットを含む）
-fn plot_combined_overview(
+fn draw_combined_overview(
+    root: &DrawingArea<BitMapBackend, Shift>,
     stats: &GameStats,
     game_result: &GameResult,
-    filename: &str,
-) -> Result<(), Box<dyn Error>> {
-    let root = BitMapBackend::new(filename, (1200, 800)).into_drawing_area();
+    palette: &PlotPalette,
+) -> Result<(), PlotError> {
     root.fill(&WHITE)?;
 
     let areas = root.split_evenly((2, 1));
@@ -241,22 +679,22 @@ fn plot_combined_overview(
     let upper_right = &upper_areas[1];
 
     // 上左: 石数推移
-    plot_disc_overview(&upper_left, stats)?;
+    plot_disc_overview(upper_left, stats, palette)?;
 
     // 上右: 思考時間
-    plot_thinking_time_overview(&upper_right, stats)?;
+    plot_thinking_time_overview(upper_right, stats, palette)?;
 
     // 下: ゲーム結果サマリー
-    plot_game_summary(&lower, game_result)?;
+    plot_game_summary(lower, game_result)?;
 
-    root.present()?;
     Ok(())
 }
 
 fn plot_disc_overview(
-    area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
+    area: &DrawingArea<BitMapBackend, Shift>,
     stats: &GameStats,
-) -> Result<(), Box<dyn Error>> {
+    palette: &PlotPalette,
+) -> Result<(), PlotError> {
     let disc_history = stats.get_disc_count_history();
     if disc_history.is_empty() {
         return Ok(());
@@ -289,25 +727,37 @@ fn plot_disc_overview(
         .y_desc("石数")
         .draw()?;
 
+    let black_color = rgb(palette.black);
+    let white_color = rgb(palette.white);
+
     // 黒の石数
-    chart.draw_series(LineSeries::new(
-        disc_history.iter().map(|(m, b, _)| (*m, *b)),
-        &BLACK,
-    ))?;
+    chart
+        .draw_series(LineSeries::new(
+            disc_history.iter().map(|(m, b, _)| (*m, *b)),
+            &black_color,
+        ))?
+        .label("黒")
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], black_color));
 
     // 白の石数
-    chart.draw_series(LineSeries::new(
-        disc_history.iter().map(|(m, _, w)| (*m, *w)),
-        &BLUE,
-    ))?;
+    chart
+        .draw_series(LineSeries::new(
+            disc_history.iter().map(|(m, _, w)| (*m, *w)),
+            &white_color,
+        ))?
+        .label("白")
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 10, y)], white_color));
+
+    chart.configure_series_labels().draw()?;
 
     Ok(())
 }
 
 fn plot_thinking_time_overview(
-    area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
+    area: &DrawingArea<BitMapBackend, Shift>,
     stats: &GameStats,
-) -> Result<(), Box<dyn Error>> {
+    palette: &PlotPalette,
+) -> Result<(), PlotError> {
     let time_history = stats.get_thinking_time_history();
     if time_history.is_empty() {
         return Ok(());
@@ -338,31 +788,32 @@ fn plot_thinking_time_overview(
     // 思考時間の折れ線グラフ
     chart.draw_series(LineSeries::new(
         time_history.iter().map(|(m, t)| (*m, *t)),
-        &RED,
+        &rgb(palette.thinking_time),
     ))?;
 
     Ok(())
 }
 
 fn plot_game_summary(
-    area: &DrawingArea<BitMapBackend, plotters::coord::Shift>,
+    area: &DrawingArea<BitMapBackend, Shift>,
     game_result: &GameResult,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), PlotError> {
     area.fill(&WHITE)?;
 
     // ゲーム結果のテキスト表示
     let winner_text = match game_result.winner {
         Some(crate::player::Player::Black) => "勝者: 黒",
         Some(crate::player::Player::White) => "勝者: 白",
+        None if game_result.termination == Termination::AgreedDraw => "合意により引き分け",
         None => "引き分け",
     };
 
     let text_style = ("sans-serif", 30);
 
     area.draw(&Text::new(
-        format!("{}", winner_text),
+        winner_text.to_string(),
         (50, 50),
-        &text_style.into_font().color(&BLACK),
+        text_style.into_font().color(&BLACK),
     ))?;
 
     area.draw(&Text::new(
@@ -371,23 +822,119 @@ fn plot_game_summary(
             game_result.black_final_count, game_result.white_final_count
         ),
         (50, 100),
-        &text_style.into_font().color(&BLACK),
+        text_style.into_font().color(&BLACK),
     ))?;
 
     area.draw(&Text::new(
         format!("総手数: {}", game_result.total_moves),
         (50, 150),
-        &text_style.into_font().color(&BLACK),
+        text_style.into_font().color(&BLACK),
     ))?;
 
     area.draw(&Text::new(
         format!(
-            "ゲーム時間: {:.1}秒",
-            game_result.game_duration.as_secs_f64()
+            "ゲーム時間: {}",
+            format_duration(game_result.game_duration, Language::Japanese)
         ),
         (50, 200),
-        &text_style.into_font().color(&BLACK),
+        text_style.into_font().color(&BLACK),
     ))?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_graphs::sample_game;
+
+    const PNG_MAGIC: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    #[test]
+    fn rendered_charts_are_png_bytes() {
+        let (mut stats, game_result) = sample_game();
+        stats.moves[0].mobility = Some((5, 4));
+
+        for png in [
+            render_disc_count_png(&stats),
+            render_thinking_time_png(&stats),
+            render_evaluation_png(&stats),
+            render_mobility_png(&stats),
+            render_overview_png(&stats, &game_result),
+        ] {
+            let png = png.unwrap();
+            assert!(png.len() > PNG_MAGIC.len());
+            assert_eq!(png[..8], PNG_MAGIC);
+        }
+    }
+
+    #[test]
+    fn rendering_empty_stats_is_empty_data() {
+        let (_, game_result) = sample_game();
+        let stats = GameStats::new();
+        assert!(matches!(
+            render_disc_count_png(&stats),
+            Err(PlotError::EmptyData)
+        ));
+        assert!(matches!(
+            render_overview_png(&stats, &game_result),
+            Err(PlotError::EmptyData)
+        ));
+    }
+
+    #[test]
+    fn archive_summary_charts_are_written_to_output_dir() {
+        let (stats, _) = sample_game();
+        let summary = ArchiveSummary::from_games(&[stats], None);
+        let output_dir =
+            std::env::temp_dir().join(format!("bitothello_archive_plot_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&output_dir);
+
+        let result = plot_archive_summary(&summary, &output_dir);
+        let written: Vec<String> = std::fs::read_dir(&output_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let _ = std::fs::remove_dir_all(&output_dir);
+
+        result.unwrap();
+        assert_eq!(written.len(), 1 + usize::from(!summary.players.is_empty()));
+        assert!(written
+            .iter()
+            .any(|name| name.ends_with("_results_by_color.png")));
+
+        let empty = ArchiveSummary::from_games(&[], None);
+        assert!(matches!(
+            plot_archive_summary(&empty, &output_dir),
+            Err(PlotError::EmptyData)
+        ));
+    }
+
+    #[test]
+    fn failed_charts_are_collected() {
+        let (stats, game_result) = sample_game();
+        let output_dir =
+            std::env::temp_dir().join(format!("bitothello_plot_errors_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&output_dir);
+        // 同じ名前のディレクトリがあるとそのグラフだけ書き出せない
+        std::fs::create_dir_all(output_dir.join("sample_disc_count.png")).unwrap();
+        std::fs::create_dir_all(output_dir.join("sample_overview.png")).unwrap();
+
+        let result = plot_game_statistics_to(&stats, &game_result, &output_dir, Some("sample"));
+        let evaluation_written = output_dir.join("sample_evaluation.png").is_file();
+        let _ = std::fs::remove_dir_all(&output_dir);
+
+        match result {
+            Err(PlotError::Charts(failures)) => {
+                let names: Vec<&str> = failures.iter().map(|(name, _)| *name).collect();
+                assert_eq!(names, ["石数推移", "総合グラフ"]);
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(evaluation_written);
+    }
+}
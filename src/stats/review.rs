@@ -0,0 +1,187 @@
+use crate::board::{notation, rc_to_pos};
+use crate::language::Language;
+use crate::player::Player;
+use crate::stats::GameStats;
+
+/// 対局後の検討で使う解析の深さ
+pub const REVIEW_DEPTH: usize = 4;
+
+// 評価値の損失がこれ以下なら「好手」、これ以下なら「疑問手」、それを超えると「悪手」
+const GOOD_MAX_LOSS: i32 = 50;
+const INACCURACY_MAX_LOSS: i32 = 150;
+
+/// 最善手と比べた手の良し悪し
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveQuality {
+    Best,       // 最善手と同じ評価
+    Good,       // 少しだけ損をした
+    Inaccuracy, // はっきり損をした
+    Blunder,    // 大きく損をした
+}
+
+impl MoveQuality {
+    /// 評価値の損失（最善手の評価値 − 打った手の評価値）から分類する
+    pub fn from_eval_loss(loss: i32) -> Self {
+        if loss <= 0 {
+            MoveQuality::Best
+        } else if loss <= GOOD_MAX_LOSS {
+            MoveQuality::Good
+        } else if loss <= INACCURACY_MAX_LOSS {
+            MoveQuality::Inaccuracy
+        } else {
+            MoveQuality::Blunder
+        }
+    }
+
+    /// 検討結果に表示する名前
+    pub fn label(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (MoveQuality::Best, Language::Japanese) => "最善",
+            (MoveQuality::Best, Language::English) => "Best",
+            (MoveQuality::Good, Language::Japanese) => "好手",
+            (MoveQuality::Good, Language::English) => "Good",
+            (MoveQuality::Inaccuracy, Language::Japanese) => "疑問手",
+            (MoveQuality::Inaccuracy, Language::English) => "Inaccuracy",
+            (MoveQuality::Blunder, Language::Japanese) => "悪手",
+            (MoveQuality::Blunder, Language::English) => "Blunder",
+        }
+    }
+}
+
+/// 1手の検討結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveReview {
+    pub move_number: usize,
+    pub player: Player,
+    pub played: usize,    // 打った手（0〜63）
+    pub best_move: usize, // エンジンの最善手
+    pub eval_loss: i32,   // 最善手と比べて失った評価値（0以上）
+    pub quality: MoveQuality,
+}
+
+impl MoveReview {
+    /// 打った手の棋譜表記
+    pub fn played_notation(&self) -> String {
+        notation(self.played)
+    }
+
+    /// 最善手の棋譜表記
+    pub fn best_notation(&self) -> String {
+        notation(self.best_move)
+    }
+}
+
+impl GameStats {
+    /// 指定したプレイヤー（通常は人間）の手を、記録した局面を再生してエンジンの最善手と比べる
+    /// 手順を再生できなくなったらそこで打ち切る
+    pub fn review_moves(&self, reviewed: &[Player], depth: usize) -> Vec<MoveReview> {
+        let mut reviews = Vec::new();
+        let mut board = self.start_position;
+
+        for record in &self.moves {
+            let Some((row, col)) = record.position else {
+                continue;
+            };
+            let played = rc_to_pos(row, col);
+
+            if reviewed.contains(&record.player) {
+                // 同じ深さで全合法手を読み、最善手と打った手の評価値を比べる
+                let scores = board.analyze_multipv(record.player, depth);
+                let played_score = scores.iter().find(|&&(pos, _)| pos == played);
+                if let (Some(&(best_move, best_score)), Some(&(_, played_score))) =
+                    (scores.first(), played_score)
+                {
+                    let eval_loss = best_score.saturating_sub(played_score).max(0);
+                    reviews.push(MoveReview {
+                        move_number: record.move_number,
+                        player: record.player,
+                        played,
+                        best_move,
+                        eval_loss,
+                        quality: MoveQuality::from_eval_loss(eval_loss),
+                    });
+                }
+            }
+
+            if !board.make_move(played, record.player) {
+                break;
+            }
+        }
+
+        reviews
+    }
+}
+
+/// 検討結果を表にして表示する
+pub fn print_review(reviews: &[MoveReview]) {
+    if reviews.is_empty() {
+        return;
+    }
+
+    println!("\n==========================");
+    println!("      手の検討 (深さ{})", REVIEW_DEPTH);
+    println!("==========================");
+    println!("手数  手番  着手  最善手  損失  評価");
+    for review in reviews {
+        println!(
+            "{:>4}  {}  {:<4}  {:<6}  {:>4}  {}",
+            review.move_number,
+            review.player.to_string(),
+            review.played_notation(),
+            review.best_notation(),
+            review.eval_loss,
+            review.quality.label(Language::Japanese)
+        );
+    }
+
+    for quality in [MoveQuality::Inaccuracy, MoveQuality::Blunder] {
+        let count = reviews.iter().filter(|r| r.quality == quality).count();
+        println!("・{}: {}回", quality.label(Language::Japanese), count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::replay_transcript;
+
+    #[test]
+    fn eval_loss_thresholds_classify_moves() {
+        let cases = [
+            (-10, MoveQuality::Best),
+            (0, MoveQuality::Best),
+            (1, MoveQuality::Good),
+            (GOOD_MAX_LOSS, MoveQuality::Good),
+            (GOOD_MAX_LOSS + 1, MoveQuality::Inaccuracy),
+            (INACCURACY_MAX_LOSS, MoveQuality::Inaccuracy),
+            (INACCURACY_MAX_LOSS + 1, MoveQuality::Blunder),
+            (i32::MAX, MoveQuality::Blunder),
+        ];
+        for (loss, quality) in cases {
+            assert_eq!(MoveQuality::from_eval_loss(loss), quality, "損失 {}", loss);
+        }
+
+        assert_eq!(MoveQuality::Blunder.label(Language::Japanese), "悪手");
+        assert_eq!(MoveQuality::Blunder.label(Language::English), "Blunder");
+    }
+
+    #[test]
+    fn review_labels_match_eval_loss() {
+        let (_, stats) = replay_transcript("f5d6c3d3c4f4f6f3e6e7").unwrap();
+        let reviews = stats.review_moves(&[Player::Black], 2);
+
+        // 黒の5手すべてが検討される
+        assert_eq!(reviews.len(), 5);
+        for review in &reviews {
+            assert_eq!(review.player, Player::Black);
+            assert!(review.eval_loss >= 0);
+            assert_eq!(
+                review.quality,
+                MoveQuality::from_eval_loss(review.eval_loss)
+            );
+            if review.played == review.best_move {
+                assert_eq!(review.quality, MoveQuality::Best);
+            }
+        }
+    }
+}
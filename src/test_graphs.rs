@@ -1,11 +1,47 @@
 use crate::player::Player;
-use crate::stats::{GameResult, GameStats};
+use crate::stats::{GameResult, GameStats, PlotError, Termination};
 use std::time::Duration;
 
 /// テスト用のサンプルデータでグラフを生成する
 pub fn generate_test_graphs() -> Result<(), Box<dyn std::error::Error>> {
     println!("テスト用グラフを生成中...");
 
+    let (stats, game_result) = sample_game();
+
+    // グラフを生成
+    match crate::stats::plot_game_statistics(&stats, &game_result) {
+        Ok(()) => {
+            println!("✓ テストグラフが正常に生成されました！");
+            println!("生成されたファイル:");
+
+            // 生成されたファイルを確認
+            use std::fs;
+            let current_dir = std::env::current_dir()?;
+            let entries = fs::read_dir(&current_dir)?;
+
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                if let Some(extension) = path.extension() {
+                    if extension == "png" {
+                        if let Some(filename) = path.file_name() {
+                            println!("  - {}", filename.to_string_lossy());
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+        Err(e) => {
+            println!("❌ グラフ生成でエラーが発生しました: {}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// 短いゲームを模したサンプルの統計と結果
+pub(crate) fn sample_game() -> (GameStats, GameResult) {
     // サンプルゲーム統計を作成
     let mut stats = GameStats::new();
 
@@ -53,38 +89,51 @@ pub fn generate_test_graphs() -> Result<(), Box<dyn std::error::Error>> {
         total_moves: 20,
         game_duration: Duration::from_secs(15),
         total_thinking_time: Duration::from_secs(12),
+        termination: Termination::Normal,
     };
 
-    // グラフを生成
-    match crate::stats::plot_game_statistics(&stats, &game_result) {
-        Ok(()) => {
-            println!("✓ テストグラフが正常に生成されました！");
-            println!("生成されたファイル:");
+    (stats, game_result)
+}
 
-            // 生成されたファイルを確認
-            use std::fs;
-            let current_dir = std::env::current_dir()?;
-            let entries = fs::read_dir(&current_dir)?;
+/// 指定したディレクトリ（無ければ作る）に接頭辞付きの4つのグラフが書き出されることを確認する
+pub fn check_output_dir_and_prefix() -> bool {
+    let (stats, game_result) = sample_game();
+    let output_dir = std::env::temp_dir().join(format!("bitothello_plots_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&output_dir);
 
-            for entry in entries {
-                let entry = entry?;
-                let path = entry.path();
-                if let Some(extension) = path.extension() {
-                    if extension == "png" {
-                        if let Some(filename) = path.file_name() {
-                            println!("  - {}", filename.to_string_lossy());
-                        }
-                    }
-                }
-            }
+    let written =
+        crate::stats::plot_game_statistics_to(&stats, &game_result, &output_dir, Some("sample"))
+            .is_ok();
+    let all_present = [
+        "sample_disc_count.png",
+        "sample_thinking_time.png",
+        "sample_evaluation.png",
+        "sample_overview.png",
+    ]
+    .iter()
+    .all(|name| output_dir.join(name).is_file());
 
-            Ok(())
-        }
-        Err(e) => {
-            println!("❌ グラフ生成でエラーが発生しました: {}", e);
-            Err(e)
-        }
-    }
+    let _ = std::fs::remove_dir_all(&output_dir);
+    written && all_present
+}
+
+/// 手の記録が無い統計では空画像を書き出さず EmptyData を返すことを確認する
+pub fn check_empty_stats_error() -> bool {
+    let stats = GameStats::new();
+    let game_result = GameResult {
+        winner: None,
+        black_final_count: 2,
+        white_final_count: 2,
+        total_moves: 0,
+        game_duration: Duration::from_secs(0),
+        total_thinking_time: Duration::from_secs(0),
+        termination: Termination::Normal,
+    };
+
+    matches!(
+        crate::stats::plot_game_statistics(&stats, &game_result),
+        Err(PlotError::EmptyData)
+    )
 }
 
 /// メイン関数から呼び出されるテスト実行関数
@@ -97,4 +146,31 @@ pub fn run_graph_test() {
         Ok(()) => println!("テスト完了: グラフファイルを確認してください"),
         Err(e) => println!("テスト失敗: {}", e),
     }
+
+    if check_empty_stats_error() {
+        println!("✓ 空の統計では EmptyData エラーになりました");
+    } else {
+        println!("❌ 空の統計で EmptyData エラーになりませんでした");
+    }
+
+    if check_output_dir_and_prefix() {
+        println!("✓ 指定したディレクトリに接頭辞付きのグラフが書き出されました");
+    } else {
+        println!("❌ 指定したディレクトリにグラフが書き出されませんでした");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_charts_are_written_to_output_dir() {
+        assert!(check_output_dir_and_prefix());
+    }
+
+    #[test]
+    fn empty_stats_are_an_error() {
+        assert!(check_empty_stats_error());
+    }
 }
@@ -0,0 +1,58 @@
+//! 公開されている make_move_undo / undo_move だけで書いた探索が、組み込みの探索と一致するか確かめる
+
+use bitothello::ai::EngineConfig;
+use bitothello::{BitBoard, Player};
+
+/// 盤面をコピーせずに打って戻すだけの素朴な negamax（末端は static_eval）
+fn negamax(board: &mut BitBoard, player: Player, depth: usize) -> i32 {
+    if depth == 0 || board.is_game_over() {
+        return board.static_eval(player);
+    }
+
+    let moves: Vec<usize> = board.legal_moves_iter(player).collect();
+    if moves.is_empty() {
+        let undo = board.pass(player).unwrap();
+        let score = -negamax(board, player.opponent(), depth - 1);
+        board.undo_move(undo);
+        return score;
+    }
+
+    let mut best = i32::MIN + 1;
+    for pos in moves {
+        let undo = board.make_move_undo(pos, player).unwrap();
+        best = best.max(-negamax(board, player.opponent(), depth - 1));
+        board.undo_move(undo);
+    }
+    best
+}
+
+#[test]
+fn make_unmake_negamax_agrees_with_builtin_search() {
+    // 前向き枝刈りを切り、時間で打ち切らないようにすれば、組み込みの探索も素朴な negamax と同じ値になる
+    let config = EngineConfig {
+        forward_pruning: false,
+        max_nodes: Some(u64::MAX),
+        ..EngineConfig::default()
+    };
+
+    let mut board = BitBoard::new();
+    let opening = ["f5", "d6", "c3", "d3", "c4", "f4", "f6", "f3", "e6", "e7"];
+    let player = board.apply_moves(&opening, Player::Black).unwrap();
+    let start = board;
+
+    for depth in 2..=4 {
+        let analysis = board.analyze_with_config(player, depth, &config);
+        let best_score = negamax(&mut board, player, depth);
+        assert_eq!(board, start, "打った手がすべて戻されていない");
+        assert_eq!(analysis.score, Some(best_score), "深さ{}", depth);
+
+        // 組み込みの探索が選んだ手は、素朴な negamax でも最善の値になる
+        let best_move = analysis.best_move.unwrap();
+        let undo = board.make_move_undo(best_move, player).unwrap();
+        assert_eq!(
+            -negamax(&mut board, player.opponent(), depth - 1),
+            best_score
+        );
+        board.undo_move(undo);
+    }
+}
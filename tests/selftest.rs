@@ -0,0 +1,13 @@
+//! `--selftest` のチェックをテストとしても実行する
+
+use bitothello::selftest;
+
+#[test]
+fn every_check_passes() {
+    // CHECKS のすべてを実行するので、チェックを追加すればここでも実行される
+    let failures: Vec<String> = selftest::CHECKS
+        .iter()
+        .filter_map(|(name, check)| check().err().map(|reason| format!("{}: {}", name, reason)))
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}